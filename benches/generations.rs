@@ -0,0 +1,117 @@
+//! Measures how many generations per second `GameOfLife::compute_n` and `PackedGameOfLife::compute_n` run at.
+//!
+//! Run with `cargo bench --bench generations`, optionally followed by `-- [texture|packed|sparse] <size>...` to
+//! pick the storage and board sizes (defaults to all of them, at 1024, 4096, 8192 and 32768).
+//!
+//! `texture` and `packed` time full boards. `sparse` times a small soup in the middle of a texture board,
+//! with and without tile tracking, and how much faster tracking is. The soup settles with tracking on, since
+//! settling an 8192x8192 board without it takes hours on a software renderer like llvmpipe.
+
+use instant::{Duration, Instant};
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::GameOfLife, packed_board::PackedGameOfLife, rule::Rule, topology::Topology};
+
+/// How long each board size is timed for, roughly
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+/// Width and height of the soup in sparse boards
+const SOUP_SIZE: u32 = 256;
+/// Generations sparse boards run for before being timed, so the soup has spread out. The same board is timed
+/// with tile tracking and then without
+const SETTLE_GENERATIONS: u64 = 1000;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let sizes: Vec<u32> = args.iter().filter_map(|arg| arg.parse().ok()).collect();
+    let sizes = if sizes.is_empty() { vec![1024, 4096, 8192, 32768] } else { sizes };
+    let picked = |storage: &str| args.iter().any(|arg| arg == storage);
+    let all = !picked("texture") && !picked("packed") && !picked("sparse");
+    let (texture, packed, sparse) = (all || picked("texture"), all || picked("packed"), all || picked("sparse"));
+
+    let instance = wgpu::Instance::new(wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all));
+    let adapter = futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        ..Default::default()
+    })).expect("no GPU adapter available");
+    let (device, queue) = futures::executor::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: Some("Benchmark device"),
+        features: wgpu::Features::empty(),
+        limits: adapter.limits(),
+    }, None)).expect("failed to request device");
+    println!("{:?}", adapter.get_info());
+
+    for size in sizes {
+        if texture {
+            match GameOfLife::check_size(size, size, &device.limits()) {
+                Ok(()) => {
+                    let mut game_of_life = GameOfLife::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, size, size, Rule::CONWAY, Topology::PLANE, 1.0, 1.0);
+                    game_of_life.construct(&queue);
+                    // An empty board would never wake a tile
+                    game_of_life.set_tile_tracking(&queue, false);
+                    measure("texture", size, &device, |n| game_of_life.compute_n(&device, &queue, n));
+                },
+                Err(err) => println!("texture {0}x{0}: skipped, {1}", size, err),
+            }
+        }
+        if packed {
+            match PackedGameOfLife::check_size(size, size, &device.limits()) {
+                Ok(()) => {
+                    let mut game_of_life = PackedGameOfLife::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, size, size, Rule::CONWAY, Topology::PLANE, 1.0, 1.0);
+                    game_of_life.construct(&queue);
+                    measure("packed", size, &device, |n| game_of_life.compute_n(&device, &queue, n));
+                },
+                Err(err) => println!("packed {0}x{0}: skipped, {1}", size, err),
+            }
+        }
+        if sparse {
+            match GameOfLife::check_size(size, size, &device.limits()) {
+                Ok(()) => {
+                    let mut game_of_life = GameOfLife::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, size, size, Rule::CONWAY, Topology::PLANE, 1.0, 1.0);
+                    game_of_life.construct(&queue);
+                    game_of_life.write_board(&queue, &sparse_board(size));
+                    game_of_life.compute_n(&device, &queue, SETTLE_GENERATIONS);
+                    let tracked = measure("sparse tracked", size, &device, |n| game_of_life.compute_n(&device, &queue, n));
+                    game_of_life.set_tile_tracking(&queue, false);
+                    let untracked = measure("sparse untracked", size, &device, |n| game_of_life.compute_n(&device, &queue, n));
+                    println!("sparse {0}x{0}: tile tracking is {1:.1}x faster", size, tracked/untracked);
+                },
+                Err(err) => println!("sparse {0}x{0}: skipped, {1}", size, err),
+            }
+        }
+    }
+}
+
+/// Warms up, then doubles the generations until a batch takes long enough to time. Returns the generations per second
+fn measure(storage: &str, size: u32, device: &wgpu::Device, mut compute_n: impl FnMut(u64)) -> f64 {
+    compute_n(1);
+    device.poll(wgpu::Maintain::Wait);
+    let mut generations = 1;
+    loop {
+        let start = Instant::now();
+        compute_n(generations);
+        device.poll(wgpu::Maintain::Wait);
+        let elapsed = start.elapsed();
+        if elapsed >= MEASURE_TIME || generations >= 1 << 20 {
+            let rate = generations as f64/elapsed.as_secs_f64();
+            println!("{0} {1}x{1}: {2} generations in {3:.3}s, {4:.3} generations/s", storage, size, generations, elapsed.as_secs_f64(), rate);
+            return rate
+        }
+        generations *= 2;
+    }
+}
+
+/// Empty board with a random soup in the middle
+fn sparse_board(size: u32) -> CpuBoard {
+    let mut board = CpuBoard::new(size, size);
+    let soup_size = SOUP_SIZE.min(size);
+    let start = (size-soup_size)/2;
+    let mut state = 0x9E3779B97F4A7C15u64;
+    for y in start..start+soup_size {
+        for x in start..start+soup_size {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            board.set(x, y, state & 1 == 1);
+        }
+    }
+    board
+}
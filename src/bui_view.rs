@@ -3,11 +3,14 @@ use std::sync::{Arc, Mutex};
 use bui::ttf::CachedFace;
 use bui_basic::containers::Init;
 use bui_basic::signal::{SignalReciever, ResizedSignal, RedrawCallback, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal, CharacterInputSignal};
+use instant::Instant;
 use winit::window::Window;
 
-use crate::ResumeCallback;
+use crate::{ResumeCallback, KeyPressedSignal};
 use crate::setup::{Setup, SetupLeftUp};
 use crate::game::{Game, GameSetupCallback};
+use crate::chunked_board::ChunkedGameOfLife;
+use crate::session::Session;
 
 pub enum Scene {
     Setup(Setup),
@@ -44,6 +47,20 @@ impl BuiView {
             Scene::Game(game) => game.render(),
         }
     }
+
+    pub fn update(&mut self) -> RedrawCallback {
+        match &mut self.scene {
+            Scene::Setup(_setup) => RedrawCallback::new(false),
+            Scene::Game(game) => game.update(),
+        }
+    }
+
+    pub fn next_update(&self) -> Option<Instant> {
+        match &self.scene {
+            Scene::Setup(_setup) => None,
+            Scene::Game(game) => game.next_update(),
+        }
+    }
 }
 
 impl Init for BuiView {
@@ -70,7 +87,7 @@ impl SignalReciever<CursorMovedSignal, RedrawCallback> for BuiView {
     fn take_signal(&mut self, signal: &mut CursorMovedSignal) -> RedrawCallback {
         match &mut self.scene {
             Scene::Setup(setup) => setup.take_signal(signal),
-            Scene::Game(game) => {game.take_signal(signal); RedrawCallback::new(false)},
+            Scene::Game(game) => game.take_signal(signal),
         }
     }
 }
@@ -79,7 +96,7 @@ impl SignalReciever<MouseLeftDownSignal, RedrawCallback> for BuiView {
     fn take_signal(&mut self, signal: &mut MouseLeftDownSignal) -> RedrawCallback {
         match &mut self.scene {
             Scene::Setup(setup) => setup.take_signal(signal),
-            Scene::Game(game) => {game.take_signal(signal); RedrawCallback::new(false)}
+            Scene::Game(game) => game.take_signal(signal),
         }
     }
 }
@@ -91,17 +108,30 @@ impl SignalReciever<MouseLeftUpSignal, (RedrawCallback, ResumeCallback)> for Bui
                 match setup.take_signal(signal) {
                     SetupLeftUp::DoNothing => (RedrawCallback::new(false), ResumeCallback::new(false)),
                     SetupLeftUp::Go => {
-                        match setup.get_dimensions() {
-                            Ok(dimensions) => {
+                        let game_options = setup.get_dimensions().and_then(|dimensions| Ok((dimensions, setup.get_rule()?, setup.get_topology()?, setup.get_history_budget()?)))
+                            .and_then(|(dimensions, rule, topology, history_budget)| match dimensions {
+                                Some(_) => Ok((dimensions, rule, topology, history_budget)),
+                                None => ChunkedGameOfLife::check(rule, topology).map(|()| (dimensions, rule, topology, history_budget)),
+                            })
+                            .and_then(|(dimensions, rule, topology, history_budget)| {
+                                let session = setup.get_session_path().map(|path| Session::new(path, dimensions, rule, topology));
+                                let bookmarks = match &session {
+                                    Some(session) => session.load()?,
+                                    None => Vec::new(),
+                                };
+                                Ok((dimensions, rule, topology, history_budget, session, bookmarks))
+                            });
+                        match game_options {
+                            Ok((dimensions, rule, topology, history_budget, session, bookmarks)) => {
                                 // TODO: Support sending renderer as a gift <3
-                                let mut game = Game::new(self.face.clone(), self.resx, self.resy, dimensions.0, dimensions.1);
+                                let mut game = Game::new(self.face.clone(), self.resx, self.resy, dimensions, rule, topology, history_budget);
+                                game.set_session(session, bookmarks);
                                 game.init();
                                 self.scene = Scene::Game(game);
                                 (RedrawCallback::new(true), ResumeCallback::new(true))
                             },
                             Err(err) => {
                                 println!("{}", err);
-                                let err = err.to_string();
                                 setup.set_error(err);
                                 setup.construct();
                                 (RedrawCallback::new(true), ResumeCallback::new(false))
@@ -132,7 +162,16 @@ impl SignalReciever<CharacterInputSignal, RedrawCallback> for BuiView {
     fn take_signal(&mut self, signal: &mut CharacterInputSignal) -> RedrawCallback {
         match &mut self.scene {
             Scene::Setup(setup) => setup.take_signal(signal),
-            Scene::Game(_game) => RedrawCallback::new(false),
+            Scene::Game(game) => game.take_signal(signal),
+        }
+    }
+}
+
+impl SignalReciever<KeyPressedSignal, RedrawCallback> for BuiView {
+    fn take_signal(&mut self, signal: &mut KeyPressedSignal) -> RedrawCallback {
+        match &mut self.scene {
+            Scene::Setup(_setup) => RedrawCallback::new(false),
+            Scene::Game(game) => game.take_signal(signal),
         }
     }
-}
\ No newline at end of file
+}
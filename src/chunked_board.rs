@@ -0,0 +1,985 @@
+use std::collections::{HashMap, HashSet};
+
+use bui::rect::{SizeAndCenter, Points};
+use bui_basic::{signal::{SignalReciever, ResizedSignal, CursorMovedSignal, MouseLeftUpSignal, RedrawCallback}, containers::Fill};
+use winit::event::VirtualKeyCode;
+
+use crate::{KeyPressedSignal, rule::Rule, topology::Topology, cpu_board::CpuBoard, hashlife::HashLife, game::{GameOfLife, RuleUniform, COMPUTE_WORKGROUP_SIZE, board_format_name}};
+
+/// Width and height of a chunk, in cells. Keep in sync with CHUNK_SIZE in game_of_life.wgsl
+pub const CHUNK_SIZE: u32 = 256;
+/// Layer of chunks that aren't allocated. Keep in sync with CHUNK_NONE in game_of_life.wgsl
+const CHUNK_NONE: u32 = u32::MAX;
+/// Most generations computed before checking which chunks are in use. Live cells spread at most
+/// a cell a generation, so they can't get through the chunks kept around every live chunk in that time
+const GENERATIONS_PER_CHECK: u64 = CHUNK_SIZE as u64/2;
+/// Chunks there is room for at first, doubled whenever they run out
+const INITIAL_CAPACITY: u32 = 16;
+/// Most chunks kept, if the device allows that many texture array layers
+const MAX_CHUNKS: u32 = 1024;
+/// Cells across the view vertically at first, and at the most zoomed in and out
+const INITIAL_VIEW_CELLS: f32 = 64.0;
+const MIN_VIEW_CELLS: f32 = 8.0;
+const MAX_VIEW_CELLS: f32 = 16384.0;
+
+/// Position of a chunk, in chunks. The chunk at (x, y) holds cells (x*CHUNK_SIZE, y*CHUNK_SIZE) up to the next chunk
+type ChunkPos = (i64, i64);
+
+/// Game of Life on an unbounded plane, kept on the GPU as chunks of `CHUNK_SIZE` by `CHUNK_SIZE`
+/// cells in the layers of a texture array.
+///
+/// Only chunks with live cells and the chunks around them are allocated and computed. Which
+/// chunks those are is checked every `GENERATIONS_PER_CHECK` generations, freeing empty chunks
+/// and allocating the ones activity is spreading into. Cells are at signed 64-bit coordinates.
+pub struct ChunkedGameOfLife {
+    arrays: ChunkArrays,
+    board_format: wgpu::TextureFormat,
+    /// Layers in `arrays`
+    capacity: u32,
+    max_chunks: u32,
+    chunks: HashMap<ChunkPos, u32>,
+    free_layers: Vec<u32>,
+    /// Chunks in the order of their layers in `ChunkArrays::layers_buffer`
+    order: Vec<ChunkPos>,
+    compute_bind_group_layout: wgpu::BindGroupLayout,
+    compute_pipeline: wgpu::ComputePipeline,
+    render_bind_group_layout: wgpu::BindGroupLayout,
+    render_pipeline: wgpu::RenderPipeline,
+    instance_count: u32,
+    active_chunks: Chunks,
+    generation: u64,
+    /// Cell in the middle of the view
+    viewx: i64,
+    viewy: i64,
+    /// Cells across the view vertically
+    view_cells: f32,
+    resx: f32,
+    resy: f32,
+    area: Points,
+    mousex: f32,
+    mousey: f32,
+    rule: Rule,
+    rule_uniform: RuleUniform,
+}
+
+enum Chunks {
+    A,
+    B,
+}
+
+/// Everything sized by the number of chunks there is room for
+struct ChunkArrays {
+    chunks_a: wgpu::Texture,
+    chunks_b: wgpu::Texture,
+    layers_buffer: wgpu::Buffer,
+    alive_buffer: wgpu::Buffer,
+    alive_read_buffer: wgpu::Buffer,
+    instance_buffer: wgpu::Buffer,
+    compute_bind_group_a: wgpu::BindGroup,
+    compute_bind_group_b: wgpu::BindGroup,
+    render_bind_group_a: wgpu::BindGroup,
+    render_bind_group_b: wgpu::BindGroup,
+}
+
+impl ChunkArrays {
+    fn new(device: &wgpu::Device, board_format: wgpu::TextureFormat, capacity: u32, compute_bind_group_layout: &wgpu::BindGroupLayout, render_bind_group_layout: &wgpu::BindGroupLayout, rule_uniform: &RuleUniform) -> Self {
+        let chunks_descriptor = |label| wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: CHUNK_SIZE,
+                height: CHUNK_SIZE,
+                depth_or_array_layers: capacity,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: board_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+        };
+        let chunks_a = device.create_texture(&chunks_descriptor("Chunked Game of Life chunks A"));
+        let chunks_b = device.create_texture(&chunks_descriptor("Chunked Game of Life chunks B"));
+        let array_view = wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        };
+        let chunks_a_view = chunks_a.create_view(&array_view);
+        let chunks_b_view = chunks_b.create_view(&array_view);
+
+        let layers_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life layers buffer"),
+            size: capacity as u64*9*4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let alive_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life alive buffer"),
+            size: capacity as u64*4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let alive_read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life alive read buffer"),
+            size: capacity as u64*4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        // One instance per chunk and one for the space between them
+        let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life instance buffer"),
+            size: (capacity as u64+1)*CHUNK_INSTANCE_SIZE,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let compute_bind_group = |label, last_view, new_view| device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: compute_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(last_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(new_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rule_uniform.binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: layers_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: alive_buffer.as_entire_binding(),
+                }
+            ],
+            label: Some(label)
+        });
+        let compute_bind_group_a = compute_bind_group("Chunked Game of Life compute bind group A", &chunks_a_view, &chunks_b_view);
+        let compute_bind_group_b = compute_bind_group("Chunked Game of Life compute bind group B", &chunks_b_view, &chunks_a_view);
+
+        let render_bind_group = |label, view| device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout: render_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(view),
+                },
+            ]
+        });
+        let render_bind_group_a = render_bind_group("Chunked Game of Life render bind group A", &chunks_a_view);
+        let render_bind_group_b = render_bind_group("Chunked Game of Life render bind group B", &chunks_b_view);
+
+        Self {
+            chunks_a,
+            chunks_b,
+            layers_buffer,
+            alive_buffer,
+            alive_read_buffer,
+            instance_buffer,
+            compute_bind_group_a,
+            compute_bind_group_b,
+            render_bind_group_a,
+            render_bind_group_b,
+        }
+    }
+}
+
+impl ChunkedGameOfLife {
+    /// Checks that a board with `rule` and `topology` can be unbounded
+    pub fn check(rule: Rule, topology: Topology) -> Result<(), String> {
+        if rule.birth() & 1 == 1 {
+            return Err(format!("unbounded boards can't have rules where cells are born with 0 neighbours like {}", rule))
+        }
+        if topology != Topology::PLANE {
+            return Err(format!("unbounded boards have no edges, so they can't be {}", topology))
+        }
+        Ok(())
+    }
+
+    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat, rule: Rule, resx: f32, resy: f32) -> Self {
+        let board_format = GameOfLife::board_format(device);
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("game_of_life.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("game_of_life.wgsl").replace("r32uint", board_format_name(board_format)).into()),
+        });
+        let max_chunks = MAX_CHUNKS.min(device.limits().max_texture_array_layers);
+        let capacity = INITIAL_CAPACITY.min(max_chunks);
+
+        let compute_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Chunked Game of Life compute bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        sample_type: wgpu::TextureSampleType::Uint
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        format: board_format,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }
+            ]
+        });
+
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Chunked Game of Life compute pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Chunked Game of Life compute pipeline layout"),
+                bind_group_layouts: &[
+                    &compute_bind_group_layout
+                ],
+                push_constant_ranges: &[],
+            })),
+            module: &shader,
+            entry_point: "compute_chunk"
+        });
+
+        let render_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Chunked Game of Life render bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        sample_type: wgpu::TextureSampleType::Uint,
+                    },
+                    count: None
+                }
+            ]
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Chunked Game of Life render pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Chunked Game of Life render pipeline layout"),
+                bind_group_layouts: &[&render_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "render_chunk_vert",
+                buffers: &[
+                    ChunkInstance::desc(),
+                ]
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "render_chunk",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL
+                })]
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        let rule_uniform = RuleUniform::new(device);
+        let arrays = ChunkArrays::new(device, board_format, capacity, &compute_bind_group_layout, &render_bind_group_layout, &rule_uniform);
+
+        Self {
+            arrays,
+            board_format,
+            capacity,
+            max_chunks,
+            chunks: HashMap::new(),
+            free_layers: (0..capacity).rev().collect(),
+            order: Vec::new(),
+            compute_bind_group_layout,
+            compute_pipeline,
+            render_bind_group_layout,
+            render_pipeline,
+            instance_count: 0,
+            active_chunks: Chunks::A,
+            generation: 0,
+            viewx: 0,
+            viewy: 0,
+            view_cells: INITIAL_VIEW_CELLS,
+            resx,
+            resy,
+            area: SizeAndCenter::ZERO.into(),
+            mousex: 0.0,
+            mousey: 0.0,
+            rule,
+            rule_uniform,
+        }
+    }
+
+    pub fn compute(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<(), String> {
+        self.compute_n(device, queue, 1)
+    }
+
+    /// Computes `n` generations, checking which chunks are in use at least every `GENERATIONS_PER_CHECK` of them.
+    /// Stops early when there's no room for the chunks live cells could spread into, before any are lost
+    pub fn compute_n(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, n: u64) -> Result<(), String> {
+        let mut remaining = n;
+        while remaining > 0 {
+            if self.chunks.is_empty() {
+                // Nothing is alive, and nothing will be
+                self.generation += remaining;
+                return Ok(())
+            }
+            let generations = remaining.min(GENERATIONS_PER_CHECK);
+            remaining -= generations;
+
+            queue.write_buffer(&self.arrays.alive_buffer, 0, &vec![0; self.capacity as usize*4]);
+
+            let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Chunked Game of Life compute command encoder")
+            });
+
+            let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Chunked Game of Life compute pass")
+            });
+
+            compute_pass.set_pipeline(&self.compute_pipeline);
+            for _ in 0..generations {
+                match self.active_chunks {
+                    Chunks::A => {
+                        compute_pass.set_bind_group(0, &self.arrays.compute_bind_group_a, &[]);
+                        self.active_chunks = Chunks::B;
+                    },
+                    Chunks::B => {
+                        compute_pass.set_bind_group(0, &self.arrays.compute_bind_group_b, &[]);
+                        self.active_chunks = Chunks::A;
+                    },
+                }
+                compute_pass.dispatch_workgroups(CHUNK_SIZE/COMPUTE_WORKGROUP_SIZE, CHUNK_SIZE/COMPUTE_WORKGROUP_SIZE, self.order.len() as u32);
+            }
+            drop(compute_pass);
+
+            command_encoder.copy_buffer_to_buffer(&self.arrays.alive_buffer, 0, &self.arrays.alive_read_buffer, 0, self.capacity as u64*4);
+            queue.submit(std::iter::once(command_encoder.finish()));
+            self.generation += generations;
+
+            let alive_slice = self.arrays.alive_read_buffer.slice(..);
+            alive_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map Chunked Game of Life alive read buffer"));
+            device.poll(wgpu::Maintain::Wait);
+            let alive_layers: Vec<u32> = bytemuck::cast_slice(&alive_slice.get_mapped_range()).to_vec();
+            self.arrays.alive_read_buffer.unmap();
+
+            // Keep every chunk that had live cells during those generations and the chunks around it
+            let live: Vec<ChunkPos> = self.chunks.iter()
+                .filter(|(_, &layer)| alive_layers[layer as usize] == 1)
+                .map(|(&chunk, _)| chunk)
+                .collect();
+            let needed = surrounding_chunks(live);
+            let unneeded: Vec<ChunkPos> = self.chunks.keys().filter(|chunk| !needed.contains(chunk)).copied().collect();
+            for chunk in unneeded {
+                self.free_layers.push(self.chunks.remove(&chunk).unwrap());
+            }
+            let result = self.allocate(device, queue, needed);
+            self.upload_layers(queue);
+            result?;
+        }
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the generation counter, for when the board was replaced with a later generation
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.board_format
+    }
+
+    /// Chunks currently allocated
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Cell in the middle of the view
+    pub fn view(&self) -> (i64, i64) {
+        (self.viewx, self.viewy)
+    }
+
+    /// Moves the view by (x, y) cells
+    pub fn pan(&mut self, x: i64, y: i64) {
+        self.viewx = self.viewx.saturating_add(x);
+        self.viewy = self.viewy.saturating_add(y);
+    }
+
+    /// Multiplies the cells in view by `factor`, within the zoom limits
+    pub fn zoom(&mut self, factor: f32) {
+        self.view_cells = (self.view_cells*factor).clamp(MIN_VIEW_CELLS, MAX_VIEW_CELLS);
+    }
+
+    pub fn render(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, loadop: wgpu::LoadOp<wgpu::Color>) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Chunked Game of Life render pass"),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: loadop,
+                        store: true,
+                    }
+                })
+            ],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffer(0, self.arrays.instance_buffer.slice(..));
+        match self.active_chunks {
+            Chunks::A => render_pass.set_bind_group(0, &self.arrays.render_bind_group_a, &[]),
+            Chunks::B => render_pass.set_bind_group(0, &self.arrays.render_bind_group_b, &[]),
+        }
+        render_pass.draw(0..4, 0..self.instance_count);
+    }
+
+    pub fn construct(&mut self, queue: &wgpu::Queue) {
+        self.rule_uniform.set(&[self.rule.birth() as u32, self.rule.survival() as u32], queue);
+        self.upload_layers(queue);
+    }
+
+    /// Flips the cell at (x, y)
+    pub fn toggle(&mut self, x: i64, y: i64, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<(), String> {
+        let mut board = self.read_region(device, queue, x, y, 1, 1);
+        board.toggle(0, 0);
+        self.write_region(device, queue, x, y, &board)
+    }
+
+    /// Copies the `width` by `height` cells from (x, y) back to the CPU. Blocks until the GPU is done with them
+    pub fn read_region(&self, device: &wgpu::Device, queue: &wgpu::Queue, x: i64, y: i64, width: u32, height: u32) -> CpuBoard {
+        let mut board = CpuBoard::new(width, height);
+        board.set_rule(self.rule);
+        let chunks: Vec<ChunkPos> = chunks_in(x, y, width, height).filter(|chunk| self.chunks.contains_key(chunk)).collect();
+        self.read_chunks(device, queue, &chunks, |cellx, celly| {
+            if cellx >= x && celly >= y && cellx-x < width as i64 && celly-y < height as i64 {
+                board.set((cellx-x) as u32, (celly-y) as u32, true);
+            }
+        });
+        board
+    }
+
+    /// Replaces the cells from (x, y) to (x+board.width(), y+board.height()) with the cells of
+    /// `board`, allocating the chunks they need
+    pub fn write_region(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, x: i64, y: i64, board: &CpuBoard) -> Result<(), String> {
+        let live: HashSet<ChunkPos> = board.alive_cells().into_iter().map(|(cellx, celly)| chunk_of(x+cellx as i64, y+celly as i64)).collect();
+        let result = self.allocate(device, queue, surrounding_chunks(live));
+
+        let texel_size = self.board_format.describe().block_size as u32;
+        for chunk in chunks_in(x, y, board.width(), board.height()) {
+            let layer = match self.chunks.get(&chunk) {
+                Some(&layer) => layer,
+                None => continue,
+            };
+            // Part of the board in this chunk, relative to the chunk
+            let chunkx = chunk.0*CHUNK_SIZE as i64;
+            let chunky = chunk.1*CHUNK_SIZE as i64;
+            let startx = (x-chunkx).max(0) as u32;
+            let starty = (y-chunky).max(0) as u32;
+            let endx = (x+board.width() as i64-chunkx).min(CHUNK_SIZE as i64) as u32;
+            let endy = (y+board.height() as i64-chunky).min(CHUNK_SIZE as i64) as u32;
+            let (width, height) = (endx-startx, endy-starty);
+
+            let mut texels = vec![0u8; (width*texel_size*height) as usize];
+            for texely in 0..height {
+                for texelx in 0..width {
+                    let boardx = (chunkx+(startx+texelx) as i64-x) as u32;
+                    let boardy = (chunky+(starty+texely) as i64-y) as u32;
+                    if board.get(boardx, boardy) {
+                        texels[((texely*width+texelx)*texel_size) as usize] = 1;
+                    }
+                }
+            }
+            queue.write_texture(wgpu::ImageCopyTexture {
+                texture: self.active_texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: startx,
+                    y: starty,
+                    z: layer,
+                },
+                aspect: wgpu::TextureAspect::All,
+            }, &texels, wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: std::num::NonZeroU32::new(width*texel_size),
+                rows_per_image: None,
+            }, wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            });
+        }
+        self.upload_layers(queue);
+        result
+    }
+
+    /// Coordinates of every live cell. Blocks until the GPU is done with them
+    pub fn alive_cells(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        self.read_chunks(device, queue, &self.order, |x, y| cells.push((x, y)));
+        cells
+    }
+
+    /// Kills every cell and frees every chunk
+    pub fn clear(&mut self, queue: &wgpu::Queue) {
+        self.free_layers.extend(self.chunks.drain().map(|(_, layer)| layer));
+        self.upload_layers(queue);
+    }
+
+    /// Jumps 2^k generations ahead with HashLife
+    pub fn jump_pow2(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, k: u32) -> Result<(), String> {
+        let mut hashlife = HashLife::new(self.rule)?;
+        for (x, y) in self.alive_cells(device, queue) {
+            hashlife.set(x, y, true);
+        }
+        hashlife.jump_pow2(k)?;
+        let generation = self.generation.checked_add(hashlife.generation()).ok_or("the generation overflows")?;
+        // Every live cell needs at least a chunk to itself
+        if hashlife.population() > self.max_chunks as u64*(CHUNK_SIZE*CHUNK_SIZE) as u64 {
+            return Err(format!("{} live cells don't fit in {} chunks", hashlife.population(), self.max_chunks))
+        }
+        let cells = hashlife.alive_cells();
+        let needed = surrounding_chunks(cells.iter().map(|&(x, y)| chunk_of(x, y)));
+        if needed.len() > self.max_chunks as usize {
+            return Err(format!("the pattern would need {} chunks, more than the maximum of {}", needed.len(), self.max_chunks))
+        }
+
+        self.clear(queue);
+        self.allocate(device, queue, needed)?;
+        let texel_size = self.board_format.describe().block_size as usize;
+        let mut texels: HashMap<ChunkPos, Vec<u8>> = HashMap::new();
+        for (x, y) in cells {
+            let chunk_texels = texels.entry(chunk_of(x, y)).or_insert_with(|| vec![0; (CHUNK_SIZE*CHUNK_SIZE) as usize*texel_size]);
+            let (texelx, texely) = (x.rem_euclid(CHUNK_SIZE as i64) as usize, y.rem_euclid(CHUNK_SIZE as i64) as usize);
+            chunk_texels[(texely*CHUNK_SIZE as usize+texelx)*texel_size] = 1;
+        }
+        for (chunk, chunk_texels) in texels {
+            self.write_layer(queue, self.chunks[&chunk], &chunk_texels);
+        }
+        self.upload_layers(queue);
+        self.generation = generation;
+        Ok(())
+    }
+
+    fn active_texture(&self) -> &wgpu::Texture {
+        match self.active_chunks {
+            Chunks::A => &self.arrays.chunks_a,
+            Chunks::B => &self.arrays.chunks_b,
+        }
+    }
+
+    /// Replaces a whole layer of the active chunks with `texels`
+    fn write_layer(&self, queue: &wgpu::Queue, layer: u32, texels: &[u8]) {
+        let texel_size = self.board_format.describe().block_size as u32;
+        queue.write_texture(wgpu::ImageCopyTexture {
+            texture: self.active_texture(),
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: 0,
+                y: 0,
+                z: layer,
+            },
+            aspect: wgpu::TextureAspect::All,
+        }, texels, wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: std::num::NonZeroU32::new(CHUNK_SIZE*texel_size),
+            rows_per_image: None,
+        }, wgpu::Extent3d {
+            width: CHUNK_SIZE,
+            height: CHUNK_SIZE,
+            depth_or_array_layers: 1,
+        });
+    }
+
+    /// Calls `alive` with the coordinates of every live cell in `chunks`, which must be allocated
+    fn read_chunks(&self, device: &wgpu::Device, queue: &wgpu::Queue, chunks: &[ChunkPos], mut alive: impl FnMut(i64, i64)) {
+        if chunks.is_empty() {
+            return
+        }
+        let texel_size = self.board_format.describe().block_size as u32;
+        let layer_size = (CHUNK_SIZE*texel_size*CHUNK_SIZE) as u64;
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life read buffer"),
+            size: layer_size*chunks.len() as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Chunked Game of Life read command encoder")
+        });
+        for (i, chunk) in chunks.iter().enumerate() {
+            command_encoder.copy_texture_to_buffer(wgpu::ImageCopyTexture {
+                texture: self.active_texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: self.chunks[chunk],
+                },
+                aspect: wgpu::TextureAspect::All,
+            }, wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: layer_size*i as u64,
+                    // Already a multiple of COPY_BYTES_PER_ROW_ALIGNMENT
+                    bytes_per_row: std::num::NonZeroU32::new(CHUNK_SIZE*texel_size),
+                    rows_per_image: None,
+                }
+            }, wgpu::Extent3d {
+                width: CHUNK_SIZE,
+                height: CHUNK_SIZE,
+                depth_or_array_layers: 1,
+            });
+        }
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let read_slice = read_buffer.slice(..);
+        read_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map Chunked Game of Life read buffer"));
+        device.poll(wgpu::Maintain::Wait);
+        {
+            let data = read_slice.get_mapped_range();
+            for (chunk, texels) in chunks.iter().zip(data.chunks(layer_size as usize)) {
+                for (i, texel) in texels.chunks(texel_size as usize).enumerate() {
+                    // Alive cells are 1, so the first byte is enough whatever the format
+                    if texel[0] == 1 {
+                        alive(chunk.0*CHUNK_SIZE as i64+(i as u32%CHUNK_SIZE) as i64, chunk.1*CHUNK_SIZE as i64+(i as u32/CHUNK_SIZE) as i64);
+                    }
+                }
+            }
+        }
+        read_buffer.unmap();
+    }
+
+    /// Allocates `chunks` that aren't already, with all their cells dead. Chunks past the most
+    /// there is room for are left out
+    fn allocate(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, chunks: impl IntoIterator<Item = ChunkPos>) -> Result<(), String> {
+        let mut result = Ok(());
+        let texel_size = self.board_format.describe().block_size as usize;
+        let dead = vec![0; (CHUNK_SIZE*CHUNK_SIZE) as usize*texel_size];
+        for chunk in chunks {
+            if self.chunks.contains_key(&chunk) {
+                continue
+            }
+            if self.free_layers.is_empty() {
+                if self.capacity == self.max_chunks {
+                    result = Err(format!("ran out of room for more than {} chunks", self.max_chunks));
+                    continue
+                }
+                self.grow(device, queue);
+            }
+            let layer = self.free_layers.pop().unwrap();
+            // Freed layers were empty when they were freed, but that may have been in the other texture
+            self.write_layer(queue, layer, &dead);
+            self.chunks.insert(chunk, layer);
+        }
+        result
+    }
+
+    /// Doubles the chunks there is room for, up to `max_chunks`
+    fn grow(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let capacity = (self.capacity*2).min(self.max_chunks);
+        let arrays = ChunkArrays::new(device, self.board_format, capacity, &self.compute_bind_group_layout, &self.render_bind_group_layout, &self.rule_uniform);
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Chunked Game of Life grow command encoder")
+        });
+        let (old_texture, new_texture) = match self.active_chunks {
+            Chunks::A => (&self.arrays.chunks_a, &arrays.chunks_a),
+            Chunks::B => (&self.arrays.chunks_b, &arrays.chunks_b),
+        };
+        // Through a buffer, as texture to texture copies between array layers lose the cells on GL
+        let texel_size = self.board_format.describe().block_size as u32;
+        let copy_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Chunked Game of Life grow buffer"),
+            size: (CHUNK_SIZE*texel_size*CHUNK_SIZE) as u64*self.capacity as u64,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let copy_layout = wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: std::num::NonZeroU32::new(CHUNK_SIZE*texel_size),
+            rows_per_image: std::num::NonZeroU32::new(CHUNK_SIZE),
+        };
+        let copy_size = wgpu::Extent3d {
+            width: CHUNK_SIZE,
+            height: CHUNK_SIZE,
+            depth_or_array_layers: self.capacity,
+        };
+        command_encoder.copy_texture_to_buffer(old_texture.as_image_copy(), wgpu::ImageCopyBuffer {
+            buffer: &copy_buffer,
+            layout: copy_layout,
+        }, copy_size);
+        command_encoder.copy_buffer_to_texture(wgpu::ImageCopyBuffer {
+            buffer: &copy_buffer,
+            layout: copy_layout,
+        }, new_texture.as_image_copy(), copy_size);
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        self.free_layers.extend((self.capacity..capacity).rev());
+        self.capacity = capacity;
+        self.arrays = arrays;
+    }
+
+    /// Writes which layers each chunk and its neighbours are in, and the instances drawing them
+    fn upload_layers(&mut self, queue: &wgpu::Queue) {
+        self.order = self.chunks.keys().copied().collect();
+        self.order.sort_unstable();
+        let mut layers = Vec::with_capacity(self.order.len()*9);
+        for &(x, y) in &self.order {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    layers.push(*self.chunks.get(&(x+dx, y+dy)).unwrap_or(&CHUNK_NONE));
+                }
+            }
+        }
+        queue.write_buffer(&self.arrays.layers_buffer, 0, bytemuck::cast_slice(&layers));
+
+        let instances = self.instances();
+        queue.write_buffer(&self.arrays.instance_buffer, 0, bytemuck::cast_slice(&instances));
+        self.instance_count = instances.len() as u32;
+    }
+
+    /// Size of a cell on screen
+    fn cell_size(&self) -> (f32, f32) {
+        let height = (self.area.p1y-self.area.p2y)/self.view_cells;
+        (height*self.resy/self.resx, height)
+    }
+
+    /// The space between chunks followed by the allocated chunks in view, clipped to the area
+    fn instances(&self) -> Vec<ChunkInstance> {
+        let (cell_width, cell_height) = self.cell_size();
+        let centerx = (self.area.p1x+self.area.p2x)/2.0;
+        let centery = (self.area.p1y+self.area.p2y)/2.0;
+        // Edges of the view in cells from its middle
+        let halfx = ((self.area.p2x-self.area.p1x)/2.0/cell_width) as f64;
+        let halfy = ((self.area.p1y-self.area.p2y)/2.0/cell_height) as f64;
+
+        let mut instances = vec![ChunkInstance {
+            position: [self.area.p1x, self.area.p2y],
+            size: [self.area.p2x-self.area.p1x, self.area.p1y-self.area.p2y],
+            cell_origin: [
+                (self.viewx.rem_euclid(CHUNK_SIZE as i64) as f64-halfx).rem_euclid(CHUNK_SIZE as f64) as f32,
+                (self.viewy.rem_euclid(CHUNK_SIZE as i64) as f64-halfy).rem_euclid(CHUNK_SIZE as f64) as f32,
+            ],
+            cell_extent: [(halfx*2.0) as f32, (halfy*2.0) as f32],
+            layer: CHUNK_NONE,
+        }];
+        for chunk in &self.order {
+            // Chunk's corner in cells from the middle of the view
+            let chunkx = (chunk.0 as i128*CHUNK_SIZE as i128-self.viewx as i128) as f64;
+            let chunky = (chunk.1 as i128*CHUNK_SIZE as i128-self.viewy as i128) as f64;
+            let startx = chunkx.max(-halfx);
+            let starty = chunky.max(-halfy);
+            let endx = (chunkx+CHUNK_SIZE as f64).min(halfx);
+            let endy = (chunky+CHUNK_SIZE as f64).min(halfy);
+            if startx >= endx || starty >= endy {
+                continue
+            }
+            instances.push(ChunkInstance {
+                position: [centerx+startx as f32*cell_width, centery+starty as f32*cell_height],
+                size: [(endx-startx) as f32*cell_width, (endy-starty) as f32*cell_height],
+                cell_origin: [(startx-chunkx) as f32, (starty-chunky) as f32],
+                cell_extent: [(endx-startx) as f32, (endy-starty) as f32],
+                layer: self.chunks[chunk],
+            });
+        }
+        instances
+    }
+}
+
+impl Fill for ChunkedGameOfLife {
+    fn fill(&mut self, fill_target: SizeAndCenter) {
+        self.area = fill_target.into();
+    }
+}
+
+impl SignalReciever<ResizedSignal, ()> for ChunkedGameOfLife {
+    fn take_signal(&mut self, signal: &mut ResizedSignal) {
+        self.resx = signal.resx;
+        self.resy = signal.resy;
+    }
+}
+
+impl SignalReciever<CursorMovedSignal, ()> for ChunkedGameOfLife {
+    fn take_signal(&mut self, signal: &mut CursorMovedSignal) {
+        self.mousex = signal.norm_posx;
+        self.mousey = signal.norm_posy;
+    }
+}
+
+impl SignalReciever<MouseLeftUpSignal, Option<(i64, i64)>> for ChunkedGameOfLife {
+    fn take_signal(&mut self, _signal: &mut MouseLeftUpSignal) -> Option<(i64, i64)> {
+        if self.mousex >= self.area.p1x && self.mousex < self.area.p2x && self.mousey <= self.area.p1y && self.mousey > self.area.p2y {
+            let (cell_width, cell_height) = self.cell_size();
+            Some((
+                self.viewx.saturating_add(((self.mousex-(self.area.p1x+self.area.p2x)/2.0)/cell_width).floor() as i64),
+                self.viewy.saturating_add(((self.mousey-(self.area.p1y+self.area.p2y)/2.0)/cell_height).floor() as i64),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Arrow keys pan an eighth of the view, page up and down zoom in and out, and home goes back to (0, 0)
+impl SignalReciever<KeyPressedSignal, RedrawCallback> for ChunkedGameOfLife {
+    fn take_signal(&mut self, signal: &mut KeyPressedSignal) -> RedrawCallback {
+        let step = (self.view_cells/8.0).max(1.0) as i64;
+        match signal.key {
+            VirtualKeyCode::Left => self.pan(-step, 0),
+            VirtualKeyCode::Right => self.pan(step, 0),
+            VirtualKeyCode::Up => self.pan(0, step),
+            VirtualKeyCode::Down => self.pan(0, -step),
+            VirtualKeyCode::PageUp => self.zoom(0.5),
+            VirtualKeyCode::PageDown => self.zoom(2.0),
+            VirtualKeyCode::Home => {
+                self.viewx = 0;
+                self.viewy = 0;
+            },
+            _ => return RedrawCallback::new(false),
+        }
+        RedrawCallback::new(true)
+    }
+}
+
+fn chunk_of(x: i64, y: i64) -> ChunkPos {
+    (x.div_euclid(CHUNK_SIZE as i64), y.div_euclid(CHUNK_SIZE as i64))
+}
+
+/// Chunks overlapping the `width` by `height` cells from (x, y)
+fn chunks_in(x: i64, y: i64, width: u32, height: u32) -> impl Iterator<Item = ChunkPos> {
+    let (startx, starty) = chunk_of(x, y);
+    let (endx, endy) = chunk_of(x+width.max(1) as i64-1, y+height.max(1) as i64-1);
+    (starty..=endy).flat_map(move |chunky| (startx..=endx).map(move |chunkx| (chunkx, chunky)))
+}
+
+/// `chunks` and the 8 chunks around each of them
+fn surrounding_chunks(chunks: impl IntoIterator<Item = ChunkPos>) -> HashSet<ChunkPos> {
+    let mut surrounding = HashSet::new();
+    for (x, y) in chunks {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                surrounding.insert((x+dx, y+dy));
+            }
+        }
+    }
+    surrounding
+}
+
+const CHUNK_INSTANCE_SIZE: u64 = std::mem::size_of::<ChunkInstance>() as u64;
+
+/// Matches ChunkInstance in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ChunkInstance {
+    position: [f32; 2],
+    size: [f32; 2],
+    cell_origin: [f32; 2],
+    cell_extent: [f32; 2],
+    layer: u32,
+}
+
+impl ChunkInstance {
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<ChunkInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 6]>() as wgpu::BufferAddress,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 8]>() as wgpu::BufferAddress,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Uint32,
+                },
+            ]
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{rule::Rule, topology::{Topology, Neighbour}};
+
+/// Game of Life board stepped on the CPU.
+///
+/// Mirrors `compute_board` in `game_of_life.wgsl`, including how neighbours past the edges are
+/// resolved. Runs anywhere, so it's used as the reference the GPU board is checked against.
+#[derive(Clone, Debug)]
+pub struct CpuBoard {
+    width: u32,
+    height: u32,
+    cells: Vec<bool>,
+    rule: Rule,
+    topology: Topology,
+}
+
+impl CpuBoard {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![false; width as usize*height as usize],
+            rule: Rule::CONWAY,
+            topology: Topology::PLANE,
+        }
+    }
+
+    pub fn with_cells(width: u32, height: u32, alive: &[(u32, u32)]) -> Self {
+        let mut board = Self::new(width, height);
+        for &(x, y) in alive {
+            board.set(x, y, true);
+        }
+        board
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        assert!(x < self.width && y < self.height, "cell ({}, {}) is outside of a {}x{} board", x, y, self.width, self.height);
+        y as usize*self.width as usize+x as usize
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        self.cells[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, alive: bool) {
+        let index = self.index(x, y);
+        self.cells[index] = alive;
+    }
+
+    pub fn toggle(&mut self, x: u32, y: u32) {
+        let index = self.index(x, y);
+        self.cells[index] = !self.cells[index];
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|alive| **alive).count()
+    }
+
+    pub fn alive_cells(&self) -> Vec<(u32, u32)> {
+        let mut alive = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.get(x, y) {
+                    alive.push((x, y));
+                }
+            }
+        }
+        alive
+    }
+
+    fn neighbour(&self, x: i64, y: i64) -> bool {
+        match self.topology.resolve(x, y, self.width, self.height) {
+            Neighbour::Cell(x, y) => self.get(x, y),
+            Neighbour::Dead => false,
+            Neighbour::Alive => true,
+        }
+    }
+
+    fn count_neighbours(&self, x: u32, y: u32) -> u32 {
+        let (x, y) = (x as i64, y as i64);
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx != 0 || dy != 0) && self.neighbour(x+dx, y+dy) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    pub fn step(&mut self) {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let alive = self.get(x, y);
+                let count = self.count_neighbours(x, y);
+                cells.push(self.rule.next(alive, count));
+            }
+        }
+        self.cells = cells;
+    }
+
+    pub fn step_n(&mut self, n: u64) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+/// Boards are equal when they have the same size and the same live cells, whatever their rule and topology
+impl PartialEq for CpuBoard {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.cells == other.cells
+    }
+}
+
+impl Eq for CpuBoard {}
+
+impl Hash for CpuBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.cells.hash(state);
+    }
+}
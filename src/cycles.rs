@@ -0,0 +1,125 @@
+use std::{collections::{HashMap, VecDeque}, fmt};
+
+use crate::stats::GenerationStats;
+
+/// A board repeating itself, every `period` generations from `start` on. A period of 1 is a still life.
+/// If the cycle isn't `exact`, it may have started before `start`, in generations whose stats were skipped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: u64,
+    pub period: u64,
+    pub exact: bool,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.period {
+            1 => write!(f, "still life since generation {}", self.start)?,
+            period => write!(f, "period {} since generation {}", period, self.start)?,
+        }
+        if !self.exact {
+            write!(f, " or earlier")?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds when a board starts repeating from the hashes in the stats of its generations, keeping those
+/// of the last `window` generations. Periods longer than that aren't found. Generations whose stats are
+/// skipped leave a gap, after which the detector starts over, and a cycle found starting right after the
+/// gap isn't exact
+pub struct CycleDetector {
+    window: u64,
+    /// Earliest generation each board was seen at, by hash and population
+    seen: HashMap<(u64, u64), u64>,
+    /// Every generation seen in the window, oldest first
+    order: VecDeque<((u64, u64), u64)>,
+    /// The cycle found and the generation it was found at
+    found: Option<(Cycle, u64)>,
+    /// First generation after the last gap, which cycles starting at might have started earlier
+    after_gap: Option<u64>,
+}
+
+impl CycleDetector {
+    pub fn new(window: u64) -> Self {
+        Self {
+            window: window.max(1),
+            seen: HashMap::new(),
+            order: VecDeque::new(),
+            found: None,
+            after_gap: None,
+        }
+    }
+
+    /// The cycle the board is in, if one has been found
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.found.map(|(cycle, _)| cycle)
+    }
+
+    /// Checks the stats of the next generation, returning the cycle if this is when it's found. Stats of
+    /// a generation that isn't after the latest one mean the board went back, so what was seen from then
+    /// on is forgotten, or was edited if it isn't the board seen then, so everything is. Stats more than a
+    /// generation after the latest one mean some were skipped, so everything is forgotten too
+    pub fn push(&mut self, stats: GenerationStats) -> Option<Cycle> {
+        let key = (stats.hash, stats.population);
+        match self.order.back().map(|(_, latest)| *latest) {
+            Some(latest) if stats.generation <= latest => {
+                let seen_then = self.order.iter().rev().find(|(_, generation)| *generation == stats.generation).map(|(key, _)| *key);
+                if seen_then == Some(key) {
+                    self.truncate(stats.generation);
+                } else {
+                    self.clear();
+                }
+            },
+            Some(latest) if stats.generation > latest+1 => {
+                self.clear();
+                self.after_gap = Some(stats.generation);
+            },
+            _ => {},
+        }
+        let previous = self.seen.get(&key).copied();
+        if previous.is_none() {
+            self.seen.insert(key, stats.generation);
+        }
+        self.order.push_back((key, stats.generation));
+        while self.order.front().is_some_and(|(_, generation)| generation+self.window < stats.generation) {
+            let (key, generation) = self.order.pop_front().unwrap();
+            if self.seen.get(&key) == Some(&generation) {
+                self.seen.remove(&key);
+            }
+        }
+
+        match (self.found, previous) {
+            (None, Some(start)) => {
+                let cycle = Cycle {
+                    start,
+                    period: stats.generation-start,
+                    exact: self.after_gap != Some(start),
+                };
+                self.found = Some((cycle, stats.generation));
+                Some(cycle)
+            },
+            _ => None,
+        }
+    }
+
+    /// Forgets what was seen at `generation` and after
+    fn truncate(&mut self, generation: u64) {
+        while self.order.back().is_some_and(|(_, seen)| *seen >= generation) {
+            let (key, seen) = self.order.pop_back().unwrap();
+            if self.seen.get(&key) == Some(&seen) {
+                self.seen.remove(&key);
+            }
+        }
+        if self.found.is_some_and(|(_, found_at)| found_at >= generation) {
+            self.found = None;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+        self.order.clear();
+        self.found = None;
+        self.after_gap = None;
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use crate::cpu_board::CpuBoard;
+
+/// Changes made to every cell of a board at once. Cells are moved around on a board with y going up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// Killing every cell
+    Clear,
+    /// Bringing dead cells to life and killing live ones
+    Invert,
+    /// Moving every cell `dx` right and `dy` up, wrapping around the edges
+    Shift {
+        dx: i32,
+        dy: i32,
+    },
+    /// Turning the board clockwise about its middle by this many quarter turns. Cells turned past the edge
+    /// of a board that isn't square are lost, and the cells turned in from past it are dead
+    Rotate(u32),
+    /// Mirroring x, so the left and right edges swap
+    FlipHorizontal,
+    /// Mirroring y, so the top and bottom edges swap
+    FlipVertical,
+}
+
+impl Edit {
+    /// Parses `clear`, `invert`, `shift <dx>,<dy>`, `rotate <90, 180 or 270>`, `flip h` or `flip v`
+    pub fn parse(edit: &str) -> Result<Self, String> {
+        let edit = edit.trim().to_lowercase();
+        let unknown = || format!("unknown edit \"{}\", expected clear, invert, shift <dx>,<dy>, rotate <degrees>, flip h or flip v", edit);
+        let (name, argument) = match edit.split_once(char::is_whitespace) {
+            Some((name, argument)) => (name, argument.trim()),
+            None => (edit.as_str(), ""),
+        };
+        match (name, argument) {
+            ("clear", "") => Ok(Edit::Clear),
+            ("invert", "") => Ok(Edit::Invert),
+            ("shift", offset) => {
+                let (dx, dy) = offset.split_once(',').ok_or_else(|| format!("failed to parse \"{}\" as an offset, expected <dx>,<dy>", offset))?;
+                let number = |number: &str| number.trim().parse::<i32>().map_err(|_| format!("failed to parse \"{}\" as a number", number.trim()));
+                Ok(Edit::Shift {
+                    dx: number(dx)?,
+                    dy: number(dy)?,
+                })
+            },
+            ("rotate", degrees) => match degrees.strip_suffix('°').unwrap_or(degrees).trim() {
+                "90" => Ok(Edit::Rotate(1)),
+                "180" => Ok(Edit::Rotate(2)),
+                "270" => Ok(Edit::Rotate(3)),
+                degrees => Err(format!("can only rotate by 90, 180 or 270 degrees, not {}", degrees)),
+            },
+            ("flip", "h" | "horizontal" | "x") => Ok(Edit::FlipHorizontal),
+            ("flip", "v" | "vertical" | "y") => Ok(Edit::FlipVertical),
+            _ => Err(unknown()),
+        }
+    }
+
+    /// Where the cell that ends up at `x`, `y` of a `width` by `height` board comes from, or None if it's
+    /// dead whatever was there. Same as edited_cell in game_of_life.wgsl
+    pub fn source(&self, x: u32, y: u32, width: u32, height: u32) -> Option<(u32, u32)> {
+        let (x, y, width, height) = (x as i64, y as i64, width as i64, height as i64);
+        let (sourcex, sourcey) = match *self {
+            Edit::Clear => return None,
+            Edit::Invert => (x, y),
+            Edit::Shift { dx, dy } => ((x-dx as i64).rem_euclid(width), (y-dy as i64).rem_euclid(height)),
+            Edit::Rotate(quarter_turns) => {
+                // Doubled so the middle of the board is a whole number
+                let mut doubled = (2*x-(width-1), 2*y-(height-1));
+                for _ in 0..quarter_turns%4 {
+                    doubled = (-doubled.1, doubled.0);
+                }
+                ((doubled.0+width-1) >> 1, (doubled.1+height-1) >> 1)
+            },
+            Edit::FlipHorizontal => (width-1-x, y),
+            Edit::FlipVertical => (x, height-1-y),
+        };
+        if (0..width).contains(&sourcex) && (0..height).contains(&sourcey) {
+            Some((sourcex as u32, sourcey as u32))
+        } else {
+            None
+        }
+    }
+
+    /// `board` with the edit made to it
+    pub fn apply(&self, board: &CpuBoard) -> CpuBoard {
+        let mut edited = board.clone();
+        for y in 0..board.height() {
+            for x in 0..board.width() {
+                let alive = match self.source(x, y, board.width(), board.height()) {
+                    Some((sourcex, sourcey)) => board.get(sourcex, sourcey),
+                    None => false,
+                };
+                edited.set(x, y, alive != (*self == Edit::Invert));
+            }
+        }
+        edited
+    }
+
+    pub(crate) fn uniform(&self, width: u32, height: u32) -> EditUniformData {
+        let (kind, quarter_turns, shift) = match *self {
+            Edit::Clear => (0, 0, [0, 0]),
+            Edit::Invert => (1, 0, [0, 0]),
+            // Wrapped onto the board so the shader only has to add the board's size to stay positive
+            Edit::Shift { dx, dy } => (2, 0, [(dx as i64).rem_euclid(width as i64) as i32, (dy as i64).rem_euclid(height as i64) as i32]),
+            Edit::Rotate(quarter_turns) => (3, quarter_turns%4, [0, 0]),
+            Edit::FlipHorizontal => (4, 0, [0, 0]),
+            Edit::FlipVertical => (5, 0, [0, 0]),
+        };
+        EditUniformData {
+            kind,
+            quarter_turns,
+            shift,
+        }
+    }
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edit::Clear => write!(f, "clear"),
+            Edit::Invert => write!(f, "invert"),
+            Edit::Shift { dx, dy } => write!(f, "shift {},{}", dx, dy),
+            Edit::Rotate(quarter_turns) => write!(f, "rotate {}", quarter_turns%4*90),
+            Edit::FlipHorizontal => write!(f, "flip h"),
+            Edit::FlipVertical => write!(f, "flip v"),
+        }
+    }
+}
+
+/// Matches Edit in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct EditUniformData {
+    /// Which edit it is, in the order of `Edit`'s variants
+    kind: u32,
+    quarter_turns: u32,
+    shift: [i32; 2],
+}
+
+bui::typed_uniform!(EditUniform, EditUniformData, "Edit Uniform");
@@ -1,34 +1,242 @@
-use std::sync::{Arc, Mutex};
+use std::{collections::hash_map::RandomState, hash::{BuildHasher, Hash, Hasher}, sync::{Arc, Mutex}};
 
 use bui::{rect::{SizeAndCenter, Points, FillAspect}, ttf::CachedFace, renderer::Renderer, text::TextRenderer};
-use bui_basic::{button::{Button, ClickedCallback}, text::Text, signal::{SignalReciever, ResizedSignal, RedrawCallback, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal}, construct::{Construct, LineTarget, StandardConstructTarget}, containers::{Init, Fill}};
-use winit::window::Window;
+use bui_basic::{button::{Button, ClickedCallback}, text::Text, text_input::TextInput, signal::{SignalReciever, ResizedSignal, RedrawCallback, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal, CharacterInputSignal}, construct::{Construct, LineTarget, StandardConstructTarget}, containers::{Init, Fill}};
+use instant::{Duration, Instant};
+use wgpu::util::DeviceExt;
+use winit::{window::Window, event::VirtualKeyCode};
+
+use crate::{KeyPressedSignal, rule::Rule, topology::Topology, cpu_board::CpuBoard, packed_board::PackedGameOfLife, chunked_board::ChunkedGameOfLife, hashlife::HashLife, history::{History, Snapshot, SnapshotBuilder}, stats::{Stats, GenerationStats}, overlay::{Overlay, OverlayRect}, graph::{Graph, GraphScale}, cycles::CycleDetector, palette::{AgePalette, AgePaletteUniform}, heatmap::Heatmap, trails::Trails, run_until::{RunUntil, StopCondition}, session::{Bookmark, Session}, soup::{Soup, SoupArea, SoupUniform}, edit::{Edit, EditUniform}, timeline::{Timeline, TimelinePick}};
+
+/// Speeds the game can run at. Going past the last one runs at max speed
+const GENERATIONS_PER_SECOND: [u32; 10] = [1, 2, 5, 10, 15, 20, 30, 60, 120, 240];
+/// Generations computed each frame at max speed, and the most computed in one frame to catch up
+const MAX_GENERATIONS_PER_FRAME: u32 = 32;
+/// How often the board is recorded in the history while running. Going back further computes forward from there
+const RUNNING_HISTORY_INTERVAL: Duration = Duration::from_secs(1);
+/// Most generations going to one on the timeline computes on from the nearest snapshot, so dragging into the
+/// gap a jump 2^k leaves doesn't hang the window
+const MAX_GO_TO_GENERATIONS: u64 = 4*MAX_GENERATIONS_PER_SUBMIT;
+/// How often stats being copied back from the GPU are checked on
+const STATS_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// Corners of the HUD, in normalized device coordinates
+const HUD_AREA: Points = Points {
+    p1x: -0.99,
+    p1y: 0.99,
+    p2x: -0.55,
+    p2y: 0.92,
+};
+const HUD_BACKGROUND: [f32; 4] = [1.0, 1.0, 1.0, 0.75];
+/// Generations the graph spans
+const GRAPH_LENGTH: u64 = 1000;
+/// Half-lives the heatmap button goes through after turning the heatmap on, None being heat that never decays
+const HEAT_HALF_LIVES: [Option<u32>; 4] = [None, Some(1000), Some(100), Some(10)];
+/// Trail lengths the trails button goes through, 0 being no trail
+const TRAIL_LENGTHS: [u32; 5] = [0, 2, 4, 8, 16];
+/// Longest period of cycles that are found
+const CYCLE_WINDOW: u64 = 10000;
+/// Corners of the line under the HUD saying what cycle the board is in
+const CYCLE_AREA: Points = Points {
+    p1x: -0.99,
+    p1y: 0.92,
+    p2x: -0.55,
+    p2y: 0.86,
+};
+/// Corners of the line under that saying what soup the board was last filled with
+const SOUP_AREA: Points = Points {
+    p1x: -0.99,
+    p1y: 0.86,
+    p2x: -0.55,
+    p2y: 0.8,
+};
+/// Corners of the line under that saying why running until last stopped
+const STOPPED_AREA: Points = Points {
+    p1x: -0.99,
+    p1y: 0.8,
+    p2x: -0.55,
+    p2y: 0.74,
+};
+/// Corners of the graph's background, its label and its lines
+const GRAPH_AREA: Points = Points {
+    p1x: 0.4,
+    p1y: 0.99,
+    p2x: 0.99,
+    p2y: 0.55,
+};
+const GRAPH_LABEL_AREA: Points = Points {
+    p1x: 0.41,
+    p1y: 0.99,
+    p2x: 0.98,
+    p2y: 0.93,
+};
+const GRAPH_LINES_AREA: Points = Points {
+    p1x: 0.41,
+    p1y: 0.92,
+    p2x: 0.98,
+    p2y: 0.56,
+};
 
 pub struct Game {
     exit_button: Button<Text>,
     step_button: Button<Text>,
+    play_button: Button<Text>,
+    pause_button: Button<Text>,
+    slower_button: Button<Text>,
+    speed_text: Text,
+    faster_button: Button<Text>,
+    jump_input: TextInput,
+    jump_button: Button<Text>,
+    jump_pow2_button: Button<Text>,
+    /// Generation the jump being computed started at and the one it's going to
+    jump: Option<(u64, u64)>,
+    back_button: Button<Text>,
+    undo_button: Button<Text>,
+    redo_button: Button<Text>,
+    history: History,
+    /// When the board was last recorded in the history
+    last_recorded: Instant,
+    generation_text: Text,
+    timeline: Timeline,
+    bookmark_input: TextInput,
+    mark_button: Button<Text>,
+    until_input: TextInput,
+    until_button: Button<Text>,
+    /// What stops the game running, if anything but pausing does
+    run_until: Option<RunUntil>,
+    /// Why running until last stopped, shown in the HUD until the game runs again
+    stopped: Option<String>,
+    stopped_text: Text,
+    bookmarks: Vec<Bookmark>,
+    /// Where bookmarks are saved, if anywhere
+    session: Option<Session>,
+    overlay: Option<Overlay>,
+    /// Stats drawn over the board
+    hud_text: Text,
+    hud_renderer: Option<TextRenderer>,
+    /// Stats the HUD was last constructed with
+    hud_stats: Option<GenerationStats>,
+    graph: Graph,
+    show_graph: bool,
+    graph_text: Text,
+    /// Which of these shows depends on what clicking it does next
+    graph_button: Button<Text>,
+    log_graph_button: Button<Text>,
+    hide_graph_button: Button<Text>,
+    cycle_detector: CycleDetector,
+    cycle_text: Text,
+    palette_button: Button<Text>,
+    palette_text: Text,
+    heatmap_button: Button<Text>,
+    heat_text: Text,
+    reset_heat_button: Button<Text>,
+    trails_button: Button<Text>,
+    trails_text: Text,
+    soup_input: TextInput,
+    randomize_button: Button<Text>,
+    /// What soup the board was last filled with, if any
+    soup: Option<Soup>,
+    soup_text: Text,
+    clear_button: Button<Text>,
+    invert_button: Button<Text>,
+    edit_input: TextInput,
+    transform_button: Button<Text>,
+    /// Last thing the bottom bar was told to show, like why a jump failed
+    message_text: Text,
+    /// When the game was made, which seeds of soups are picked from
+    created: Instant,
+    /// Whether the game pauses when the board is found to be cycling
+    auto_pause: bool,
+    /// Which of these shows depends on whether auto-pausing is on
+    auto_pause_off_button: Button<Text>,
+    auto_pause_on_button: Button<Text>,
+    running: bool,
+    speed: usize,
+    next_generation: Instant,
     renderer: Option<Renderer>,
     text_renderer: Option<TextRenderer>,
-    game_of_life: Option<GameOfLife>,
+    game_of_life: Option<Storage>,
     resx: f32,
     resy: f32,
-    width: u32,
-    height: u32,
+    /// Width and height of the board, or None for an unbounded one
+    dimensions: Option<(u32, u32)>,
+    rule: Rule,
+    topology: Topology,
     bottom_bar_sy: f32,
 }
 
 impl Game {
-    pub fn new(face: Arc<Mutex<CachedFace>>, resx: f32, resy: f32, width: u32, height: u32) -> Self {
+    pub fn new(face: Arc<Mutex<CachedFace>>, resx: f32, resy: f32, dimensions: Option<(u32, u32)>, rule: Rule, topology: Topology, history_budget: usize) -> Self {
         Self {
             exit_button: Button::new(Text::new_with_res("Exit", face.clone(), resx, resy), SizeAndCenter::ZERO),
             step_button: Button::new(Text::new_with_res("Step", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            play_button: Button::new(Text::new_with_res("Play", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            pause_button: Button::new(Text::new_with_res("Pause", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            slower_button: Button::new(Text::new_with_res("-", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            speed_text: Text::new_with_res(Self::speed_label(3), face.clone(), resx, resy),
+            faster_button: Button::new(Text::new_with_res("+", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            jump_input: TextInput::new_with_res("0".to_string(), face.clone(), resx, resy),
+            jump_button: Button::new(Text::new_with_res("Jump", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            jump_pow2_button: Button::new(Text::new_with_res("Jump 2^k", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            jump: None,
+            back_button: Button::new(Text::new_with_res("Back", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            undo_button: Button::new(Text::new_with_res("Undo", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            redo_button: Button::new(Text::new_with_res("Redo", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            history: History::new(history_budget),
+            last_recorded: Instant::now(),
+            generation_text: Text::new_with_res("Gen 0", face.clone(), resx, resy),
+            timeline: Timeline::new(),
+            bookmark_input: TextInput::new_with_res(String::new(), face.clone(), resx, resy),
+            mark_button: Button::new(Text::new_with_res("Bookmark", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            until_input: TextInput::new_with_res("static".to_string(), face.clone(), resx, resy),
+            until_button: Button::new(Text::new_with_res("Run until", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            run_until: None,
+            stopped: None,
+            stopped_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            bookmarks: Vec::new(),
+            session: None,
+            overlay: None,
+            hud_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            hud_renderer: None,
+            hud_stats: None,
+            graph: Graph::new(GRAPH_LENGTH),
+            show_graph: false,
+            graph_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            graph_button: Button::new(Text::new_with_res("Graph", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            log_graph_button: Button::new(Text::new_with_res("Log scale", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            hide_graph_button: Button::new(Text::new_with_res("Hide graph", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            cycle_detector: CycleDetector::new(CYCLE_WINDOW),
+            cycle_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            palette_button: Button::new(Text::new_with_res("Colours", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            palette_text: Text::new_with_res(Self::palette_label(None), face.clone(), resx, resy),
+            heatmap_button: Button::new(Text::new_with_res("Heatmap", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            heat_text: Text::new_with_res(Self::heat_label(false, None), face.clone(), resx, resy),
+            reset_heat_button: Button::new(Text::new_with_res("Reset heat", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            trails_button: Button::new(Text::new_with_res("Trails", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            trails_text: Text::new_with_res(Self::trails_label(0), face.clone(), resx, resy),
+            soup_input: TextInput::new_with_res("50%".to_string(), face.clone(), resx, resy),
+            randomize_button: Button::new(Text::new_with_res("Randomize", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            soup: None,
+            soup_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            clear_button: Button::new(Text::new_with_res("Clear", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            invert_button: Button::new(Text::new_with_res("Invert", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            edit_input: TextInput::new_with_res("rotate 90".to_string(), face.clone(), resx, resy),
+            transform_button: Button::new(Text::new_with_res("Transform", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            message_text: Text::new_with_res(String::new(), face.clone(), resx, resy),
+            created: Instant::now(),
+            auto_pause: false,
+            auto_pause_off_button: Button::new(Text::new_with_res("Auto-pause off", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            auto_pause_on_button: Button::new(Text::new_with_res("Auto-pause on", face.clone(), resx, resy), SizeAndCenter::ZERO),
+            running: false,
+            speed: 3,
+            next_generation: Instant::now(),
             renderer: None,
             text_renderer: None,
             game_of_life: None,
             resx,
             resy,
-            width,
-            height,
+            dimensions,
+            rule,
+            topology,
             bottom_bar_sy: 0.0,
         }
     }
@@ -36,13 +244,571 @@ impl Game {
     pub fn resume(&mut self, window: &Window) {
         let renderer = futures::executor::block_on(Renderer::new(window));
         self.text_renderer = Some(TextRenderer::new(renderer.device(), renderer.config().format, 1000, renderer.config().width, renderer.config().height));
-        let mut game_of_life = GameOfLife::new(renderer.device(), renderer.config().format, self.width, self.height, self.resx, self.resy);
+        self.overlay = Some(Overlay::new(renderer.device(), renderer.config().format));
+        self.hud_renderer = Some(TextRenderer::new(renderer.device(), renderer.config().format, 200, renderer.config().width, renderer.config().height));
+        let mut game_of_life = Storage::new(renderer.device(), renderer.config().format, self.dimensions, self.rule, self.topology, self.resx, self.resy);
         game_of_life.fill(Self::calculate_game_of_life_space(self.bottom_bar_sy));
         self.game_of_life = Some(game_of_life);
         self.renderer = Some(renderer);
         self.construct();
     }
 
+    /// Saves bookmarks to `session`, starting with the ones already saved there
+    pub fn set_session(&mut self, session: Option<Session>, bookmarks: Vec<Bookmark>) {
+        self.session = session;
+        self.bookmarks = bookmarks;
+    }
+
+    fn speed_label(speed: usize) -> String {
+        match GENERATIONS_PER_SECOND.get(speed) {
+            Some(generations_per_second) => format!("{} gen/s", generations_per_second),
+            None => "Max".to_string(),
+        }
+    }
+
+    fn set_speed(&mut self, speed: usize) {
+        self.speed = speed.min(GENERATIONS_PER_SECOND.len());
+        self.speed_text.set_text(Self::speed_label(self.speed));
+        self.next_generation = Instant::now();
+    }
+
+    fn palette_label(palette: Option<&AgePalette>) -> String {
+        match palette {
+            Some(palette) => palette.name.to_string(),
+            None => "Position".to_string(),
+        }
+    }
+
+    /// Switches to colouring cells by the next age palette, then back to by where they are
+    fn next_palette(&mut self) {
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let next = match game_of_life.palette() {
+            None => AgePalette::PRESETS.first(),
+            Some(palette) => AgePalette::PRESETS.iter().skip_while(|preset| preset.name != palette.name).nth(1),
+        };
+        match game_of_life.set_palette(next.copied()) {
+            Ok(()) => self.palette_text.set_text(Self::palette_label(next)),
+            Err(e) => self.show_message(format!("Couldn't change colours: {}", e)),
+        }
+    }
+
+    fn heat_label(enabled: bool, half_life: Option<u32>) -> String {
+        match (enabled, half_life) {
+            (false, _) => "Heat off".to_string(),
+            (true, None) => "Heat all time".to_string(),
+            (true, Some(half_life)) => format!("Heat half-life {}", half_life),
+        }
+    }
+
+    /// Turns the heatmap on, then makes its heat decay faster and faster, then turns it off
+    fn next_heatmap(&mut self) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let next = match game_of_life.heatmap_enabled() {
+            false => HEAT_HALF_LIVES.first(),
+            true => HEAT_HALF_LIVES.iter().skip_while(|half_life| **half_life != game_of_life.heat_half_life()).nth(1),
+        };
+        if let Some(half_life) = next {
+            game_of_life.set_heat_half_life(renderer.queue(), *half_life);
+        }
+        let result = game_of_life.set_heatmap(renderer.device(), renderer.queue(), next.is_some());
+        self.heat_text.set_text(Self::heat_label(game_of_life.heatmap_enabled(), game_of_life.heat_half_life()));
+        if let Err(e) = result {
+            self.show_message(format!("Couldn't show the heatmap: {}", e));
+        }
+    }
+
+    fn trails_label(length: u32) -> String {
+        match length {
+            0 => "Trails off".to_string(),
+            length => format!("Trail {} gens", length),
+        }
+    }
+
+    /// Makes the trail of past generations longer, then turns it off. Trails too big for the board turn it off too
+    fn next_trail_length(&mut self) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let length = game_of_life.trail_length();
+        let next = TRAIL_LENGTHS.iter().copied().find(|next| *next > length).unwrap_or(0);
+        if let Err(err) = game_of_life.set_trail_length(renderer.device(), renderer.queue(), next) {
+            // Turned off so the next click starts from the shortest trail again
+            game_of_life.set_trail_length(renderer.device(), renderer.queue(), 0).unwrap();
+            self.trails_text.set_text(Self::trails_label(0));
+            self.show_message(format!("Couldn't show trails: {}", err));
+            return
+        }
+        self.trails_text.set_text(Self::trails_label(next));
+    }
+
+    /// Seed for a soup that wasn't given one
+    fn random_seed(&self) -> u64 {
+        let mut hasher = RandomState::new().build_hasher();
+        self.created.elapsed().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Fills the board with the soup in the soup input, picking a seed if it doesn't have one. The soup is
+    /// bookmarked, named after everything needed to make it again
+    fn randomize(&mut self) {
+        let soup = match Soup::parse(self.soup_input.get_text(), self.random_seed()) {
+            Ok(soup) => soup,
+            Err(err) => {
+                self.show_message(err);
+                return
+            },
+        };
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        // Soups that don't fit leave the history alone
+        let snapshot = Self::snapshot(&self.history, game_of_life, renderer);
+        if let Err(err) = game_of_life.fill_soup(renderer.device(), renderer.queue(), &soup) {
+            self.show_message(format!("Failed to randomize: {}", err));
+            return
+        }
+        if let Some(snapshot) = snapshot {
+            self.history.record(snapshot);
+        }
+        self.show_message(format!("Randomized with {}", soup));
+        self.soup = Some(soup);
+        if self.dimensions.is_some() {
+            self.add_bookmark(Some(format!("soup {}", soup)));
+        }
+    }
+
+    /// Makes the edit to the whole board, which can be undone. Edits that fail leave the history alone
+    fn edit(&mut self, edit: Edit) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let snapshot = Self::snapshot(&self.history, game_of_life, renderer);
+        match game_of_life.edit(renderer.device(), renderer.queue(), &edit) {
+            Ok(()) => {
+                if let Some(snapshot) = snapshot {
+                    self.history.record(snapshot);
+                }
+                self.show_message(format!("Edited the board with {}", edit));
+            },
+            Err(err) => self.show_message(format!("Failed to edit the board: {}", err)),
+        }
+    }
+
+    /// Shows `message` in the bottom bar until the next one
+    fn show_message(&mut self, message: String) {
+        eprintln!("{}", message);
+        self.message_text.set_text(message);
+    }
+
+    /// Starts computing up to the generation in the jump input, a few generations each update so the window
+    /// keeps responding. The jump can be undone
+    fn start_jump(&mut self) {
+        let generation = self.game_of_life.as_ref().unwrap().generation();
+        match self.jump_input.get_text().trim().parse::<u64>() {
+            Ok(target) if target >= generation => {
+                let renderer = self.renderer.as_ref().unwrap();
+                Self::record(&mut self.history, self.game_of_life.as_ref().unwrap(), renderer);
+                self.jump = Some((generation, target));
+                self.show_message(format!("Jumping to gen {}", target));
+            },
+            Ok(target) => self.show_message(format!("Can't jump back to gen {} from gen {}", target, generation)),
+            Err(_) => self.show_message(format!("Failed to parse \"{}\" as a generation", self.jump_input.get_text())),
+        }
+    }
+
+    /// Computes the next generations of the jump, waiting for the GPU to be done with the last ones so they
+    /// don't pile up faster than it computes them. Shows how far along the jump is
+    fn continue_jump(&mut self, start: u64, target: u64) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        if !game_of_life.stats_pending() {
+            let generations = target.saturating_sub(game_of_life.generation()).min(MAX_GENERATIONS_PER_SUBMIT);
+            if let Err(err) = game_of_life.compute_n(renderer.device(), renderer.queue(), generations) {
+                self.compute_failed(err);
+                return
+            }
+        }
+        let generation = game_of_life.generation();
+        if generation >= target {
+            self.jump = None;
+            self.show_message(format!("Jumped to gen {}", target));
+        } else {
+            // Undoing while jumping can go back past where the jump started
+            let progress = generation.saturating_sub(start) as f64/(target-start) as f64*100.0;
+            self.message_text.set_text(format!("Jumping to gen {}, {:.1}% done", target, progress));
+        }
+    }
+
+    /// Jumps 2^k generations ahead, k being what's in the jump input
+    fn jump_pow2(&mut self) {
+        let k = match self.jump_input.get_text().trim().parse::<u32>() {
+            Ok(k) => k,
+            Err(_) => {
+                self.show_message(format!("Failed to parse \"{}\" as a power of 2", self.jump_input.get_text()));
+                return
+            },
+        };
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let snapshot = Self::snapshot(&self.history, game_of_life, renderer);
+        match game_of_life.jump_pow2(renderer.device(), renderer.queue(), k) {
+            Ok(()) => {
+                if let Some(snapshot) = snapshot {
+                    self.history.record(snapshot);
+                }
+                self.jump = None;
+                self.show_message(format!("Jumped 2^{} generations", k));
+            },
+            Err(err) => self.show_message(format!("Failed to jump 2^{} generations: {}", k, err)),
+        }
+    }
+
+    fn set_running(&mut self, running: bool) {
+        self.running = running;
+        self.run_until = None;
+        if running {
+            self.stopped = None;
+        }
+        self.next_generation = Instant::now();
+    }
+
+    /// Pauses after computing stopped partway, like when an unbounded board ran out of room for chunks
+    fn compute_failed(&mut self, err: String) {
+        self.set_running(false);
+        self.jump = None;
+        let generation = self.game_of_life.as_ref().unwrap().generation();
+        self.show_message(format!("Paused at gen {}: {}", generation, err));
+    }
+
+    /// Runs the game until the condition in the until input holds, unless it already does
+    fn start_run_until(&mut self) {
+        let condition = match self.until_input.get_text().parse::<StopCondition>() {
+            Ok(condition) => condition,
+            Err(err) => {
+                self.show_message(err);
+                return
+            },
+        };
+        let mut run_until = RunUntil::new(condition);
+        let generation = self.game_of_life.as_ref().unwrap().generation();
+        let already = if run_until.needs_stats() {
+            // Waits on the stats of generations already computed, so the run only checks ones after
+            self.renderer.as_ref().unwrap().device().poll(wgpu::Maintain::Wait);
+            self.poll_stats();
+            match self.game_of_life.as_ref().unwrap().stats() {
+                Some(stats) if stats.generation == generation => run_until.check(&stats),
+                Some(_) => None,
+                None => {
+                    self.show_message("Unbounded boards can only run until a generation".to_string());
+                    return
+                },
+            }
+        } else {
+            match condition {
+                StopCondition::Generation(target) if target <= generation => Some(format!("generation {} is already past", target)),
+                _ => None,
+            }
+        };
+        if let Some(reason) = already {
+            self.show_message(format!("Not running, {}", reason));
+            return
+        }
+        self.set_running(true);
+        self.run_until = Some(run_until);
+    }
+
+    /// Computes up to `generations` while running until a condition holds. The condition is checked on the stats
+    /// copied back from the GPU in `poll_stats`, so the next generations are only computed once the last ones have
+    /// been checked, to not run far past where it holds
+    fn compute_until(run_until: &RunUntil, game_of_life: &mut Storage, renderer: &Renderer, generations: u64) -> Result<Option<String>, String> {
+        if let StopCondition::Generation(target) = run_until.condition() {
+            game_of_life.compute_n(renderer.device(), renderer.queue(), generations.min(target.saturating_sub(game_of_life.generation())))?;
+            return Ok((game_of_life.generation() >= target).then(|| format!("reached generation {}", target)))
+        }
+        if !game_of_life.stats_pending() {
+            game_of_life.compute_n(renderer.device(), renderer.queue(), generations)?;
+        }
+        Ok(None)
+    }
+
+    /// Stops running until a condition because it held at `generation`, going back to it if the board ran past it
+    fn stop_run_until(&mut self, generation: u64, reason: String) {
+        self.set_running(false);
+        let current = self.game_of_life.as_ref().unwrap().generation();
+        let stopped = if generation < current && !self.go_to(generation) {
+            format!("Stopped {} gens past gen {}: {}", current-generation, generation, reason)
+        } else {
+            format!("Stopped at gen {}: {}", generation, reason)
+        };
+        eprintln!("{}", stopped);
+        self.stopped = Some(stopped);
+    }
+
+    /// Picks up stats copied back from the GPU, returning whether the HUD or graph need to show new ones
+    fn poll_stats(&mut self) -> bool {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        game_of_life.poll_stats(renderer.device());
+        let taken = game_of_life.take_stats();
+        let graph_changed = self.show_graph && !taken.is_empty();
+        let stats_changed = game_of_life.stats() != self.hud_stats;
+        let cycle = self.cycle_detector.cycle();
+        let mut found = false;
+        let mut stop = None;
+        for stats in taken {
+            self.graph.push(stats);
+            if let Some(cycle) = self.cycle_detector.push(stats) {
+                self.show_message(format!("Found a cycle: {}", cycle));
+                found = true;
+            }
+            if let Some(run_until) = self.run_until.as_mut().filter(|_| stop.is_none()) {
+                stop = run_until.check(&stats).map(|reason| (stats.generation, reason));
+            }
+        }
+        let stopped = stop.is_some();
+        if let Some((generation, reason)) = stop {
+            self.stop_run_until(generation, reason);
+        } else if found && self.auto_pause && self.running {
+            self.set_running(false);
+        }
+        graph_changed || stats_changed || stopped || self.cycle_detector.cycle() != cycle
+    }
+
+    /// The auto-pause button showing, which turns auto-pausing on or off
+    fn auto_pause_button(&mut self) -> &mut Button<Text> {
+        if self.auto_pause {
+            &mut self.auto_pause_on_button
+        } else {
+            &mut self.auto_pause_off_button
+        }
+    }
+
+    /// The graph button showing, which shows the graph, switches it to a log scale or hides it
+    fn graph_button(&mut self) -> &mut Button<Text> {
+        match (self.show_graph, self.graph.scale()) {
+            (false, _) => &mut self.graph_button,
+            (true, GraphScale::Linear) => &mut self.log_graph_button,
+            (true, GraphScale::Log) => &mut self.hide_graph_button,
+        }
+    }
+
+    fn click_graph_button(&mut self) {
+        match (self.show_graph, self.graph.scale()) {
+            (false, _) => self.show_graph = true,
+            (true, GraphScale::Linear) => self.graph.set_scale(GraphScale::Log),
+            (true, GraphScale::Log) => {
+                self.show_graph = false;
+                self.graph.set_scale(GraphScale::Linear);
+            },
+        }
+    }
+
+    /// Computes the generations that are due while the game is running, and shows any new stats
+    pub fn update(&mut self) -> RedrawCallback {
+        if let Some((start, target)) = self.jump {
+            self.continue_jump(start, target);
+            self.poll_stats();
+            self.construct();
+            return RedrawCallback::new(true)
+        }
+        if !self.running {
+            let stats_changed = self.poll_stats();
+            if stats_changed {
+                self.construct();
+            }
+            return RedrawCallback::new(stats_changed)
+        }
+
+        let now = Instant::now();
+        let generations = match GENERATIONS_PER_SECOND.get(self.speed) {
+            Some(generations_per_second) => {
+                let interval = Duration::from_secs(1)/(*generations_per_second);
+                let mut generations = 0;
+                while self.next_generation <= now && generations < MAX_GENERATIONS_PER_FRAME {
+                    self.next_generation += interval;
+                    generations += 1;
+                }
+                if self.next_generation <= now {
+                    // Too far behind to catch up
+                    self.next_generation = now+interval;
+                }
+                generations
+            },
+            None => MAX_GENERATIONS_PER_FRAME,
+        };
+
+        if generations == 0 {
+            let stats_changed = self.poll_stats();
+            if stats_changed {
+                self.construct();
+            }
+            return RedrawCallback::new(stats_changed)
+        }
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        if now.duration_since(self.last_recorded) >= RUNNING_HISTORY_INTERVAL {
+            Self::record(&mut self.history, game_of_life, renderer);
+            self.last_recorded = now;
+        }
+        let stop = match self.run_until.as_ref() {
+            Some(run_until) => Self::compute_until(run_until, game_of_life, renderer, generations as u64).map(|stop| stop.map(|reason| (game_of_life.generation(), reason))),
+            None => game_of_life.compute_n(renderer.device(), renderer.queue(), generations as u64).map(|()| None),
+        };
+        match stop {
+            Ok(Some((generation, reason))) => self.stop_run_until(generation, reason),
+            Ok(None) => {},
+            Err(err) => self.compute_failed(err),
+        }
+        self.poll_stats();
+        self.construct();
+        RedrawCallback::new(true)
+    }
+
+    /// When the event loop should wake up to call `update` again, if it should at all
+    pub fn next_update(&self) -> Option<Instant> {
+        if self.jump.is_some() {
+            Some(Instant::now()+STATS_POLL_INTERVAL)
+        } else if !self.running {
+            self.game_of_life.as_ref().filter(|game_of_life| game_of_life.stats_pending()).map(|_| Instant::now()+STATS_POLL_INTERVAL)
+        } else if self.speed < GENERATIONS_PER_SECOND.len() {
+            Some(self.next_generation)
+        } else {
+            Some(Instant::now())
+        }
+    }
+
+    /// Snapshots the board for the history, unless it's turned off or the board is unbounded
+    fn snapshot(history: &History, game_of_life: &Storage, renderer: &Renderer) -> Option<Snapshot> {
+        if history.is_enabled() {
+            game_of_life.snapshot(renderer.device(), renderer.queue())
+        } else {
+            None
+        }
+    }
+
+    /// Records the board in the history before it changes
+    fn record(history: &mut History, game_of_life: &Storage, renderer: &Renderer) {
+        if let Some(snapshot) = Self::snapshot(history, game_of_life, renderer) {
+            history.record(snapshot);
+        }
+    }
+
+    /// Puts the board back the way it was before the last change. Returns whether it did
+    fn undo(&mut self) -> bool {
+        if !self.history.can_undo() {
+            self.show_message("Nothing to undo".to_string());
+            return false
+        }
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let snapshot = self.history.undo(game_of_life.snapshot(renderer.device(), renderer.queue()).unwrap()).unwrap();
+        game_of_life.restore(renderer.queue(), &snapshot);
+        true
+    }
+
+    /// Makes the last undone change again. Returns whether it did
+    fn redo(&mut self) -> bool {
+        if !self.history.can_redo() {
+            self.show_message("Nothing to redo".to_string());
+            return false
+        }
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let snapshot = self.history.redo(game_of_life.snapshot(renderer.device(), renderer.queue()).unwrap()).unwrap();
+        game_of_life.restore(renderer.queue(), &snapshot);
+        true
+    }
+
+    /// Goes back to the previous generation, undoing any changes made since. Returns whether it did
+    fn back(&mut self) -> bool {
+        match self.game_of_life.as_ref().unwrap().generation().checked_sub(1) {
+            Some(generation) => self.go_to(generation),
+            None => {
+                self.show_message("Can't go back from gen 0".to_string());
+                false
+            },
+        }
+    }
+
+    /// Goes to `generation`, undoing or redoing changes to get there. Returns whether it did. Generations too
+    /// far past the nearest snapshot, like in the gap a jump 2^k leaves, aren't gone to
+    fn go_to(&mut self, generation: u64) -> bool {
+        let current = self.game_of_life.as_ref().unwrap().generation();
+        match self.history.computes_from(current, generation) {
+            None => {
+                self.show_message(format!("No history goes back to gen {}", generation));
+                return false
+            },
+            Some(from) if generation-from > MAX_GO_TO_GENERATIONS => {
+                self.show_message(format!("Gen {} is too far past gen {} to compute", generation, from));
+                return false
+            },
+            Some(_) => {},
+        }
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        let snapshot = if generation < current {
+            self.history.back_to(game_of_life.snapshot(renderer.device(), renderer.queue()).unwrap(), generation)
+        } else if self.history.can_redo() {
+            self.history.forward_to(game_of_life.snapshot(renderer.device(), renderer.queue()).unwrap(), generation)
+        } else {
+            None
+        };
+        if let Some(snapshot) = snapshot {
+            game_of_life.restore(renderer.queue(), &snapshot);
+        }
+        if let Err(err) = game_of_life.compute_n(renderer.device(), renderer.queue(), generation-game_of_life.generation()) {
+            self.compute_failed(err);
+            return false
+        }
+        true
+    }
+
+    /// Bookmarks the board, named `name` or after what's in the bookmark input or its generation
+    fn add_bookmark(&mut self, name: Option<String>) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_ref().unwrap();
+        let snapshot = match game_of_life.snapshot(renderer.device(), renderer.queue()) {
+            Some(snapshot) => snapshot,
+            None => {
+                self.show_message("Unbounded boards can't be bookmarked".to_string());
+                return
+            },
+        };
+        let name = name.unwrap_or_else(|| match self.bookmark_input.get_text().trim() {
+            "" => format!("gen {}", snapshot.generation()),
+            name => name.to_string(),
+        });
+        self.bookmarks.push(Bookmark {
+            name,
+            snapshot,
+        });
+        if let Some(Err(err)) = self.session.as_ref().map(|session| session.save(&self.bookmarks)) {
+            self.show_message(err);
+        }
+    }
+
+    /// Puts the board back the way it was when it was bookmarked. Going there can be undone
+    fn go_to_bookmark(&mut self, bookmark: usize) {
+        let renderer = self.renderer.as_ref().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        Self::record(&mut self.history, game_of_life, renderer);
+        game_of_life.restore(renderer.queue(), &self.bookmarks[bookmark].snapshot);
+    }
+
+    /// Shows where the board is on the timeline, and what dragging on it would go to
+    fn update_timeline(&mut self) {
+        let generation = self.game_of_life.as_ref().unwrap().generation();
+        let bookmarks: Vec<u64> = self.bookmarks.iter().map(|bookmark| bookmark.snapshot.generation()).collect();
+        let (start, end) = self.history.generation_range().unwrap_or((generation, generation));
+        let start = bookmarks.iter().copied().chain([start, generation]).min().unwrap();
+        let end = bookmarks.iter().copied().chain([end, generation]).max().unwrap();
+        self.timeline.set_generations(start, end, generation, bookmarks);
+        self.generation_text.set_text(match self.timeline.hovered() {
+            Some(TimelinePick::Generation(generation)) => format!("Go to gen {}", generation),
+            Some(TimelinePick::Bookmark(bookmark)) => format!("Go to {}", self.bookmarks[bookmark].name),
+            None => format!("Gen {}", generation),
+        });
+    }
+
     fn calculate_game_of_life_space(bottom_bar_sy: f32) -> SizeAndCenter {
         SizeAndCenter {
             sx: 1.0,
@@ -56,6 +822,8 @@ impl Game {
         let renderer = self.renderer.as_mut().unwrap();
         let text_renderer = self.text_renderer.as_ref().unwrap();
         let game_of_life = self.game_of_life.as_mut().unwrap();
+        let overlay = self.overlay.as_ref().unwrap();
+        let hud_renderer = self.hud_renderer.as_ref().unwrap();
         match renderer.surface().get_current_texture() {
             Ok(surface_texture) => {
                 let view = surface_texture.texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -65,6 +833,8 @@ impl Game {
 
                 text_renderer.render_all(&mut encoder, &view, wgpu::LoadOp::Clear(wgpu::Color::WHITE));
                 game_of_life.render(&mut encoder, &view, wgpu::LoadOp::Load);
+                overlay.render(&mut encoder, &view);
+                hud_renderer.render_all(&mut encoder, &view, wgpu::LoadOp::Load);
 
                 renderer.queue().submit(std::iter::once(encoder.finish()));
                 surface_texture.present();
@@ -85,14 +855,92 @@ impl Game {
     }
 
     pub fn construct(&mut self) {
-        let renderer = self.renderer.as_mut().unwrap();
-        let text_renderer = self.text_renderer.as_mut().unwrap();
+        self.update_timeline();
         let mut text_lines = LineTarget(Vec::new());
         text_lines.append(self.exit_button.construct());
         text_lines.append(self.step_button.construct());
+        if self.running {
+            text_lines.append(self.pause_button.construct());
+        } else {
+            text_lines.append(self.play_button.construct());
+        }
+        text_lines.append(self.slower_button.construct());
+        text_lines.append(self.speed_text.construct());
+        text_lines.append(self.faster_button.construct());
+        text_lines.append(self.jump_input.construct());
+        text_lines.append(self.jump_button.construct());
+        text_lines.append(self.jump_pow2_button.construct());
+        text_lines.append(self.back_button.construct());
+        text_lines.append(self.undo_button.construct());
+        text_lines.append(self.redo_button.construct());
+        text_lines.append(self.generation_text.construct());
+        text_lines.append(self.bookmark_input.construct());
+        text_lines.append(self.mark_button.construct());
+        text_lines.append(self.until_input.construct());
+        text_lines.append(self.until_button.construct());
+        text_lines.append(self.graph_button().construct());
+        text_lines.append(self.auto_pause_button().construct());
+        text_lines.append(self.palette_button.construct());
+        text_lines.append(self.palette_text.construct());
+        text_lines.append(self.heatmap_button.construct());
+        text_lines.append(self.heat_text.construct());
+        text_lines.append(self.reset_heat_button.construct());
+        text_lines.append(self.trails_button.construct());
+        text_lines.append(self.trails_text.construct());
+        text_lines.append(self.soup_input.construct());
+        text_lines.append(self.randomize_button.construct());
+        text_lines.append(self.clear_button.construct());
+        text_lines.append(self.invert_button.construct());
+        text_lines.append(self.edit_input.construct());
+        text_lines.append(self.transform_button.construct());
+        text_lines.append(self.message_text.construct());
+        let text_renderer = self.text_renderer.as_mut().unwrap();
+        let renderer = self.renderer.as_mut().unwrap();
         text_renderer.set_line_buffer(renderer.queue(), text_lines.0.as_slice());
-        
+
         let game_of_life = self.game_of_life.as_mut().unwrap();
+        let mut rects = self.timeline.rects();
+        let mut hud_lines = LineTarget(Vec::new());
+        self.hud_stats = game_of_life.stats();
+        if let Some(stats) = self.hud_stats {
+            self.hud_text.set_text(format!("Pop {} (+{} -{})", stats.population, stats.births, stats.deaths));
+            hud_lines.append(self.hud_text.construct());
+            rects.push(OverlayRect::new(HUD_AREA, HUD_BACKGROUND));
+            if let Some(cycle) = self.cycle_detector.cycle() {
+                self.cycle_text.set_text(cycle.to_string());
+                hud_lines.append(self.cycle_text.construct());
+                rects.push(OverlayRect::new(CYCLE_AREA, HUD_BACKGROUND));
+            }
+            if let Some(soup) = self.soup {
+                self.soup_text.set_text(format!("Soup {}", soup));
+                hud_lines.append(self.soup_text.construct());
+                rects.push(OverlayRect::new(SOUP_AREA, HUD_BACKGROUND));
+            }
+        }
+        if let Some(stopped) = &self.stopped {
+            self.stopped_text.set_text(stopped.clone());
+            hud_lines.append(self.stopped_text.construct());
+            rects.push(OverlayRect::new(STOPPED_AREA, HUD_BACKGROUND));
+        }
+        // Going back in the history leaves stats of generations that are now ahead of the board
+        self.graph.truncate(game_of_life.generation());
+        let graph_lines = if self.show_graph && self.hud_stats.is_some() {
+            let scale = match self.graph.scale() {
+                GraphScale::Linear => "",
+                GraphScale::Log => ", log scale",
+            };
+            self.graph_text.set_text(format!("Pop / births / deaths, max {}{}", self.graph.max(), scale));
+            hud_lines.append(self.graph_text.construct());
+            rects.push(OverlayRect::new(GRAPH_AREA, HUD_BACKGROUND));
+            self.graph.lines()
+        } else {
+            Vec::new()
+        };
+        self.hud_renderer.as_mut().unwrap().set_line_buffer(renderer.queue(), hud_lines.0.as_slice());
+        let overlay = self.overlay.as_mut().unwrap();
+        overlay.set_rects(renderer.device(), renderer.queue(), &rects);
+        overlay.set_lines(renderer.device(), renderer.queue(), &graph_lines);
+        
         game_of_life.construct(renderer.queue());
     }
 }
@@ -102,6 +950,30 @@ impl Init for Game {
         self.fill(SizeAndCenter::FULL);
         self.exit_button.init();
         self.step_button.init();
+        self.play_button.init();
+        self.pause_button.init();
+        self.slower_button.init();
+        self.faster_button.init();
+        self.jump_button.init();
+        self.jump_pow2_button.init();
+        self.back_button.init();
+        self.undo_button.init();
+        self.redo_button.init();
+        self.mark_button.init();
+        self.until_button.init();
+        self.graph_button.init();
+        self.log_graph_button.init();
+        self.hide_graph_button.init();
+        self.auto_pause_off_button.init();
+        self.auto_pause_on_button.init();
+        self.palette_button.init();
+        self.heatmap_button.init();
+        self.reset_heat_button.init();
+        self.trails_button.init();
+        self.randomize_button.init();
+        self.clear_button.init();
+        self.invert_button.init();
+        self.transform_button.init();
     }
 }
 
@@ -113,25 +985,265 @@ impl Fill for Game {
             centery: 0.0,
             resx: self.resx,
             resy: self.resy,
-            aspect: 20.0,
+            aspect: 5.0,
         }.into();
         bottom_bar_area.cy = -1.0+bottom_bar_area.sy;
         self.bottom_bar_sy = bottom_bar_area.sy;
 
+        // Timeline along the top row, buttons along the second one, view settings along the third one and edits
+        // of the board along the bottom one
+        self.generation_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.999,
+            p1y: 0.97,
+            p2x: -0.72,
+            p2y: 0.53,
+        }.into()));
+
+        self.timeline.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.7,
+            p1y: 0.97,
+            p2x: -0.26,
+            p2y: 0.53,
+        }.into()));
+
+        let auto_pause_area = bottom_bar_area.get_relative(Points {
+            p1x: -0.24,
+            p1y: 0.97,
+            p2x: -0.02,
+            p2y: 0.53,
+        }.into());
+        self.auto_pause_off_button.fill(auto_pause_area);
+        self.auto_pause_on_button.fill(auto_pause_area);
+
+        self.bookmark_input.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.0,
+            p1y: 0.97,
+            p2x: 0.22,
+            p2y: 0.53,
+        }.into()));
+
+        self.mark_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.24,
+            p1y: 0.97,
+            p2x: 0.44,
+            p2y: 0.53,
+        }.into()));
+
+        self.until_input.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.48,
+            p1y: 0.97,
+            p2x: 0.76,
+            p2y: 0.53,
+        }.into()));
+
+        self.until_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.78,
+            p1y: 0.97,
+            p2x: 0.999,
+            p2y: 0.53,
+        }.into()));
+
         self.exit_button.fill(bottom_bar_area.get_relative(Points {
             p1x: -0.999,
-            p1y: 0.95,
-            p2x: -0.7,
-            p2y: -0.95,
+            p1y: 0.47,
+            p2x: -0.86,
+            p2y: 0.03,
         }.into()));
 
         self.step_button.fill(bottom_bar_area.get_relative(Points {
-            p1x: -0.6,
-            p1y: 0.95,
-            p2x: 0.6,
-            p2y: -0.95,
+            p1x: -0.84,
+            p1y: 0.47,
+            p2x: -0.7,
+            p2y: 0.03,
+        }.into()));
+
+        let play_pause_area = bottom_bar_area.get_relative(Points {
+            p1x: -0.68,
+            p1y: 0.47,
+            p2x: -0.54,
+            p2y: 0.03,
+        }.into());
+        self.play_button.fill(play_pause_area);
+        self.pause_button.fill(play_pause_area);
+
+        self.slower_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.52,
+            p1y: 0.47,
+            p2x: -0.48,
+            p2y: 0.03,
+        }.into()));
+
+        self.speed_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.47,
+            p1y: 0.47,
+            p2x: -0.31,
+            p2y: 0.03,
+        }.into()));
+
+        self.faster_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.3,
+            p1y: 0.47,
+            p2x: -0.26,
+            p2y: 0.03,
+        }.into()));
+
+        self.back_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.22,
+            p1y: 0.47,
+            p2x: -0.1,
+            p2y: 0.03,
+        }.into()));
+
+        self.undo_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.08,
+            p1y: 0.47,
+            p2x: 0.04,
+            p2y: 0.03,
         }.into()));
 
+        self.redo_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.06,
+            p1y: 0.47,
+            p2x: 0.18,
+            p2y: 0.03,
+        }.into()));
+
+        self.jump_input.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.22,
+            p1y: 0.47,
+            p2x: 0.38,
+            p2y: 0.03,
+        }.into()));
+
+        self.jump_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.4,
+            p1y: 0.47,
+            p2x: 0.54,
+            p2y: 0.03,
+        }.into()));
+
+        self.jump_pow2_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.56,
+            p1y: 0.47,
+            p2x: 0.76,
+            p2y: 0.03,
+        }.into()));
+
+        let graph_button_area = bottom_bar_area.get_relative(Points {
+            p1x: 0.78,
+            p1y: 0.47,
+            p2x: 0.999,
+            p2y: 0.03,
+        }.into());
+        self.graph_button.fill(graph_button_area);
+        self.log_graph_button.fill(graph_button_area);
+        self.hide_graph_button.fill(graph_button_area);
+
+        self.palette_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.999,
+            p1y: -0.03,
+            p2x: -0.84,
+            p2y: -0.47,
+        }.into()));
+
+        self.palette_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.82,
+            p1y: -0.03,
+            p2x: -0.66,
+            p2y: -0.47,
+        }.into()));
+
+        self.heatmap_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.64,
+            p1y: -0.03,
+            p2x: -0.48,
+            p2y: -0.47,
+        }.into()));
+
+        self.heat_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.46,
+            p1y: -0.03,
+            p2x: -0.2,
+            p2y: -0.47,
+        }.into()));
+
+        self.reset_heat_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.18,
+            p1y: -0.03,
+            p2x: -0.02,
+            p2y: -0.47,
+        }.into()));
+
+        self.trails_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.0,
+            p1y: -0.03,
+            p2x: 0.16,
+            p2y: -0.47,
+        }.into()));
+
+        self.trails_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.18,
+            p1y: -0.03,
+            p2x: 0.38,
+            p2y: -0.47,
+        }.into()));
+
+        self.soup_input.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.4,
+            p1y: -0.03,
+            p2x: 0.7,
+            p2y: -0.47,
+        }.into()));
+
+        self.randomize_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: 0.72,
+            p1y: -0.03,
+            p2x: 0.9,
+            p2y: -0.47,
+        }.into()));
+
+        self.clear_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.999,
+            p1y: -0.53,
+            p2x: -0.84,
+            p2y: -0.97,
+        }.into()));
+
+        self.invert_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.82,
+            p1y: -0.53,
+            p2x: -0.66,
+            p2y: -0.97,
+        }.into()));
+
+        self.edit_input.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.64,
+            p1y: -0.53,
+            p2x: -0.34,
+            p2y: -0.97,
+        }.into()));
+
+        self.transform_button.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.32,
+            p1y: -0.53,
+            p2x: -0.16,
+            p2y: -0.97,
+        }.into()));
+
+        self.message_text.fill(bottom_bar_area.get_relative(Points {
+            p1x: -0.14,
+            p1y: -0.53,
+            p2x: 0.999,
+            p2y: -0.97,
+        }.into()));
+
+        self.hud_text.fill(HUD_AREA.into());
+        self.cycle_text.fill(CYCLE_AREA.into());
+        self.soup_text.fill(SOUP_AREA.into());
+        self.stopped_text.fill(STOPPED_AREA.into());
+        self.graph_text.fill(GRAPH_LABEL_AREA.into());
+        self.graph.fill(GRAPH_LINES_AREA.into());
+
         if let Some(game_of_life) = self.game_of_life.as_mut() {
             game_of_life.fill(Self::calculate_game_of_life_space(self.bottom_bar_sy));
         }
@@ -147,10 +1259,51 @@ impl SignalReciever<ResizedSignal, RedrawCallback> for Game {
         renderer.resize(signal.resxp, signal.resyp);
         let text_renderer = self.text_renderer.as_mut().unwrap();
         text_renderer.on_resize(renderer.device(), signal.resxp, signal.resyp);
+        self.hud_renderer.as_mut().unwrap().on_resize(renderer.device(), signal.resxp, signal.resyp);
 
         // TODO: does calculations that are redone after this fills. Don't
         self.exit_button.take_signal(signal);
         self.step_button.take_signal(signal);
+        self.play_button.take_signal(signal);
+        self.pause_button.take_signal(signal);
+        self.slower_button.take_signal(signal);
+        self.speed_text.take_signal(signal);
+        self.faster_button.take_signal(signal);
+        self.jump_input.take_signal(signal);
+        self.jump_button.take_signal(signal);
+        self.jump_pow2_button.take_signal(signal);
+        self.back_button.take_signal(signal);
+        self.undo_button.take_signal(signal);
+        self.redo_button.take_signal(signal);
+        self.generation_text.take_signal(signal);
+        self.bookmark_input.take_signal(signal);
+        self.mark_button.take_signal(signal);
+        self.until_input.take_signal(signal);
+        self.until_button.take_signal(signal);
+        self.graph_button.take_signal(signal);
+        self.log_graph_button.take_signal(signal);
+        self.hide_graph_button.take_signal(signal);
+        self.auto_pause_off_button.take_signal(signal);
+        self.auto_pause_on_button.take_signal(signal);
+        self.palette_button.take_signal(signal);
+        self.palette_text.take_signal(signal);
+        self.heatmap_button.take_signal(signal);
+        self.heat_text.take_signal(signal);
+        self.reset_heat_button.take_signal(signal);
+        self.trails_button.take_signal(signal);
+        self.trails_text.take_signal(signal);
+        self.soup_input.take_signal(signal);
+        self.randomize_button.take_signal(signal);
+        self.clear_button.take_signal(signal);
+        self.invert_button.take_signal(signal);
+        self.edit_input.take_signal(signal);
+        self.transform_button.take_signal(signal);
+        self.message_text.take_signal(signal);
+        self.hud_text.take_signal(signal);
+        self.graph_text.take_signal(signal);
+        self.cycle_text.take_signal(signal);
+        self.soup_text.take_signal(signal);
+        self.stopped_text.take_signal(signal);
         
         let game_of_life = self.game_of_life.as_mut().unwrap();
         game_of_life.take_signal(signal);
@@ -158,65 +1311,660 @@ impl SignalReciever<ResizedSignal, RedrawCallback> for Game {
         self.fill(SizeAndCenter::FULL);
         self.construct();
 
-        RedrawCallback::new(true)
+        RedrawCallback::new(true)
+    }
+}
+
+impl SignalReciever<CursorMovedSignal, RedrawCallback> for Game {
+    fn take_signal(&mut self, signal: &mut CursorMovedSignal) -> RedrawCallback {
+        self.exit_button.take_signal(signal);
+        self.step_button.take_signal(signal);
+        self.play_button.take_signal(signal);
+        self.pause_button.take_signal(signal);
+        self.slower_button.take_signal(signal);
+        self.faster_button.take_signal(signal);
+        self.jump_input.take_signal(signal);
+        self.jump_button.take_signal(signal);
+        self.jump_pow2_button.take_signal(signal);
+        self.back_button.take_signal(signal);
+        self.undo_button.take_signal(signal);
+        self.redo_button.take_signal(signal);
+        self.bookmark_input.take_signal(signal);
+        self.mark_button.take_signal(signal);
+        self.until_input.take_signal(signal);
+        self.until_button.take_signal(signal);
+        self.graph_button.take_signal(signal);
+        self.log_graph_button.take_signal(signal);
+        self.hide_graph_button.take_signal(signal);
+        self.auto_pause_off_button.take_signal(signal);
+        self.auto_pause_on_button.take_signal(signal);
+        self.palette_button.take_signal(signal);
+        self.heatmap_button.take_signal(signal);
+        self.reset_heat_button.take_signal(signal);
+        self.trails_button.take_signal(signal);
+        self.soup_input.take_signal(signal);
+        self.randomize_button.take_signal(signal);
+        self.clear_button.take_signal(signal);
+        self.invert_button.take_signal(signal);
+        self.edit_input.take_signal(signal);
+        self.transform_button.take_signal(signal);
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+        game_of_life.take_signal(signal);
+        if self.timeline.take_signal(signal) {
+            self.construct();
+            RedrawCallback::new(true)
+        } else {
+            RedrawCallback::new(false)
+        }
+    }
+}
+
+impl SignalReciever<MouseLeftDownSignal, RedrawCallback> for Game {
+    fn take_signal(&mut self, signal: &mut MouseLeftDownSignal) -> RedrawCallback {
+        self.exit_button.take_signal(signal);
+        self.step_button.take_signal(signal);
+        if self.running {
+            self.pause_button.take_signal(signal);
+        } else {
+            self.play_button.take_signal(signal);
+        }
+        self.slower_button.take_signal(signal);
+        self.faster_button.take_signal(signal);
+        self.jump_button.take_signal(signal);
+        self.jump_pow2_button.take_signal(signal);
+        self.back_button.take_signal(signal);
+        self.undo_button.take_signal(signal);
+        self.redo_button.take_signal(signal);
+        self.mark_button.take_signal(signal);
+        self.until_button.take_signal(signal);
+        self.graph_button().take_signal(signal);
+        self.auto_pause_button().take_signal(signal);
+        self.palette_button.take_signal(signal);
+        self.heatmap_button.take_signal(signal);
+        self.reset_heat_button.take_signal(signal);
+        self.trails_button.take_signal(signal);
+        self.randomize_button.take_signal(signal);
+        self.clear_button.take_signal(signal);
+        self.invert_button.take_signal(signal);
+        self.transform_button.take_signal(signal);
+        if self.timeline.take_signal(signal) {
+            self.construct();
+            RedrawCallback::new(true)
+        } else {
+            RedrawCallback::new(false)
+        }
+    }
+}
+
+impl SignalReciever<MouseLeftUpSignal, (GameSetupCallback, RedrawCallback)> for Game {
+    fn take_signal(&mut self, signal: &mut MouseLeftUpSignal) -> (GameSetupCallback, RedrawCallback) {
+        let mut redraw_callback = RedrawCallback::new(false);
+        let mut game_setup_callback = GameSetupCallback::None;
+
+        let mut reconstruct = false;
+        let play_pause_clicked = if self.running {
+            self.pause_button.take_signal(signal)
+        } else {
+            self.play_button.take_signal(signal)
+        };
+        if play_pause_clicked == ClickedCallback::Clicked {
+            self.set_running(!self.running);
+            reconstruct = true;
+        }
+        if self.slower_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.set_speed(self.speed.saturating_sub(1));
+            reconstruct = true;
+        }
+        if self.faster_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.set_speed(self.speed+1);
+            reconstruct = true;
+        }
+        self.until_input.take_signal(signal);
+        if self.until_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.start_run_until();
+            reconstruct = true;
+        }
+        if self.graph_button().take_signal(signal) == ClickedCallback::Clicked {
+            self.click_graph_button();
+            reconstruct = true;
+        }
+        if self.auto_pause_button().take_signal(signal) == ClickedCallback::Clicked {
+            self.auto_pause = !self.auto_pause;
+            reconstruct = true;
+        }
+        if self.palette_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.next_palette();
+            reconstruct = true;
+        }
+        if self.heatmap_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.next_heatmap();
+            reconstruct = true;
+        }
+        if self.reset_heat_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.game_of_life.as_ref().unwrap().reset_heat(self.renderer.as_ref().unwrap().queue());
+            redraw_callback = RedrawCallback::new(true);
+        }
+        if self.trails_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.next_trail_length();
+            reconstruct = true;
+        }
+        self.soup_input.take_signal(signal);
+        if self.randomize_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.randomize();
+            reconstruct = true;
+        }
+        if self.clear_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.edit(Edit::Clear);
+            reconstruct = true;
+        }
+        if self.invert_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.edit(Edit::Invert);
+            reconstruct = true;
+        }
+        self.edit_input.take_signal(signal);
+        if self.transform_button.take_signal(signal) == ClickedCallback::Clicked {
+            match Edit::parse(self.edit_input.get_text()) {
+                Ok(edit) => self.edit(edit),
+                Err(err) => self.show_message(err),
+            }
+            reconstruct = true;
+        }
+        if reconstruct {
+            self.construct();
+            redraw_callback = RedrawCallback::new(true);
+        }
+        if self.back_button.take_signal(signal) == ClickedCallback::Clicked {
+            // Redrawn either way to show why it couldn't go back
+            self.back();
+            redraw_callback = RedrawCallback::new(true);
+        }
+        if self.undo_button.take_signal(signal) == ClickedCallback::Clicked && self.undo() {
+            redraw_callback = RedrawCallback::new(true);
+        }
+        if self.redo_button.take_signal(signal) == ClickedCallback::Clicked && self.redo() {
+            redraw_callback = RedrawCallback::new(true);
+        }
+        self.bookmark_input.take_signal(signal);
+        if self.mark_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.add_bookmark(None);
+            redraw_callback = RedrawCallback::new(true);
+        }
+        match self.timeline.take_signal(signal) {
+            Some(TimelinePick::Generation(generation)) => {
+                self.go_to(generation);
+                redraw_callback = RedrawCallback::new(true);
+            },
+            Some(TimelinePick::Bookmark(bookmark)) => {
+                self.go_to_bookmark(bookmark);
+                redraw_callback = RedrawCallback::new(true);
+            },
+            None => {},
+        }
+
+        self.jump_input.take_signal(signal);
+        if self.jump_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.start_jump();
+            self.construct();
+            redraw_callback = RedrawCallback::new(true);
+        }
+        if self.jump_pow2_button.take_signal(signal) == ClickedCallback::Clicked {
+            self.jump_pow2();
+            self.construct();
+            redraw_callback = RedrawCallback::new(true);
+        }
+
+        let renderer = self.renderer.as_mut().unwrap();
+        let game_of_life = self.game_of_life.as_mut().unwrap();
+
+        if self.exit_button.take_signal(signal) == ClickedCallback::Clicked {
+            game_setup_callback = GameSetupCallback::Setup;
+        }
+        // Recorded before, as the board may have changed partway when these fail
+        let mut step_failed = None;
+        if self.step_button.take_signal(signal) == ClickedCallback::Clicked {
+            Self::record(&mut self.history, game_of_life, renderer);
+            step_failed = game_of_life.compute(renderer.device(), renderer.queue()).err();
+            redraw_callback = RedrawCallback::new(true);
+        }
+
+        let mut toggle_failed = None;
+        if let Some((x, y)) = game_of_life.take_signal(signal) {
+            Self::record(&mut self.history, game_of_life, renderer);
+            if let Err(err) = game_of_life.toggle(x, y, renderer.device(), renderer.queue()) {
+                toggle_failed = Some(format!("Failed to toggle ({}, {}): {}", x, y, err));
+            }
+            redraw_callback = RedrawCallback::new(true);
+        }
+
+        if let Some(err) = step_failed {
+            self.compute_failed(err);
+        }
+        if let Some(message) = toggle_failed {
+            self.show_message(message);
+        }
+
+        if redraw_callback.get_redraw() {
+            self.construct();
+        }
+        (game_setup_callback, redraw_callback)
+    }
+}
+
+impl SignalReciever<CharacterInputSignal, RedrawCallback> for Game {
+    fn take_signal(&mut self, signal: &mut CharacterInputSignal) -> RedrawCallback {
+        let mut reconstruct_callback = self.jump_input.take_signal(signal);
+        reconstruct_callback.or(self.bookmark_input.take_signal(signal));
+        reconstruct_callback.or(self.until_input.take_signal(signal));
+        reconstruct_callback.or(self.soup_input.take_signal(signal));
+        reconstruct_callback.or(self.edit_input.take_signal(signal));
+        if reconstruct_callback.get_reconstruct() {
+            self.construct();
+            RedrawCallback::new(true)
+        } else {
+            RedrawCallback::new(false)
+        }
+    }
+}
+
+/// Ctrl+Z undoes, Ctrl+Y or Ctrl+Shift+Z redoes and Ctrl+Left goes back a generation. Anything else goes to the board
+impl SignalReciever<KeyPressedSignal, RedrawCallback> for Game {
+    fn take_signal(&mut self, signal: &mut KeyPressedSignal) -> RedrawCallback {
+        let ctrl = signal.modifiers.ctrl() || signal.modifiers.logo();
+        let changed = match signal.key {
+            VirtualKeyCode::Z if ctrl && signal.modifiers.shift() => Some(self.redo()),
+            VirtualKeyCode::Z if ctrl => Some(self.undo()),
+            VirtualKeyCode::Y if ctrl => Some(self.redo()),
+            VirtualKeyCode::Left if ctrl => {
+                // Redrawn either way to show why it couldn't go back
+                self.back();
+                Some(true)
+            },
+            _ => None,
+        };
+        if let Some(changed) = changed {
+            if changed {
+                self.construct();
+            }
+            return RedrawCallback::new(changed)
+        }
+
+        let redraw_callback = self.game_of_life.as_mut().unwrap().take_signal(signal);
+        if redraw_callback.get_redraw() {
+            self.construct();
+        }
+        redraw_callback
+    }
+}
+
+pub enum GameSetupCallback {
+    None,
+    Setup,
+}
+
+enum Board {
+    A,
+    B,
+}
+
+bui::typed_uniform!(ToggleCellUniform, [i32; 2], "Toggle Cell Uniform");
+bui::typed_uniform!(RuleUniform, [u32; 2], "Rule Uniform");
+bui::typed_uniform!(TopologyUniform, [u32; 2], "Topology Uniform");
+
+/// Where a board is kept on the GPU. Boards that fit in textures are, bigger ones are packed, and
+/// unbounded ones are chunked
+// There is one board at a time, so boxing the bigger ones saves nothing
+#[allow(clippy::large_enum_variant)]
+pub enum Storage {
+    Texture(GameOfLife),
+    Packed(PackedGameOfLife),
+    Chunked(ChunkedGameOfLife),
+}
+
+impl Storage {
+    /// Checks that a `width` by `height` board fits in some storage on a device with `limits`
+    pub fn check_size(width: u32, height: u32, limits: &wgpu::Limits) -> Result<(), String> {
+        GameOfLife::check_size(width, height, limits).or_else(|_| PackedGameOfLife::check_size(width, height, limits))
+    }
+
+    /// Storage for a board of `dimensions`, or an unbounded board if there are none
+    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat, dimensions: Option<(u32, u32)>, rule: Rule, topology: Topology, resx: f32, resy: f32) -> Self {
+        match dimensions {
+            Some((width, height)) if GameOfLife::check_size(width, height, &device.limits()).is_ok() => Storage::Texture(GameOfLife::new(device, texture_format, width, height, rule, topology, resx, resy)),
+            Some((width, height)) => Storage::Packed(PackedGameOfLife::new(device, texture_format, width, height, rule, topology, resx, resy)),
+            None => Storage::Chunked(ChunkedGameOfLife::new(device, texture_format, rule, resx, resy)),
+        }
+    }
+
+    pub fn compute(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<(), String> {
+        self.compute_n(device, queue, 1)
+    }
+
+    /// Computes `n` generations. Only unbounded boards can fail, when they run out of room for chunks,
+    /// having computed the generations before that
+    pub fn compute_n(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, n: u64) -> Result<(), String> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.compute_n(device, queue, n),
+            Storage::Packed(game_of_life) => game_of_life.compute_n(device, queue, n),
+            Storage::Chunked(game_of_life) => return game_of_life.compute_n(device, queue, n),
+        }
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.generation(),
+            Storage::Packed(game_of_life) => game_of_life.generation(),
+            Storage::Chunked(game_of_life) => game_of_life.generation(),
+        }
+    }
+
+    pub fn render(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, loadop: wgpu::LoadOp<wgpu::Color>) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.render(encoder, view, loadop),
+            Storage::Packed(game_of_life) => game_of_life.render(encoder, view, loadop),
+            Storage::Chunked(game_of_life) => game_of_life.render(encoder, view, loadop),
+        }
+    }
+
+    pub fn construct(&mut self, queue: &wgpu::Queue) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.construct(queue),
+            Storage::Packed(game_of_life) => game_of_life.construct(queue),
+            Storage::Chunked(game_of_life) => game_of_life.construct(queue),
+        }
+    }
+
+    /// Flips the cell at (x, y). Unbounded boards can run out of chunks for it
+    pub fn toggle(&mut self, x: i64, y: i64, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<(), String> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.toggle(x as i32, y as i32, device, queue),
+            Storage::Packed(game_of_life) => game_of_life.toggle(x as i32, y as i32, device, queue),
+            Storage::Chunked(game_of_life) => return game_of_life.toggle(x, y, device, queue),
+        }
+        Ok(())
+    }
+
+    /// Copies a bounded board back to the CPU, or None for an unbounded one
+    pub fn read_board(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<CpuBoard> {
+        match self {
+            Storage::Texture(game_of_life) => Some(game_of_life.read_board(device, queue)),
+            Storage::Packed(game_of_life) => Some(game_of_life.read_board(device, queue)),
+            Storage::Chunked(_) => None,
+        }
+    }
+
+    pub fn palette(&self) -> Option<&AgePalette> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.palette(),
+            Storage::Packed(_) | Storage::Chunked(_) => None,
+        }
+    }
+
+    /// Colours cells by age with `palette`, or by where they are if it's None. Only texture boards keep track of ages
+    pub fn set_palette(&mut self, palette: Option<AgePalette>) -> Result<(), String> {
+        match (self, palette) {
+            (Storage::Texture(game_of_life), palette) => {
+                game_of_life.set_palette(palette);
+                Ok(())
+            },
+            (_, None) => Ok(()),
+            (_, Some(_)) => Err("only texture boards keep track of cell ages".to_string()),
+        }
+    }
+
+    pub fn heatmap_enabled(&self) -> bool {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.heatmap_enabled(),
+            Storage::Packed(_) | Storage::Chunked(_) => false,
+        }
+    }
+
+    /// Shows how many times each cell has changed state instead of the board. Only texture boards keep track of heat
+    pub fn set_heatmap(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, enabled: bool) -> Result<(), String> {
+        match (self, enabled) {
+            (Storage::Texture(game_of_life), enabled) => game_of_life.set_heatmap(device, queue, enabled),
+            (_, false) => Ok(()),
+            (_, true) => Err("only texture boards keep track of heat".to_string()),
+        }
+    }
+
+    pub fn heat_half_life(&self) -> Option<u32> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.heat_half_life(),
+            Storage::Packed(_) | Storage::Chunked(_) => None,
+        }
+    }
+
+    pub fn set_heat_half_life(&mut self, queue: &wgpu::Queue, half_life: Option<u32>) {
+        if let Storage::Texture(game_of_life) = self {
+            game_of_life.set_heat_half_life(queue, half_life);
+        }
+    }
+
+    pub fn reset_heat(&self, queue: &wgpu::Queue) {
+        if let Storage::Texture(game_of_life) = self {
+            game_of_life.reset_heat(queue);
+        }
+    }
+
+    pub fn trail_length(&self) -> u32 {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.trail_length(),
+            Storage::Packed(_) | Storage::Chunked(_) => 0,
+        }
+    }
+
+    /// Draws the last `length` generations behind the board. Only texture boards keep past generations
+    pub fn set_trail_length(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, length: u32) -> Result<(), String> {
+        match (self, length) {
+            (Storage::Texture(game_of_life), length) => game_of_life.set_trail_length(device, queue, length),
+            (_, 0) => Ok(()),
+            (_, _) => Err("only texture boards keep past generations".to_string()),
+        }
+    }
+
+    /// Fills the soup's area with it. Texture boards are filled on the GPU, others get the same cells from the
+    /// CPU. Unbounded boards have no middle, so they can only be filled in a region
+    pub fn fill_soup(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, soup: &Soup) -> Result<(), String> {
+        match self {
+            Storage::Texture(game_of_life) => return game_of_life.fill_soup(device, queue, soup),
+            Storage::Packed(game_of_life) => {
+                let mut board = game_of_life.read_board(device, queue);
+                soup.check(board.width(), board.height())?;
+                soup.fill(&mut board);
+                game_of_life.write_board(queue, &board);
+            },
+            Storage::Chunked(game_of_life) => {
+                let region = match soup.area {
+                    SoupArea::Region(region) => region,
+                    _ => return Err("unbounded boards can only be randomized in a region".to_string()),
+                };
+                // Nothing is past the edge of an unbounded board
+                soup.check_box([region.width, region.height])?;
+                let mut board = CpuBoard::new(region.width, region.height);
+                for y in 0..region.height {
+                    for x in 0..region.width {
+                        board.set(x, y, soup.alive_in_region(&region, x, y));
+                    }
+                }
+                return game_of_life.write_region(device, queue, region.x, region.y, &board)
+            },
+        }
+        Ok(())
+    }
+
+    /// Makes the edit to every cell of the board. Texture boards are edited on the GPU, packed ones get the same
+    /// cells from the CPU. Unbounded boards have infinitely many dead cells, so they can only be cleared
+    pub fn edit(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, edit: &Edit) -> Result<(), String> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.edit(device, queue, edit),
+            Storage::Packed(game_of_life) => {
+                let board = game_of_life.read_board(device, queue);
+                game_of_life.write_board(queue, &edit.apply(&board));
+            },
+            Storage::Chunked(game_of_life) => match edit {
+                Edit::Clear => game_of_life.clear(queue),
+                _ => return Err(format!("unbounded boards can't be edited with {}", edit)),
+            },
+        }
+        Ok(())
+    }
+
+    /// Replaces a bounded board with the cells of `board`. Unbounded boards are written with `ChunkedGameOfLife::write_region`
+    pub fn write_board(&mut self, queue: &wgpu::Queue, board: &CpuBoard) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.write_board(queue, board),
+            Storage::Packed(game_of_life) => game_of_life.write_board(queue, board),
+            Storage::Chunked(_) => unreachable!("unbounded boards can't be written as a whole"),
+        }
+    }
+
+    /// Snapshots a bounded board for the history, or None for an unbounded one
+    pub fn snapshot(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<Snapshot> {
+        match self {
+            Storage::Texture(game_of_life) => Some(game_of_life.snapshot(device, queue)),
+            Storage::Packed(game_of_life) => Some(game_of_life.snapshot(device, queue)),
+            Storage::Chunked(_) => None,
+        }
+    }
+
+    /// Puts a bounded board back the way it was in `snapshot`
+    pub fn restore(&mut self, queue: &wgpu::Queue, snapshot: &Snapshot) {
+        self.set_generation(snapshot.generation());
+        self.write_board(queue, &snapshot.to_board());
+    }
+
+    /// Picks up the stats that have been copied back since this was last called. Unbounded boards don't count any
+    pub fn poll_stats(&mut self, device: &wgpu::Device) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.poll_stats(device),
+            Storage::Packed(game_of_life) => game_of_life.poll_stats(device),
+            Storage::Chunked(_) => {},
+        }
+    }
+
+    /// Population of a bounded board, and the births and deaths that led to it, as of the last stats picked up
+    pub fn stats(&self) -> Option<GenerationStats> {
+        match self {
+            Storage::Texture(game_of_life) => Some(game_of_life.stats()),
+            Storage::Packed(game_of_life) => Some(game_of_life.stats()),
+            Storage::Chunked(_) => None,
+        }
+    }
+
+    /// Stats of every generation picked up since this was last called, oldest first
+    pub fn take_stats(&mut self) -> Vec<GenerationStats> {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.take_stats(),
+            Storage::Packed(game_of_life) => game_of_life.take_stats(),
+            Storage::Chunked(_) => Vec::new(),
+        }
+    }
+
+    pub fn stats_pending(&self) -> bool {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.stats_pending(),
+            Storage::Packed(game_of_life) => game_of_life.stats_pending(),
+            Storage::Chunked(_) => false,
+        }
+    }
+
+    pub fn topology(&self) -> Topology {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.topology(),
+            Storage::Packed(game_of_life) => game_of_life.topology(),
+            Storage::Chunked(_) => Topology::PLANE,
+        }
+    }
+
+    pub fn set_generation(&mut self, generation: u64) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.set_generation(generation),
+            Storage::Packed(game_of_life) => game_of_life.set_generation(generation),
+            Storage::Chunked(game_of_life) => game_of_life.set_generation(generation),
+        }
     }
-}
 
-impl SignalReciever<CursorMovedSignal, ()> for Game {
-    fn take_signal(&mut self, signal: &mut CursorMovedSignal) {
-        self.exit_button.take_signal(signal);
-        self.step_button.take_signal(signal);
-        let game_of_life = self.game_of_life.as_mut().unwrap();
-        game_of_life.take_signal(signal);
+    /// Jumps 2^k generations ahead with HashLife. HashLife runs on an unbounded plane, so this only works for
+    /// bounded boards with dead edges. Where cells end up past the edge, computing would have stopped them there
+    /// instead, so the jump fails and leaves the board alone. Cells that only go past the edge for a while and
+    /// die out there can't be told apart this way
+    pub fn jump_pow2(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, k: u32) -> Result<(), String> {
+        if let Storage::Chunked(game_of_life) = self {
+            return game_of_life.jump_pow2(device, queue, k)
+        }
+        if self.topology() != Topology::PLANE {
+            return Err(format!("HashLife only runs on a plane, not {}", self.topology()))
+        }
+        let board = self.read_board(device, queue).unwrap();
+        let mut hashlife = HashLife::from_board(&board)?;
+        hashlife.jump_pow2(k)?;
+        let jumped = hashlife.to_board(board.width(), board.height());
+        if jumped.population() as u64 != hashlife.population() {
+            return Err(format!("{} cells would end up past the edge of the board, step or jump there instead", hashlife.population()-jumped.population() as u64))
+        }
+        let generation = self.generation().checked_add(hashlife.generation()).ok_or("the generation overflows")?;
+        self.set_generation(generation);
+        self.write_board(queue, &jumped);
+        Ok(())
     }
 }
 
-impl SignalReciever<MouseLeftDownSignal, ()> for Game {
-    fn take_signal(&mut self, signal: &mut MouseLeftDownSignal) {
-        self.exit_button.take_signal(signal);
-        self.step_button.take_signal(signal);
+impl Fill for Storage {
+    fn fill(&mut self, fill_target: SizeAndCenter) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.fill(fill_target),
+            Storage::Packed(game_of_life) => game_of_life.fill(fill_target),
+            Storage::Chunked(game_of_life) => game_of_life.fill(fill_target),
+        }
     }
 }
 
-impl SignalReciever<MouseLeftUpSignal, (GameSetupCallback, RedrawCallback)> for Game {
-    fn take_signal(&mut self, signal: &mut MouseLeftUpSignal) -> (GameSetupCallback, RedrawCallback) {
-        let mut redraw_callback = RedrawCallback::new(false);
-        let mut game_setup_callback = GameSetupCallback::None;
-        let renderer = self.renderer.as_mut().unwrap();
-        let game_of_life = self.game_of_life.as_mut().unwrap();
-
-        if self.exit_button.take_signal(signal) == ClickedCallback::Clicked {
-            game_setup_callback = GameSetupCallback::Setup;
-        }
-        if self.step_button.take_signal(signal) == ClickedCallback::Clicked {
-            game_of_life.compute(&renderer);
-            redraw_callback = RedrawCallback::new(true);
+impl SignalReciever<ResizedSignal, ()> for Storage {
+    fn take_signal(&mut self, signal: &mut ResizedSignal) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Packed(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Chunked(game_of_life) => game_of_life.take_signal(signal),
         }
+    }
+}
 
-        if let Some((x, y)) = game_of_life.take_signal(signal) {
-            game_of_life.toggle(x, y, &renderer);
-            redraw_callback = RedrawCallback::new(true);
+impl SignalReciever<CursorMovedSignal, ()> for Storage {
+    fn take_signal(&mut self, signal: &mut CursorMovedSignal) {
+        match self {
+            Storage::Texture(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Packed(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Chunked(game_of_life) => game_of_life.take_signal(signal),
         }
-
-        (game_setup_callback, redraw_callback)
     }
 }
 
-pub enum GameSetupCallback {
-    None,
-    Setup,
+impl SignalReciever<MouseLeftUpSignal, Option<(i64, i64)>> for Storage {
+    fn take_signal(&mut self, signal: &mut MouseLeftUpSignal) -> Option<(i64, i64)> {
+        let cell: Option<(i32, i32)> = match self {
+            Storage::Texture(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Packed(game_of_life) => game_of_life.take_signal(signal),
+            Storage::Chunked(game_of_life) => return game_of_life.take_signal(signal),
+        };
+        cell.map(|(x, y)| (x as i64, y as i64))
+    }
 }
 
-enum Board {
-    A,
-    B,
+/// Only unbounded boards can be moved around
+impl SignalReciever<KeyPressedSignal, RedrawCallback> for Storage {
+    fn take_signal(&mut self, signal: &mut KeyPressedSignal) -> RedrawCallback {
+        match self {
+            Storage::Chunked(game_of_life) => game_of_life.take_signal(signal),
+            _ => RedrawCallback::new(false),
+        }
+    }
 }
 
-bui::typed_uniform!(ToggleCellUniform, [i32; 2], "Toggle Cell Uniform");
-
 pub struct GameOfLife {
     board_a: wgpu::Texture,
     board_b: wgpu::Texture,
+    board_format: wgpu::TextureFormat,
     compute_pipeline: wgpu::ComputePipeline,
     board_bind_group_a: wgpu::BindGroup,
     board_bind_group_b: wgpu::BindGroup,
@@ -224,6 +1972,7 @@ pub struct GameOfLife {
     render_bind_group_a: wgpu::BindGroup,
     render_bind_group_b: wgpu::BindGroup,
     active_board: Board,
+    generation: u64,
     width: u32,
     height: u32,
     aspect: f32,
@@ -238,11 +1987,64 @@ pub struct GameOfLife {
     toggle_cell_uniform: ToggleCellUniform,
     toggle_bind_group_a: wgpu::BindGroup,
     toggle_bind_group_b: wgpu::BindGroup,
+    soup_pipeline: wgpu::ComputePipeline,
+    soup_uniform: SoupUniform,
+    soup_bind_group_a: wgpu::BindGroup,
+    soup_bind_group_b: wgpu::BindGroup,
+    edit_pipeline: wgpu::ComputePipeline,
+    edit_uniform: EditUniform,
+    edit_bind_group_a: wgpu::BindGroup,
+    edit_bind_group_b: wgpu::BindGroup,
+    /// Adds the cells an edit changed to the stats
+    edit_stats_pipeline: wgpu::ComputePipeline,
+    rule: Rule,
+    rule_uniform: RuleUniform,
+    topology: Topology,
+    topology_uniform: TopologyUniform,
+    /// Whether only tiles that can change are computed
+    track_tiles: bool,
+    tiles: u32,
+    changed_a: wgpu::Buffer,
+    changed_b: wgpu::Buffer,
+    dispatch_args: wgpu::Buffer,
+    reset_tiles_pipeline: wgpu::ComputePipeline,
+    gather_tiles_pipeline: wgpu::ComputePipeline,
+    gather_bind_group_a: wgpu::BindGroup,
+    gather_bind_group_b: wgpu::BindGroup,
+    active_pipeline: wgpu::ComputePipeline,
+    active_bind_group_a: wgpu::BindGroup,
+    active_bind_group_b: wgpu::BindGroup,
+    stats: Stats,
+    /// Generation each cell last changed at, which ages are counted from
+    changed_at: wgpu::Texture,
+    /// How cells are coloured by age, or None to colour them by where they are
+    palette: Option<AgePalette>,
+    palette_uniform: AgePaletteUniform,
+    heatmap: Heatmap,
+    /// Past generations drawn behind the board
+    trails: Trails,
 }
 
 impl GameOfLife {
-    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat, width: u32, height: u32, resx: f32, resy: f32) -> Self {
-        let compute_shader = device.create_shader_module(wgpu::include_wgsl!("game_of_life.wgsl"));
+    /// Checks that a `width` by `height` board fits in textures on a device with `limits`
+    pub fn check_size(width: u32, height: u32, limits: &wgpu::Limits) -> Result<(), String> {
+        let max_size = MAX_TEXTURE_BOARD_SIZE.min(limits.max_texture_dimension_2d);
+        if width > max_size {
+            Err(format!("width exceeds the maximum of {}", max_size))
+        } else if height > max_size {
+            Err(format!("height exceeds the maximum of {}", max_size))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat, width: u32, height: u32, rule: Rule, topology: Topology, resx: f32, resy: f32) -> Self {
+        let board_format = Self::board_format(device);
+        let compute_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("game_of_life.wgsl"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("game_of_life.wgsl").replace("r32uint", board_format_name(board_format)).into()),
+        });
 
         let board_a = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("Game of Life board A"),
@@ -254,7 +2056,7 @@ impl GameOfLife {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba16Float,
+            format: board_format,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
         });
 
@@ -270,12 +2072,28 @@ impl GameOfLife {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba16Float,
+            format: board_format,
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
         });
 
         let board_b_view = board_b.create_view(&wgpu::TextureViewDescriptor::default());
 
+        let changed_at = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Game of Life changed at"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                ..Default::default()
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R32Sint,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_DST,
+        });
+
+        let changed_at_view = changed_at.create_view(&wgpu::TextureViewDescriptor::default());
+
         let board_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("Game of Life board bind group layout"),
             entries: &[
@@ -285,7 +2103,7 @@ impl GameOfLife {
                     ty: wgpu::BindingType::Texture {
                         multisampled: false,
                         view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true }
+                        sample_type: wgpu::TextureSampleType::Uint
                     },
                     count: None,
                 },
@@ -295,13 +2113,50 @@ impl GameOfLife {
                     ty: wgpu::BindingType::StorageTexture {
                         access: wgpu::StorageTextureAccess::WriteOnly,
                         view_dimension: wgpu::TextureViewDimension::D2,
-                        format: wgpu::TextureFormat::Rgba16Float,
+                        format: board_format,
                     },
                     count: None,
-                }
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                Stats::layout_entry(),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        format: wgpu::TextureFormat::R32Sint,
+                    },
+                    count: None,
+                },
             ]
         });
 
+        let rule_uniform = RuleUniform::new(device);
+        let topology_uniform = TopologyUniform::new(device);
+        let stats = Stats::new(device, &compute_shader, &board_bind_group_layout);
+        let heatmap = Heatmap::new(device, width, height);
+        let trails = Trails::new(device, width, height, board_format);
+
         let board_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &board_bind_group_layout,
             entries: &[
@@ -312,7 +2167,20 @@ impl GameOfLife {
                 wgpu::BindGroupEntry {
                     binding: 1,
                     resource: wgpu::BindingResource::TextureView(&board_b_view)
-                }
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rule_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: topology_uniform.binding()
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
             ],
             label: Some("Game of Life board bind group A")
         });
@@ -327,17 +2195,232 @@ impl GameOfLife {
                 wgpu::BindGroupEntry {
                     binding: 1,
                     resource: wgpu::BindingResource::TextureView(&board_a_view)
-                }
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rule_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: topology_uniform.binding()
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
             ],
             label: Some("Game of Life board bind group B")
         });
 
+        // Every tile is active for the first generation
+        let tiles = dispatch_size(width)*dispatch_size(height);
+        let changed_a = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Game of Life changed tiles A"),
+            contents: bytemuck::cast_slice(&vec![1u32; tiles as usize]),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let changed_b = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Game of Life changed tiles B"),
+            contents: bytemuck::cast_slice(&vec![1u32; tiles as usize]),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        });
+
+        // The number of active tiles followed by their indices
+        let active_tiles = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Game of Life active tiles"),
+            size: (tiles as u64+1)*4,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        let dispatch_args = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Game of Life active tiles dispatch"),
+            contents: bytemuck::cast_slice(&[0u32, 0, 1]),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+        });
+
+        let gather_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Game of Life gather tiles bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }
+            ]
+        });
+
+        let gather_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &gather_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: changed_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: changed_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: active_tiles.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: dispatch_args.as_entire_binding(),
+                }
+            ],
+            label: Some("Game of Life gather tiles bind group A")
+        });
+
+        let gather_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &gather_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: changed_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: changed_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: active_tiles.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: dispatch_args.as_entire_binding(),
+                }
+            ],
+            label: Some("Game of Life gather tiles bind group B")
+        });
+
+        let gather_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Game of Life gather tiles pipeline layout"),
+            bind_group_layouts: &[
+                &board_bind_group_layout,
+                &gather_bind_group_layout
+            ],
+            push_constant_ranges: &[],
+        });
+
+        let reset_tiles_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life reset tiles pipeline"),
+            layout: Some(&gather_pipeline_layout),
+            module: &compute_shader,
+            entry_point: "reset_tiles"
+        });
+
+        let gather_tiles_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life gather tiles pipeline"),
+            layout: Some(&gather_pipeline_layout),
+            module: &compute_shader,
+            entry_point: "gather_tiles"
+        });
+
+        let active_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Game of Life active tiles bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }
+            ]
+        });
+
+        let active_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &active_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: active_tiles.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: changed_b.as_entire_binding(),
+                }
+            ],
+            label: Some("Game of Life active tiles bind group A")
+        });
+
+        let active_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &active_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: active_tiles.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: changed_a.as_entire_binding(),
+                }
+            ],
+            label: Some("Game of Life active tiles bind group B")
+        });
+
         let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
             label: Some("Game of Life compute pipeline"),
             layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Game of Life compute pipeline layout"),
                 bind_group_layouts: &[
-                    &board_bind_group_layout
+                    &board_bind_group_layout,
+                    // Unused, but the heatmap comes after it
+                    &gather_bind_group_layout,
+                    heatmap.compute_layout()
                 ],
                 push_constant_ranges: &[],
             })),
@@ -345,14 +2428,19 @@ impl GameOfLife {
             entry_point: "compute_board"
         });
 
-        let render_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("Game of Life render sampler"),
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
+        let active_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life active tiles pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Game of Life active tiles pipeline layout"),
+                bind_group_layouts: &[
+                    &board_bind_group_layout,
+                    &active_bind_group_layout,
+                    heatmap.compute_layout()
+                ],
+                push_constant_ranges: &[],
+            })),
+            module: &compute_shader,
+            entry_point: "compute_active"
         });
 
         let render_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -364,19 +2452,35 @@ impl GameOfLife {
                     ty: wgpu::BindingType::Texture {
                         multisampled: false,
                         view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        sample_type: wgpu::TextureSampleType::Uint,
                     },
                     count: None
                 },
                 wgpu::BindGroupLayoutEntry {
                     binding: 1,
                     visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Sint,
+                    },
+                    count: None
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
                     count: None,
-                }
+                },
             ]
         });
 
+        let palette_uniform = AgePaletteUniform::new(device);
+
         let render_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("Game of Life render bind group A"),
             layout: &render_bind_group_layout,
@@ -387,7 +2491,11 @@ impl GameOfLife {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&render_sampler)
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: palette_uniform.binding()
                 },
             ]
         });
@@ -402,7 +2510,11 @@ impl GameOfLife {
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&render_sampler)
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: palette_uniform.binding()
                 },
             ]
         });
@@ -418,7 +2530,7 @@ impl GameOfLife {
             label: Some("Game of Life render pipeline"),
             layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Game of Life render pipeline layout"),
-                bind_group_layouts: &[&render_bind_group_layout],
+                bind_group_layouts: &[&render_bind_group_layout, heatmap.render_layout(), trails.layout()],
                 push_constant_ranges: &[],
             })),
             vertex: wgpu::VertexState {
@@ -474,7 +2586,7 @@ impl GameOfLife {
                     ty: wgpu::BindingType::Texture {
                         multisampled: false,
                         view_dimension: wgpu::TextureViewDimension::D2,
-                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        sample_type: wgpu::TextureSampleType::Uint,
                     },
                     count: None
                 },
@@ -484,10 +2596,21 @@ impl GameOfLife {
                     ty: wgpu::BindingType::StorageTexture {
                         access: wgpu::StorageTextureAccess::WriteOnly,
                         view_dimension: wgpu::TextureViewDimension::D2,
-                        format: wgpu::TextureFormat::Rgba16Float,
+                        format: board_format,
                     },
                     count: None,
-                }
+                },
+                Stats::layout_entry(),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 6,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        format: wgpu::TextureFormat::R32Sint,
+                    },
+                    count: None,
+                },
             ]
         });
 
@@ -508,7 +2631,12 @@ impl GameOfLife {
                 wgpu::BindGroupEntry {
                     binding: 2,
                     resource: wgpu::BindingResource::TextureView(&board_b_view)
-                }
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
             ]
         });
 
@@ -527,7 +2655,12 @@ impl GameOfLife {
                 wgpu::BindGroupEntry {
                     binding: 2,
                     resource: wgpu::BindingResource::TextureView(&board_a_view)
-                }
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
             ]
         });
 
@@ -544,37 +2677,152 @@ impl GameOfLife {
             entry_point: "toggle"
         });
 
+        // Edits of the whole board are bound like toggling, with what to do in the uniform
+        let soup_uniform = SoupUniform::new(device);
+
+        let soup_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Game of Life soup bind group A"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: soup_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&board_a_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&board_b_view)
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+            ]
+        });
+
+        let soup_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Game of Life soup bind group B"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: soup_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&board_b_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&board_a_view)
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+            ]
+        });
+
+        let edit_uniform = EditUniform::new(device);
+
+        let edit_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Game of Life edit bind group A"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: edit_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&board_a_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&board_b_view)
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+            ]
+        });
+
+        let edit_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Game of Life edit bind group B"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: edit_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&board_b_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&board_a_view)
+                },
+                stats.bind_group_entry(),
+                wgpu::BindGroupEntry {
+                    binding: 6,
+                    resource: wgpu::BindingResource::TextureView(&changed_at_view)
+                },
+            ]
+        });
+
+        let edit_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Game of Life edit pipeline layout"),
+            bind_group_layouts: &[
+                &toggle_bind_group_layout
+            ],
+            push_constant_ranges: &[]
+        });
+
+        let soup_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life soup pipeline"),
+            layout: Some(&edit_pipeline_layout),
+            module: &compute_shader,
+            entry_point: "fill_soup"
+        });
+
+        let edit_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life edit pipeline"),
+            layout: Some(&edit_pipeline_layout),
+            module: &compute_shader,
+            entry_point: "edit_board"
+        });
+
+        let edit_stats_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Game of Life edit stats pipeline"),
+            layout: Some(&edit_pipeline_layout),
+            module: &compute_shader,
+            entry_point: "add_edit_stats"
+        });
+
         Self {
             board_a,
             board_b,
             compute_pipeline,
             board_bind_group_a,
             board_bind_group_b,
+            board_format,
             active_board: Board::A,
+            generation: 0,
             render_pipeline,
             render_bind_group_a,
             render_bind_group_b,
             width,
             height,
             aspect: width as f32/height as f32,
-            vertices: [
-                GameOfLifeVertex {
-                    position: [0.0, 0.0],
-                    tex_coords: [1.0, 1.0]
-                },
-                GameOfLifeVertex {
-                    position: [0.0, 0.0],
-                    tex_coords: [1.0, 0.0]
-                },
-                GameOfLifeVertex {
-                    position: [0.0, 0.0],
-                    tex_coords: [0.0, 1.0]
-                },
-                GameOfLifeVertex {
-                    position: [0.0, 0.0],
-                    tex_coords: [0.0, 0.0]
-                }
-            ],
+            vertices: GameOfLifeVertex::QUAD,
             vertex_buffer,
             resx,
             resy,
@@ -585,33 +2833,140 @@ impl GameOfLife {
             toggle_cell_uniform,
             toggle_bind_group_a,
             toggle_bind_group_b,
+            soup_pipeline,
+            soup_uniform,
+            soup_bind_group_a,
+            soup_bind_group_b,
+            edit_pipeline,
+            edit_uniform,
+            edit_bind_group_a,
+            edit_bind_group_b,
+            edit_stats_pipeline,
+            rule,
+            rule_uniform,
+            topology,
+            topology_uniform,
+            track_tiles: true,
+            tiles,
+            changed_a,
+            changed_b,
+            dispatch_args,
+            reset_tiles_pipeline,
+            gather_tiles_pipeline,
+            gather_bind_group_a,
+            gather_bind_group_b,
+            active_pipeline,
+            active_bind_group_a,
+            active_bind_group_b,
+            stats,
+            changed_at,
+            palette: None,
+            palette_uniform,
+            heatmap,
+            trails,
         }
     }
 
-    pub fn compute(&mut self, renderer: &Renderer) {
-        let mut command_encoder = renderer.device().create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("Game of Life compute command encoder")
+    /// Picks the most compact format boards can be stored in on `device`. R8Uint isn't a storage
+    /// format in WebGPU, but some backends allow it. Anything that can run compute shaders can store R32Uint
+    pub fn board_format(device: &wgpu::Device) -> wgpu::TextureFormat {
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Game of Life board format probe"),
+            size: wgpu::Extent3d::default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Uint,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::STORAGE_BINDING,
         });
+        match futures::executor::block_on(device.pop_error_scope()) {
+            None => wgpu::TextureFormat::R8Uint,
+            Some(_) => wgpu::TextureFormat::R32Uint,
+        }
+    }
 
-        let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-            label: Some("Game of Life compute pass")
-        });
+    pub fn compute(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.compute_n(device, queue, 1);
+    }
 
-        compute_pass.set_pipeline(&self.compute_pipeline);
-        match self.active_board {
-            Board::A => {
-                compute_pass.set_bind_group(0, &self.board_bind_group_a, &[]);
-                self.active_board = Board::B;
-            },
-            Board::B => {
-                compute_pass.set_bind_group(0, &self.board_bind_group_b, &[]);
-                self.active_board = Board::A;
-            },
+    /// Computes `n` generations, recording up to `MAX_GENERATIONS_PER_SUBMIT` of them per command buffer.
+    /// While tracking tiles, each generation lists the tiles that can change then computes only those.
+    /// The stats of each command buffer's generations are read back after it. The last few generations
+    /// are copied into the trail between compute passes
+    pub fn compute_n(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, n: u64) {
+        let mut remaining = n;
+        while remaining > 0 {
+            let generations = remaining.min(MAX_GENERATIONS_PER_SUBMIT);
+            remaining -= generations;
+            self.stats.start(queue, self.generation);
+
+            let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Game of Life compute command encoder")
+            });
+
+            let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Game of Life compute pass")
+            });
+
+            for i in 0..generations {
+                if self.trails.records(remaining+generations-i) {
+                    drop(compute_pass);
+                    let board = match self.active_board {
+                        Board::A => &self.board_a,
+                        Board::B => &self.board_b,
+                    };
+                    self.trails.record(&mut command_encoder, board);
+                    compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                        label: Some("Game of Life compute pass")
+                    });
+                }
+                let (board_bind_group, gather_bind_group, active_bind_group) = match self.active_board {
+                    Board::A => {
+                        self.active_board = Board::B;
+                        (&self.board_bind_group_a, &self.gather_bind_group_a, &self.active_bind_group_a)
+                    },
+                    Board::B => {
+                        self.active_board = Board::A;
+                        (&self.board_bind_group_b, &self.gather_bind_group_b, &self.active_bind_group_b)
+                    },
+                };
+                if self.track_tiles {
+                    compute_pass.set_pipeline(&self.reset_tiles_pipeline);
+                    compute_pass.set_bind_group(0, board_bind_group, &[]);
+                    compute_pass.set_bind_group(1, gather_bind_group, &[]);
+                    compute_pass.dispatch_workgroups(1, 1, 1);
+                    compute_pass.set_pipeline(&self.gather_tiles_pipeline);
+                    compute_pass.dispatch_workgroups(dispatch_size(dispatch_size(self.width)), dispatch_size(dispatch_size(self.height)), 1);
+                    compute_pass.set_pipeline(&self.active_pipeline);
+                    compute_pass.set_bind_group(1, active_bind_group, &[]);
+                    compute_pass.set_bind_group(2, self.heatmap.compute_bind_group(), &[]);
+                    compute_pass.dispatch_workgroups_indirect(&self.dispatch_args, 0);
+                } else {
+                    compute_pass.set_pipeline(&self.compute_pipeline);
+                    compute_pass.set_bind_group(0, board_bind_group, &[]);
+                    compute_pass.set_bind_group(1, gather_bind_group, &[]);
+                    compute_pass.set_bind_group(2, self.heatmap.compute_bind_group(), &[]);
+                    compute_pass.dispatch_workgroups(dispatch_size(self.width), dispatch_size(self.height), 1);
+                }
+                self.stats.next(&mut compute_pass);
+            }
+
+            drop(compute_pass);
+            queue.submit(std::iter::once(command_encoder.finish()));
+            self.generation += generations;
+            self.stats.read_back(device, queue, self.generation, generations);
         }
-        compute_pass.dispatch_workgroups(self.width, self.height, 1);
+        self.set_colour_uniforms(queue);
+    }
 
-        drop(compute_pass);
-        renderer.queue().submit(std::iter::once(command_encoder.finish()));
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the generation counter, for when the board was replaced with a later generation
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
     }
 
     pub fn render(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, loadop: wgpu::LoadOp<wgpu::Color>) {
@@ -635,17 +2990,112 @@ impl GameOfLife {
             Board::A => render_pass.set_bind_group(0, &self.render_bind_group_a, &[]),
             Board::B => render_pass.set_bind_group(0, &self.render_bind_group_b, &[]),
         }
+        render_pass.set_bind_group(1, self.heatmap.render_bind_group(), &[]);
+        render_pass.set_bind_group(2, self.trails.bind_group(), &[]);
         render_pass.draw(0..4, 0..1);
     }
 
     pub fn construct(&self, queue: &wgpu::Queue) {
         queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice::<GameOfLifeVertex, u8>(&self.vertices));
+        self.rule_uniform.set(&[self.rule.birth() as u32, self.rule.survival() as u32], queue);
+        self.topology_uniform.set(&[self.topology.x.shader_value(), self.topology.y.shader_value()], queue);
+        self.set_colour_uniforms(queue);
+    }
+
+    /// Brings what cells are coloured by up to date with the generation
+    fn set_colour_uniforms(&self, queue: &wgpu::Queue) {
+        self.palette_uniform.set(&AgePalette::uniform(self.palette.as_ref(), self.generation), queue);
+        self.heatmap.set_uniform(queue, self.generation);
+        self.trails.set_uniform(queue);
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn palette(&self) -> Option<&AgePalette> {
+        self.palette.as_ref()
+    }
+
+    /// Colours cells by age with `palette`, or by where they are if it's None. Takes effect on `construct`
+    pub fn set_palette(&mut self, palette: Option<AgePalette>) {
+        self.palette = palette;
+    }
+
+    pub fn heatmap_enabled(&self) -> bool {
+        self.heatmap.enabled()
+    }
+
+    /// Shows how many times each cell has changed state instead of the board, counting from when it's turned
+    /// on. Fails if the heat of every cell wouldn't fit in a buffer
+    pub fn set_heatmap(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, enabled: bool) -> Result<(), String> {
+        self.heatmap.set_enabled(device, queue, enabled)?;
+        self.set_colour_uniforms(queue);
+        Ok(())
+    }
+
+    pub fn heat_half_life(&self) -> Option<u32> {
+        self.heatmap.half_life()
     }
 
-    pub fn toggle(&mut self, x: i32, y: i32, renderer: &Renderer) {
-        self.toggle_cell_uniform.set(&[x, y], renderer.queue());
+    /// Halves the heat of cells every `half_life` generations they don't change, or never if it's None
+    pub fn set_heat_half_life(&mut self, queue: &wgpu::Queue, half_life: Option<u32>) {
+        self.heatmap.set_half_life(half_life);
+        self.set_colour_uniforms(queue);
+    }
+
+    pub fn reset_heat(&self, queue: &wgpu::Queue) {
+        self.heatmap.reset(queue);
+    }
+
+    /// Heat of every cell, row by row, or all 0 while the heatmap is off
+    pub fn read_heat(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<f32> {
+        self.heatmap.read(device, queue, self.generation)
+    }
+
+    pub fn trail_length(&self) -> u32 {
+        self.trails.length()
+    }
+
+    /// The past generations drawn behind the board, the last one first
+    pub fn read_trail(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<CpuBoard> {
+        self.trails.read(device, queue)
+    }
+
+    /// Draws the last `length` generations behind the board, fading with age, or none if it's 0
+    pub fn set_trail_length(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, length: u32) -> Result<(), String> {
+        self.trails.set_length(device, length)?;
+        self.set_colour_uniforms(queue);
+        Ok(())
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.board_format
+    }
+
+    /// Turns computing only the tiles that can change on or off. It's on by default
+    pub fn set_tile_tracking(&mut self, queue: &wgpu::Queue, track_tiles: bool) {
+        if track_tiles && !self.track_tiles {
+            self.mark_all_changed(queue);
+        }
+        self.track_tiles = track_tiles;
+    }
 
-        let mut command_encoder = renderer.device().create_command_encoder(&wgpu::CommandEncoderDescriptor {
+    /// Makes every tile active for the next generation, for when the board changed outside of `compute_n`
+    fn mark_all_changed(&self, queue: &wgpu::Queue) {
+        let changed = vec![1u32; self.tiles as usize];
+        queue.write_buffer(&self.changed_a, 0, bytemuck::cast_slice(&changed));
+        queue.write_buffer(&self.changed_b, 0, bytemuck::cast_slice(&changed));
+    }
+
+    pub fn toggle(&mut self, x: i32, y: i32, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.toggle_cell_uniform.set(&[x, y], queue);
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Game of Life compute command encoder")
         });
 
@@ -662,9 +3112,9 @@ impl GameOfLife {
             }),
         }
 
-        renderer.queue().submit(std::iter::once(command_encoder.finish()));
+        queue.submit(std::iter::once(command_encoder.finish()));
 
-        let mut command_encoder = renderer.device().create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("Game of Life compute command encoder")
         });
 
@@ -686,7 +3136,203 @@ impl GameOfLife {
         compute_pass.dispatch_workgroups(1, 1, 1);
 
         drop(compute_pass);
-        renderer.queue().submit(std::iter::once(command_encoder.finish()));
+        queue.submit(std::iter::once(command_encoder.finish()));
+        self.mark_all_changed(queue);
+        self.stats.read_back(device, queue, self.generation, 0);
+    }
+
+    /// Replaces the cells in the soup's area with the soup's, on the GPU. Fails if the soup doesn't fit on the board
+    pub fn fill_soup(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, soup: &Soup) -> Result<(), String> {
+        soup.check(self.width, self.height)?;
+        self.soup_uniform.set(&soup.uniform(self.width, self.height), queue);
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Game of Life soup command encoder")
+        });
+
+        let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Game of Life soup pass")
+        });
+
+        compute_pass.set_pipeline(&self.soup_pipeline);
+        match self.active_board {
+            Board::A => {
+                compute_pass.set_bind_group(0, &self.soup_bind_group_a, &[]);
+                self.active_board = Board::B;
+            },
+            Board::B => {
+                compute_pass.set_bind_group(0, &self.soup_bind_group_b, &[]);
+                self.active_board = Board::A;
+            },
+        }
+        compute_pass.dispatch_workgroups(dispatch_size(self.width), dispatch_size(self.height), 1);
+        compute_pass.set_pipeline(&self.edit_stats_pipeline);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+
+        drop(compute_pass);
+        queue.submit(std::iter::once(command_encoder.finish()));
+        self.mark_all_changed(queue);
+        self.stats.read_back(device, queue, self.generation, 0);
+        Ok(())
+    }
+
+    /// Makes the edit to every cell of the board, on the GPU
+    pub fn edit(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, edit: &Edit) {
+        self.edit_uniform.set(&edit.uniform(self.width, self.height), queue);
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Game of Life edit command encoder")
+        });
+
+        let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Game of Life edit pass")
+        });
+
+        compute_pass.set_pipeline(&self.edit_pipeline);
+        match self.active_board {
+            Board::A => {
+                compute_pass.set_bind_group(0, &self.edit_bind_group_a, &[]);
+                self.active_board = Board::B;
+            },
+            Board::B => {
+                compute_pass.set_bind_group(0, &self.edit_bind_group_b, &[]);
+                self.active_board = Board::A;
+            },
+        }
+        compute_pass.dispatch_workgroups(dispatch_size(self.width), dispatch_size(self.height), 1);
+        compute_pass.set_pipeline(&self.edit_stats_pipeline);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+
+        drop(compute_pass);
+        queue.submit(std::iter::once(command_encoder.finish()));
+        self.mark_all_changed(queue);
+        self.stats.read_back(device, queue, self.generation, 0);
+    }
+
+    fn active_texture(&self) -> &wgpu::Texture {
+        match self.active_board {
+            Board::A => &self.board_a,
+            Board::B => &self.board_b,
+        }
+    }
+
+    /// Copies the active board back to the CPU. Blocks until the GPU is done with it
+    pub fn read_board(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> CpuBoard {
+        let mut board = CpuBoard::new(self.width, self.height);
+        board.set_rule(self.rule);
+        board.set_topology(self.topology);
+        self.read_cells(device, queue, |x, y, alive| board.set(x, y, alive));
+        board
+    }
+
+    /// Snapshots the active board straight from what's copied back, without making a `CpuBoard`
+    pub fn snapshot(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Snapshot {
+        let mut builder = SnapshotBuilder::new(self.width, self.height, self.generation);
+        self.read_cells(device, queue, |_, _, alive| builder.push_run(alive, 1));
+        builder.finish()
+    }
+
+    /// Copies the active board back to the CPU, calling `read` with each cell row by row. Blocks until the
+    /// GPU is done with it
+    fn read_cells(&self, device: &wgpu::Device, queue: &wgpu::Queue, mut read: impl FnMut(u32, u32, bool)) {
+        let texel_size = self.board_format.describe().block_size as u32;
+        let bytes_per_row = (self.width*texel_size).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)*wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Game of Life read buffer"),
+            size: bytes_per_row as u64*self.height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Game of Life read command encoder")
+        });
+        command_encoder.copy_texture_to_buffer(self.active_texture().as_image_copy(), wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: std::num::NonZeroU32::new(bytes_per_row),
+                rows_per_image: None,
+            }
+        }, wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        });
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let read_slice = read_buffer.slice(..);
+        read_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map Game of Life read buffer"));
+        device.poll(wgpu::Maintain::Wait);
+
+        {
+            let data = read_slice.get_mapped_range();
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    // Alive cells are 1, so the first byte is enough whatever the format
+                    read(x, y, data[(y*bytes_per_row+x*texel_size) as usize] == 1);
+                }
+            }
+        }
+        read_buffer.unmap();
+    }
+
+    /// Replaces the active board with the cells of `board`
+    pub fn write_board(&mut self, queue: &wgpu::Queue, board: &CpuBoard) {
+        assert!(board.width() == self.width && board.height() == self.height, "can't write a {}x{} board to a {}x{} Game of Life", board.width(), board.height(), self.width, self.height);
+        let texel_size = self.board_format.describe().block_size as u32;
+        let mut texels = vec![0u8; (self.width*texel_size) as usize*self.height as usize];
+        for (x, y) in board.alive_cells() {
+            texels[((y*self.width+x)*texel_size) as usize] = 1;
+        }
+        queue.write_texture(self.active_texture().as_image_copy(), &texels, wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: std::num::NonZeroU32::new(self.width*texel_size),
+            rows_per_image: None,
+        }, wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        });
+        self.mark_all_changed(queue);
+        self.stats.set_board(queue, self.generation, board);
+        self.trails.clear();
+
+        // Cells written alive were just born, dead ones died too long ago to show
+        let mut changed_at = vec![(self.generation as u32).wrapping_sub(1 << 31); (self.width*self.height) as usize];
+        for (x, y) in board.alive_cells() {
+            changed_at[(y*self.width+x) as usize] = self.generation as u32;
+        }
+        queue.write_texture(self.changed_at.as_image_copy(), bytemuck::cast_slice(&changed_at), wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: std::num::NonZeroU32::new(self.width*4),
+            rows_per_image: None,
+        }, wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        });
+        self.set_colour_uniforms(queue);
+    }
+
+    /// Picks up the stats that have been copied back since this was last called, without waiting for the GPU
+    pub fn poll_stats(&mut self, device: &wgpu::Device) {
+        self.stats.poll(device);
+    }
+
+    /// Population of the board, and the births and deaths that led to it, as of the last stats picked up
+    pub fn stats(&self) -> GenerationStats {
+        self.stats.latest()
+    }
+
+    /// Stats of every generation picked up since this was last called, oldest first
+    pub fn take_stats(&mut self) -> Vec<GenerationStats> {
+        self.stats.take()
+    }
+
+    /// Whether there are stats still being copied back
+    pub fn stats_pending(&self) -> bool {
+        self.stats.is_pending()
     }
 }
 
@@ -735,17 +3381,58 @@ impl SignalReciever<MouseLeftUpSignal, Option<(i32, i32)>> for GameOfLife {
     }
 }
 
-const GAME_OF_LIFE_VERTEX_SIZE: u64 = std::mem::size_of::<GameOfLifeVertex>() as u64;
+/// Most generations recorded into one command buffer by `GameOfLife::compute_n`
+pub(crate) const MAX_GENERATIONS_PER_SUBMIT: u64 = 1024;
+/// Width and height of a `compute_board` workgroup, in cells
+pub(crate) const COMPUTE_WORKGROUP_SIZE: u32 = 16;
+/// Biggest board kept in textures, past this boards are packed
+const MAX_TEXTURE_BOARD_SIZE: u32 = 8192;
+
+/// Workgroups needed along an axis to cover `cells` cells
+pub(crate) fn dispatch_size(cells: u32) -> u32 {
+    cells.div_ceil(COMPUTE_WORKGROUP_SIZE)
+}
+
+/// Name of a board format in WGSL
+pub(crate) fn board_format_name(format: wgpu::TextureFormat) -> &'static str {
+    match format {
+        wgpu::TextureFormat::R8Uint => "r8uint",
+        wgpu::TextureFormat::R32Uint => "r32uint",
+        _ => unreachable!("{:?} isn't a board format", format),
+    }
+}
+
+pub(crate) const GAME_OF_LIFE_VERTEX_SIZE: u64 = std::mem::size_of::<GameOfLifeVertex>() as u64;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct GameOfLifeVertex {
-    position: [f32; 2],
+pub(crate) struct GameOfLifeVertex {
+    pub(crate) position: [f32; 2],
     tex_coords: [f32; 2],
 }
 
 impl GameOfLifeVertex {
-    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+    /// Corners of the board, positioned by `fill`
+    pub(crate) const QUAD: [GameOfLifeVertex; 4] = [
+        GameOfLifeVertex {
+            position: [0.0, 0.0],
+            tex_coords: [1.0, 1.0]
+        },
+        GameOfLifeVertex {
+            position: [0.0, 0.0],
+            tex_coords: [1.0, 0.0]
+        },
+        GameOfLifeVertex {
+            position: [0.0, 0.0],
+            tex_coords: [0.0, 1.0]
+        },
+        GameOfLifeVertex {
+            position: [0.0, 0.0],
+            tex_coords: [0.0, 0.0]
+        }
+    ];
+
+    pub(crate) fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
         use std::mem;
         wgpu::VertexBufferLayout {
             array_stride: mem::size_of::<GameOfLifeVertex>() as wgpu::BufferAddress,
@@ -1,110 +1,465 @@
+// Boards hold 1 in red for alive cells and 0 for dead ones. GameOfLife::new swaps r32uint for the
+// board's format if it isn't R32Uint
+
 @group(0) @binding(0)
-var last_board: texture_2d<f32>;
+var last_board: texture_2d<u32>;
 
 @group(0) @binding(1)
-var new_board: texture_storage_2d<rgba16float, write>;
+var new_board: texture_storage_2d<r32uint, write>;
+
+// Bit n of birth/survival is set if a cell with n neighbours is born/survives
+struct Rule {
+    birth: u32,
+    survival: u32,
+}
+
+@group(0) @binding(2)
+var<uniform> rule: Rule;
 
 fn map(e: f32, real_low: f32, real_high: f32, map_low: f32, map_high: f32) -> f32 {
     return map_low+(e-real_low)/(real_high-real_low)*(map_high-map_low);
 }
 
-@compute @workgroup_size(256)
-fn compute_board(
-    @builtin(global_invocation_id) gid: vec3<u32>
-) {
-    // TODO: not this
-    let dim = vec2<u32>(textureDimensions(last_board)); // both boards are the same size (right???)
-    let one = 1u; // or this
+// Matches Edge::shader_value in topology.rs
+let EDGE_DEAD: u32 = 0u;
+let EDGE_WRAP: u32 = 1u;
+let EDGE_FLIP: u32 = 2u;
+let EDGE_MIRROR: u32 = 3u;
+let EDGE_ALIVE: u32 = 4u;
 
-    var count = 0u;
+// Edge past the left/right (x) and top/bottom (y) of the board
+struct Topology {
+    x: u32,
+    y: u32,
+}
 
-    let alive = textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y)), 0).r > 0.1;
+@group(0) @binding(3)
+var<uniform> topology: Topology;
 
-    if (dim.x > 1u && gid.x > 0u) {
-        if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x-one, gid.y)), 0).r > 0.1) {
-            count += 1u;
-        }
-        if (gid.x+1u < dim.x) {
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y)), 0).r > 0.1) {
-                count += 1u;
-            }
-            if (dim.y > 1u && gid.y > 0u) {
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x-one, gid.y-one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y-one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y-one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-            }
-            if (gid.y+1u < dim.y) {
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x-one, gid.y+one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y+one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y+one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-            }
-        } else {
-            if (dim.y > 1u && gid.y > 0u) {
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x-one, gid.y-one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y-one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-            }
-            if (gid.y+1u < dim.y) {
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x-one, gid.y+one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-                if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y+one)), 0).r > 0.1) {
-                    count += 1u;
-                }
-            }
-        }
-    } else if (gid.x+1u < dim.x) {
-        if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y)), 0).r > 0.1) {
-            count += 1u;
-        }
-        if (dim.y > 1u && gid.y > 0u) {
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y-one)), 0).r > 0.1) {
-                count += 1u;
-            }
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y-one)), 0).r > 0.1) {
-                count += 1u;
-            }
+// Matches Neighbour in topology.rs
+let NEIGHBOUR_CELL: i32 = 0;
+let NEIGHBOUR_DEAD: i32 = 1;
+let NEIGHBOUR_ALIVE: i32 = 2;
+
+// Same as Topology::resolve in topology.rs. The cell is in xy and what the neighbour is in z
+fn resolve_neighbour(cell: vec2<i32>, dim: vec2<i32>) -> vec3<i32> {
+    var pos = cell;
+    if (pos.x < 0 || pos.x >= dim.x) {
+        if (topology.x == EDGE_DEAD) {
+            return vec3<i32>(pos, NEIGHBOUR_DEAD);
+        } else if (topology.x == EDGE_ALIVE) {
+            return vec3<i32>(pos, NEIGHBOUR_ALIVE);
+        } else if (topology.x == EDGE_WRAP) {
+            pos.x = (pos.x+dim.x)%dim.x;
+        } else if (topology.x == EDGE_FLIP) {
+            pos.x = (pos.x+dim.x)%dim.x;
+            pos.y = dim.y - 1 - pos.y;
+        } else if (topology.x == EDGE_MIRROR) {
+            pos.x = clamp(pos.x, 0, dim.x - 1);
         }
-        if (gid.y+1u < dim.y) {
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y+one)), 0).r > 0.1) {
-                count += 1u;
-            }
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x+one, gid.y+one)), 0).r > 0.1) {
-                count += 1u;
-            }
+    }
+    if (pos.y < 0 || pos.y >= dim.y) {
+        if (topology.y == EDGE_DEAD) {
+            return vec3<i32>(pos, NEIGHBOUR_DEAD);
+        } else if (topology.y == EDGE_ALIVE) {
+            return vec3<i32>(pos, NEIGHBOUR_ALIVE);
+        } else if (topology.y == EDGE_WRAP) {
+            pos.y = (pos.y+dim.y)%dim.y;
+        } else if (topology.y == EDGE_FLIP) {
+            pos.y = (pos.y+dim.y)%dim.y;
+            pos.x = dim.x - 1 - pos.x;
+        } else if (topology.y == EDGE_MIRROR) {
+            pos.y = clamp(pos.y, 0, dim.y - 1);
         }
+    }
+    return vec3<i32>(pos, NEIGHBOUR_CELL);
+}
+
+fn neighbour_alive(cell: vec2<i32>, dim: vec2<i32>) -> bool {
+    let neighbour = resolve_neighbour(cell, dim);
+    if (neighbour.z == NEIGHBOUR_CELL) {
+        return textureLoad(last_board, neighbour.xy, 0).r == 1u;
+    }
+    return neighbour.z == NEIGHBOUR_ALIVE;
+}
+
+// Live cells of a generation, split in two words to count past 2^32, the cells born and died to get to it
+// and the hash of the board
+struct GenerationStats {
+    population_low: u32,
+    population_high: u32,
+    births: u32,
+    deaths: u32,
+    hash_low: u32,
+    hash_high: u32,
+}
+
+// Stats of the generations computed since the last submit. Keep in sync with stats.rs
+struct Stats {
+    // Index in generations of the generation being computed
+    index: u32,
+    // Low bits of the generation of the board before the generation being computed
+    generation: u32,
+    births: atomic<u32>,
+    deaths: atomic<u32>,
+    // What the births and deaths of the generation being computed add to the hash
+    hash_change_low: atomic<u32>,
+    hash_change_high: atomic<u32>,
+    // Live cells and hash of the board as it is before the generation being computed
+    population_low: u32,
+    population_high: u32,
+    hash_low: u32,
+    hash_high: u32,
+    generations: array<GenerationStats>,
+}
+
+@group(0) @binding(5)
+var<storage, read_write> stats: Stats;
+
+fn add_population(births: u32, deaths: u32) {
+    let low = stats.population_low;
+    var high = stats.population_high;
+    let added = low+births;
+    if (added < low) {
+        high = high+1u;
+    }
+    if (added < deaths) {
+        high = high - 1u;
+    }
+    stats.population_low = added - deaths;
+    stats.population_high = high;
+}
+
+fn mix_hash(value: u32) -> u32 {
+    var x = value;
+    x = x ^ (x >> 16u);
+    x = x*0x7feb352du;
+    x = x ^ (x >> 15u);
+    x = x*0x846ca68bu;
+    x = x ^ (x >> 16u);
+    return x;
+}
+
+// What a live cell adds to the hash of a board, which is the wrapping sum of these over its live cells.
+// Keep in sync with cell_hash in stats.rs
+fn cell_hash(cell: vec2<u32>) -> vec2<u32> {
+    let low = mix_hash(cell.x ^ mix_hash(cell.y));
+    return vec2<u32>(low, mix_hash(low ^ 0x9e3779b9u));
+}
+
+// Adds a cell born or, if `births` is 0, one that died to the population and hash
+fn add_toggle(cell: vec2<u32>, births: u32, deaths: u32) {
+    add_population(births, deaths);
+    let hash = cell_hash(cell);
+    if (births > 0u) {
+        stats.hash_low = stats.hash_low+hash.x;
+        stats.hash_high = stats.hash_high+hash.y;
     } else {
-        if (dim.y > 1u && gid.y > 0u) {
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y-one)), 0).r > 0.1) {
-                count += 1u;
+        stats.hash_low = stats.hash_low - hash.x;
+        stats.hash_high = stats.hash_high - hash.y;
+    }
+}
+
+// Cells born and died in a workgroup's cells, and what they add to the hash
+var<workgroup> tile_births: atomic<u32>;
+var<workgroup> tile_deaths: atomic<u32>;
+var<workgroup> tile_hash_low: atomic<u32>;
+var<workgroup> tile_hash_high: atomic<u32>;
+
+fn reset_tile_stats() {
+    atomicStore(&tile_births, 0u);
+    atomicStore(&tile_deaths, 0u);
+    atomicStore(&tile_hash_low, 0u);
+    atomicStore(&tile_hash_high, 0u);
+}
+
+fn count_change(cell: vec2<u32>, alive: u32, next: u32) {
+    if (next > alive) {
+        atomicAdd(&tile_births, 1u);
+        let hash = cell_hash(cell);
+        atomicAdd(&tile_hash_low, hash.x);
+        atomicAdd(&tile_hash_high, hash.y);
+    } else if (next < alive) {
+        atomicAdd(&tile_deaths, 1u);
+        let hash = cell_hash(cell);
+        atomicSub(&tile_hash_low, hash.x);
+        atomicSub(&tile_hash_high, hash.y);
+    }
+}
+
+// Adds the workgroup's births and deaths to the generation's, from one invocation once they're all counted
+fn add_tile_stats() {
+    let births = atomicLoad(&tile_births);
+    let deaths = atomicLoad(&tile_deaths);
+    if (births > 0u) {
+        atomicAdd(&stats.births, births);
+    }
+    if (deaths > 0u) {
+        atomicAdd(&stats.deaths, deaths);
+    }
+    if (births > 0u || deaths > 0u) {
+        atomicAdd(&stats.hash_change_low, atomicLoad(&tile_hash_low));
+        atomicAdd(&stats.hash_change_high, atomicLoad(&tile_hash_high));
+    }
+}
+
+// Run after every generation to record its stats and start counting the next one's
+@compute @workgroup_size(1)
+fn next_stats() {
+    let births = atomicLoad(&stats.births);
+    let deaths = atomicLoad(&stats.deaths);
+    atomicStore(&stats.births, 0u);
+    atomicStore(&stats.deaths, 0u);
+    add_population(births, deaths);
+    stats.hash_low = stats.hash_low+atomicLoad(&stats.hash_change_low);
+    stats.hash_high = stats.hash_high+atomicLoad(&stats.hash_change_high);
+    atomicStore(&stats.hash_change_low, 0u);
+    atomicStore(&stats.hash_change_high, 0u);
+    if (stats.index < arrayLength(&stats.generations)) {
+        stats.generations[stats.index] = GenerationStats(stats.population_low, stats.population_high, births, deaths, stats.hash_low, stats.hash_high);
+    }
+    stats.index = stats.index+1u;
+    stats.generation = stats.generation+1u;
+}
+
+// Low bits of the generation each cell of a texture board last changed at, as bits of an i32 so the
+// format isn't swapped for the board's
+@group(0) @binding(6)
+var changed_at: texture_storage_2d<r32sint, write>;
+
+// Records that the cell changed to get to the generation being computed
+fn store_changed_at(cell: vec2<i32>, alive: u32, next: u32) {
+    if (next != alive) {
+        textureStore(changed_at, cell, vec4<i32>(bitcast<i32>(stats.generation+1u), 0, 0, 0));
+    }
+}
+
+// Matches `Heat` in heatmap.rs: how many times a cell changed, decayed up to the generation it last changed at
+struct Heat {
+    heat: f32,
+    updated: u32,
+}
+
+// Matches HeatmapUniformData in heatmap.rs
+struct Heatmap {
+    decay: f32,
+    enabled: u32,
+    generation: u32,
+    width: u32,
+}
+
+@group(2) @binding(0)
+var<storage, read_write> heat: array<Heat>;
+
+@group(2) @binding(1)
+var<uniform> heatmap: Heatmap;
+
+// Adds a change to the heat of the cell if it changed to get to the generation being computed
+fn add_heat(cell: vec2<i32>, alive: u32, next: u32) {
+    if (heatmap.enabled == 0u || next == alive) {
+        return;
+    }
+    let i = u32(cell.y)*heatmap.width+u32(cell.x);
+    let generation = stats.generation+1u;
+    heat[i].heat = heat[i].heat*pow(heatmap.decay, f32(generation-heat[i].updated))+1.0;
+    heat[i].updated = generation;
+}
+
+// Keep in sync with COMPUTE_WORKGROUP_SIZE in game.rs
+let WORKGROUP_SIZE: i32 = 16;
+// A workgroup's cells plus a one cell halo around them
+let TILE_SIZE: i32 = 18;
+
+// Cells of the workgroup's tile, 1 if alive. Row major, TILE_SIZE*TILE_SIZE
+var<workgroup> tile: array<u32, 324>;
+
+// Loads the tile of cells from `origin` into `tile`, every invocation taking a turn at every
+// WORKGROUP_SIZE*WORKGROUP_SIZE'th cell
+fn load_tile(origin: vec2<i32>, index: u32, dim: vec2<i32>) {
+    for (var i = i32(index); i < TILE_SIZE*TILE_SIZE; i = i+WORKGROUP_SIZE*WORKGROUP_SIZE) {
+        let pos = origin+vec2<i32>(i%TILE_SIZE, i/TILE_SIZE);
+        // Workgroups hanging over the edge of the board only need one cell past it
+        tile[i] = select(0u, 1u, pos.x <= dim.x && pos.y <= dim.y && neighbour_alive(pos, dim));
+    }
+}
+
+// The next generation of the cell at `center` in the tile, 1 if alive
+fn next_cell(center: vec2<i32>) -> u32 {
+    let alive = tile[center.y*TILE_SIZE+center.x] == 1u;
+
+    var count = 0u;
+    for (var dy = -1; dy <= 1; dy = dy+1) {
+        for (var dx = -1; dx <= 1; dx = dx+1) {
+            if (dx != 0 || dy != 0) {
+                count += tile[(center.y+dy)*TILE_SIZE+center.x+dx];
             }
         }
-        if (gid.y+1u < dim.y) {
-            if (textureLoad(last_board, vec2<i32>(vec2<u32>(gid.x, gid.y+one)), 0).r > 0.1) {
-                count += 1u;
+    }
+
+    return (select(rule.birth, rule.survival, alive) >> count) & 1u;
+}
+
+@compute @workgroup_size(16, 16)
+fn compute_board(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_id) lid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let dim = vec2<i32>(textureDimensions(last_board)); // both boards are the same size (right???)
+
+    if (index == 0u) {
+        reset_tile_stats();
+    }
+    load_tile(vec2<i32>(wid.xy)*WORKGROUP_SIZE - vec2<i32>(1, 1), index, dim);
+    workgroupBarrier();
+
+    if (i32(gid.x) < dim.x && i32(gid.y) < dim.y) {
+        let center = vec2<i32>(lid.xy)+vec2<i32>(1, 1);
+        let next = next_cell(center);
+        textureStore(new_board, vec2<i32>(gid.xy), vec4<u32>(next, 0u, 0u, 0u));
+        count_change(gid.xy, tile[center.y*TILE_SIZE+center.x], next);
+        store_changed_at(vec2<i32>(gid.xy), tile[center.y*TILE_SIZE+center.x], next);
+        add_heat(vec2<i32>(gid.xy), tile[center.y*TILE_SIZE+center.x], next);
+    }
+    workgroupBarrier();
+
+    if (index == 0u) {
+        add_tile_stats();
+    }
+}
+
+// Boards are split into tiles of WORKGROUP_SIZE by WORKGROUP_SIZE cells, numbered row major. Only
+// tiles that changed in the last generation or are next to one that did can change in the next,
+// so gather_tiles lists those and compute_active is dispatched indirectly over the list
+
+// Workgroups along x when dispatching compute_active, keeping big boards under the limit on workgroups per dimension
+let ACTIVE_TILES_PER_ROW: u32 = 256u;
+
+fn tiles_dim(dim: vec2<i32>) -> vec2<i32> {
+    return (dim+vec2<i32>(WORKGROUP_SIZE - 1, WORKGROUP_SIZE - 1))/WORKGROUP_SIZE;
+}
+
+struct ActiveTiles {
+    count: u32,
+    tiles: array<u32>,
+}
+
+struct GatherTiles {
+    count: atomic<u32>,
+    tiles: array<u32>,
+}
+
+// Read by dispatch_workgroups_indirect. z is always 1
+struct DispatchArgs {
+    x: atomic<u32>,
+    y: atomic<u32>,
+    z: u32,
+}
+
+// 1 for the tiles that changed in the last generation
+@group(1) @binding(0)
+var<storage, read> last_changed: array<u32>;
+
+// 1 for the tiles that change in the generation being computed, cleared by gather_tiles
+@group(1) @binding(1)
+var<storage, read_write> next_changed: array<u32>;
+
+@group(1) @binding(2)
+var<storage, read_write> gather_list: GatherTiles;
+
+@group(1) @binding(3)
+var<storage, read_write> dispatch_args: DispatchArgs;
+
+@compute @workgroup_size(1)
+fn reset_tiles() {
+    atomicStore(&gather_list.count, 0u);
+    atomicStore(&dispatch_args.x, 0u);
+    atomicStore(&dispatch_args.y, 0u);
+}
+
+@compute @workgroup_size(16, 16)
+fn gather_tiles(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+) {
+    let tiles = tiles_dim(vec2<i32>(textureDimensions(last_board)));
+    let pos = vec2<i32>(gid.xy);
+    if (pos.x >= tiles.x || pos.y >= tiles.y) {
+        return;
+    }
+    let index = u32(pos.y*tiles.x+pos.x);
+    next_changed[index] = 0u;
+
+    // Cells past wrapping and flipping edges come from the other side of the board, so the tiles along them are always active
+    let wraps = vec2<bool>(topology.x == EDGE_WRAP || topology.x == EDGE_FLIP, topology.y == EDGE_WRAP || topology.y == EDGE_FLIP);
+    var is_active = (wraps.x && (pos.x == 0 || pos.x == tiles.x - 1)) || (wraps.y && (pos.y == 0 || pos.y == tiles.y - 1));
+    for (var dy = -1; dy <= 1; dy = dy+1) {
+        for (var dx = -1; dx <= 1; dx = dx+1) {
+            let neighbour = pos+vec2<i32>(dx, dy);
+            if (neighbour.x >= 0 && neighbour.y >= 0 && neighbour.x < tiles.x && neighbour.y < tiles.y && last_changed[neighbour.y*tiles.x+neighbour.x] == 1u) {
+                is_active = true;
             }
         }
     }
 
-    if ((alive && (count == 2u || count == 3u)) || (!alive && count == 3u)) {
-        textureStore(new_board, vec2<i32>(gid.xy), vec4<f32>(1.0, 1.0, 1.0, 1.0));
-    } else {
-        textureStore(new_board, vec2<i32>(gid.xy), vec4<f32>(0.0, map(f32(gid.x)/f32(dim.x), 0.0, 1.0, 0.1, 1.0), map(f32(gid.y)/f32(dim.y), 0.0, 1.0, 0.1, 1.0), 1.0));
+    if (is_active) {
+        let i = atomicAdd(&gather_list.count, 1u);
+        gather_list.tiles[i] = index;
+        atomicMax(&dispatch_args.x, min(i+1u, ACTIVE_TILES_PER_ROW));
+        atomicMax(&dispatch_args.y, i/ACTIVE_TILES_PER_ROW+1u);
+    }
+}
+
+@group(1) @binding(0)
+var<storage, read> active_list: ActiveTiles;
+
+@group(1) @binding(1)
+var<storage, read_write> changed_tiles: array<atomic<u32>>;
+
+var<workgroup> tile_changed: atomic<u32>;
+
+// Same as compute_board, for the tile at index wid.y*ACTIVE_TILES_PER_ROW+wid.x of active_list
+@compute @workgroup_size(16, 16)
+fn compute_active(
+    @builtin(local_invocation_id) lid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let list_index = wid.y*ACTIVE_TILES_PER_ROW+wid.x;
+    if (list_index >= active_list.count) {
+        return;
+    }
+    let dim = vec2<i32>(textureDimensions(last_board));
+    let tiles_x = u32(tiles_dim(dim).x);
+    let tile_index = active_list.tiles[list_index];
+    let tile_pos = vec2<i32>(vec2<u32>(tile_index%tiles_x, tile_index/tiles_x));
+
+    if (index == 0u) {
+        atomicStore(&tile_changed, 0u);
+        reset_tile_stats();
+    }
+    load_tile(tile_pos*WORKGROUP_SIZE - vec2<i32>(1, 1), index, dim);
+    workgroupBarrier();
+
+    let cell = tile_pos*WORKGROUP_SIZE+vec2<i32>(lid.xy);
+    let center = vec2<i32>(lid.xy)+vec2<i32>(1, 1);
+    if (cell.x < dim.x && cell.y < dim.y) {
+        let next = next_cell(center);
+        textureStore(new_board, cell, vec4<u32>(next, 0u, 0u, 0u));
+        if (next != tile[center.y*TILE_SIZE+center.x]) {
+            atomicStore(&tile_changed, 1u);
+        }
+        count_change(vec2<u32>(cell), tile[center.y*TILE_SIZE+center.x], next);
+        store_changed_at(cell, tile[center.y*TILE_SIZE+center.x], next);
+        add_heat(cell, tile[center.y*TILE_SIZE+center.x], next);
+    }
+    workgroupBarrier();
+
+    if (index == 0u) {
+        if (atomicLoad(&tile_changed) == 1u) {
+            atomicStore(&changed_tiles[tile_index], 1u);
+        }
+        add_tile_stats();
     }
 }
 
@@ -128,32 +483,576 @@ fn render_vert(
     return out;
 }
 
+// White for alive cells, a gradient across the board for dead ones
+fn cell_colour(alive: bool, cell: vec2<u32>, dim: vec2<u32>) -> vec4<f32> {
+    if (alive) {
+        return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    }
+    return vec4<f32>(0.0, map(f32(cell.x)/f32(dim.x), 0.0, 1.0, 0.1, 1.0), map(f32(cell.y)/f32(dim.y), 0.0, 1.0, 0.1, 1.0), 1.0);
+}
+
+// Matches AgePaletteUniformData in palette.rs
+struct AgePalette {
+    young: vec4<f32>,
+    old: vec4<f32>,
+    dead: vec4<f32>,
+    background: vec4<f32>,
+    alive_span: f32,
+    dead_span: f32,
+    generation: u32,
+    enabled: u32,
+}
+
 @group(0) @binding(0)
-var render_board: texture_2d<f32>;
+var render_board: texture_2d<u32>;
 
 @group(0) @binding(1)
-var render_sampler: sampler;
+var render_changed_at: texture_2d<i32>;
+
+@group(0) @binding(2)
+var<uniform> palette: AgePalette;
+
+// Same as AgePalette::colour in palette.rs
+fn age_colour(alive: bool, age: u32) -> vec4<f32> {
+    if (alive) {
+        return mix(palette.young, palette.old, min(f32(age)/palette.alive_span, 1.0));
+    }
+    return mix(palette.dead, palette.background, min(f32(age)/palette.dead_span, 1.0));
+}
+
+@group(1) @binding(0)
+var<storage, read> render_heat: array<Heat>;
+
+@group(1) @binding(1)
+var<uniform> render_heatmap: Heatmap;
+
+// Keep in sync with HALF_HOT in heatmap.rs
+let HALF_HOT: f32 = 8.0;
+
+// Same as heat_colour in heatmap.rs
+fn heat_colour(heat: f32) -> vec4<f32> {
+    let scaled = heat/(heat+HALF_HOT)*3.0;
+    if (scaled < 1.0) {
+        return mix(vec4<f32>(1.0, 1.0, 1.0, 1.0), vec4<f32>(1.0, 0.85, 0.2, 1.0), scaled);
+    } else if (scaled < 2.0) {
+        return mix(vec4<f32>(1.0, 0.85, 0.2, 1.0), vec4<f32>(0.85, 0.1, 0.05, 1.0), scaled - 1.0);
+    }
+    return mix(vec4<f32>(0.85, 0.1, 0.05, 1.0), vec4<f32>(0.1, 0.0, 0.0, 1.0), min(scaled - 2.0, 1.0));
+}
+
+// Matches TrailsUniformData in trails.rs
+struct Trails {
+    length: u32,
+    head: u32,
+    count: u32,
+}
+
+@group(2) @binding(0)
+var render_trail: texture_2d_array<u32>;
+
+@group(2) @binding(1)
+var<uniform> trails: Trails;
+
+// Same as opacity in trails.rs
+fn trail_opacity(age: u32) -> f32 {
+    return 1.0 - f32(age)/f32(trails.length+1u);
+}
+
+// Lays the cell's past generations over `colour`, oldest first, with live ones drawn in `alive_colour`
+fn add_trail(colour: vec4<f32>, alive_colour: vec4<f32>, cell: vec2<u32>) -> vec4<f32> {
+    var out = colour;
+    for (var age = trails.count; age > 0u; age = age - 1u) {
+        let layer = (trails.head+trails.length-age)%trails.length;
+        if (textureLoad(render_trail, vec2<i32>(cell), i32(layer), 0).r == 1u) {
+            out = mix(out, alive_colour, trail_opacity(age));
+        }
+    }
+    return out;
+}
 
 @fragment
 fn render_frag(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(render_board, render_sampler, in.tex_coords);
-    // return vec4<f32>(0.0);
+    let dim = vec2<u32>(textureDimensions(render_board));
+    let cell = min(vec2<u32>(in.tex_coords*vec2<f32>(dim)), dim - vec2<u32>(1u, 1u));
+    let alive = textureLoad(render_board, vec2<i32>(cell), 0).r == 1u;
+    if (render_heatmap.enabled != 0u) {
+        let cell_heat = render_heat[cell.y*render_heatmap.width+cell.x];
+        let colour = heat_colour(cell_heat.heat*pow(render_heatmap.decay, f32(render_heatmap.generation-cell_heat.updated)));
+        // Live cells are tinted blue so they still show up over the heat
+        return select(colour, mix(colour, vec4<f32>(0.0, 0.3, 1.0, 1.0), 0.6), alive);
+    }
+    if (palette.enabled == 0u) {
+        if (alive) {
+            return cell_colour(alive, cell, dim);
+        }
+        return add_trail(cell_colour(alive, cell, dim), cell_colour(true, cell, dim), cell);
+    }
+    let changed = bitcast<u32>(textureLoad(render_changed_at, vec2<i32>(cell), 0).r);
+    if (alive) {
+        return age_colour(alive, palette.generation - changed);
+    }
+    return add_trail(age_colour(alive, palette.generation - changed), palette.young, cell);
 }
 
 @group(0) @binding(0)
 var<uniform> cell: vec2<i32>;
 
 @group(0) @binding(1)
-var toggle_read_board: texture_2d<f32>;
+var toggle_read_board: texture_2d<u32>;
 
 @group(0) @binding(2)
-var toggle_write_board: texture_storage_2d<rgba16float, write>;
+var toggle_write_board: texture_storage_2d<r32uint, write>;
 
 @compute @workgroup_size(1)
 fn toggle() {
-    if (textureLoad(toggle_read_board, cell, 0).r > 0.1) {
-        textureStore(toggle_write_board, cell, vec4<f32>(0.0, 0.0, 0.0, 1.0));
-    } else {
-        textureStore(toggle_write_board, cell, vec4<f32>(1.0, 1.0, 1.0, 1.0));
+    let alive = textureLoad(toggle_read_board, cell, 0).r;
+    textureStore(toggle_write_board, cell, vec4<u32>(1u - alive, 0u, 0u, 0u));
+    textureStore(changed_at, cell, vec4<i32>(bitcast<i32>(stats.generation), 0, 0, 0));
+    add_toggle(vec2<u32>(cell), 1u - alive, alive);
+}
+
+// Matches SoupUniformData in soup.rs
+struct Soup {
+    seed_low: u32,
+    seed_high: u32,
+    // Cells are alive where the top 24 bits of their hash are below this
+    threshold: u32,
+    // Bit i is set for each transform i of soup_transform the soup's symmetry is made of
+    transforms: u32,
+    // Corners of the cells filled, the second one past them
+    min: vec2<u32>,
+    max: vec2<u32>,
+}
+
+@group(0) @binding(0)
+var<uniform> soup: Soup;
+
+@group(0) @binding(1)
+var edit_read_board: texture_2d<u32>;
+
+@group(0) @binding(2)
+var edit_write_board: texture_storage_2d<r32uint, write>;
+
+// Writes an edited cell to the write board, counting it like a generation's change if it changed
+fn store_edit(cell: vec2<u32>, alive: u32, next: u32) {
+    textureStore(edit_write_board, vec2<i32>(cell), vec4<u32>(next, 0u, 0u, 0u));
+    count_change(cell, alive, next);
+    if (next != alive) {
+        textureStore(changed_at, vec2<i32>(cell), vec4<i32>(bitcast<i32>(stats.generation), 0, 0, 0));
+    }
+}
+
+// Keep in sync with soup_hash in soup.rs
+fn soup_hash(cell: vec2<u32>) -> u32 {
+    return mix_hash(cell.x ^ mix_hash(cell.y ^ mix_hash(soup.seed_low ^ mix_hash(soup.seed_high))));
+}
+
+// Same as transform in soup.rs
+fn soup_transform(i: u32, cell: vec2<u32>, size: vec2<u32>) -> vec2<u32> {
+    let far = size - vec2<u32>(1u, 1u);
+    if (i == 1u) {
+        return vec2<u32>(far.x - cell.y, cell.x);
+    } else if (i == 2u) {
+        return far - cell;
+    } else if (i == 3u) {
+        return vec2<u32>(cell.y, far.y - cell.x);
+    } else if (i == 4u) {
+        return vec2<u32>(far.x - cell.x, cell.y);
+    } else if (i == 5u) {
+        return vec2<u32>(cell.x, far.y - cell.y);
+    } else if (i == 6u) {
+        return cell.yx;
+    } else if (i == 7u) {
+        return far - cell.yx;
     }
-}
\ No newline at end of file
+    return cell;
+}
+
+// Whether the cell is alive in the soup. Every cell the symmetry maps onto each other takes the hash of
+// the first of them, row by row
+fn soup_alive(cell: vec2<u32>) -> bool {
+    let size = soup.max - soup.min;
+    let local = cell - soup.min;
+    var first = local;
+    for (var i = 1u; i < 8u; i = i+1u) {
+        if (((soup.transforms >> i) & 1u) == 1u) {
+            let other = soup_transform(i, local, size);
+            if (other.y < first.y || (other.y == first.y && other.x < first.x)) {
+                first = other;
+            }
+        }
+    }
+    return (soup_hash(soup.min+first) >> 8u) < soup.threshold;
+}
+
+// Writes the read board to the write board with the cells in the soup's area replaced by the soup's,
+// counting the cells that changed like a generation's
+@compute @workgroup_size(16, 16)
+fn fill_soup(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+) {
+    let dim = vec2<u32>(textureDimensions(edit_read_board));
+    if (index == 0u) {
+        reset_tile_stats();
+    }
+    workgroupBarrier();
+
+    if (gid.x < dim.x && gid.y < dim.y) {
+        let cell = vec2<i32>(gid.xy);
+        let alive = textureLoad(edit_read_board, cell, 0).r;
+        var next = alive;
+        if (all(gid.xy >= soup.min) && all(gid.xy < soup.max)) {
+            next = select(0u, 1u, soup_alive(gid.xy));
+        }
+        store_edit(gid.xy, alive, next);
+    }
+    workgroupBarrier();
+
+    if (index == 0u) {
+        add_tile_stats();
+    }
+}
+
+let EDIT_CLEAR: u32 = 0u;
+let EDIT_INVERT: u32 = 1u;
+let EDIT_SHIFT: u32 = 2u;
+let EDIT_ROTATE: u32 = 3u;
+let EDIT_FLIP_HORIZONTAL: u32 = 4u;
+let EDIT_FLIP_VERTICAL: u32 = 5u;
+
+// Matches EditUniformData in edit.rs
+struct Edit {
+    // One of the EDIT_ constants
+    kind: u32,
+    // Clockwise quarter turns of EDIT_ROTATE
+    quarter_turns: u32,
+    // Offset of EDIT_SHIFT, wrapped onto the board
+    shift: vec2<i32>,
+}
+
+@group(0) @binding(0)
+var<uniform> edit: Edit;
+
+// Same as Edit::source in edit.rs, returning the state of the edited cell
+fn edited_cell(cell: vec2<u32>, alive: u32, dim: vec2<u32>) -> u32 {
+    if (edit.kind == EDIT_CLEAR) {
+        return 0u;
+    } else if (edit.kind == EDIT_INVERT) {
+        return 1u - alive;
+    }
+    let size = vec2<i32>(dim);
+    var source = vec2<i32>(cell);
+    if (edit.kind == EDIT_SHIFT) {
+        source = (source - edit.shift + size) % size;
+    } else if (edit.kind == EDIT_ROTATE) {
+        // Doubled so the middle of the board is a whole number
+        var doubled = source*2 - (size - vec2<i32>(1, 1));
+        for (var i = 0u; i < edit.quarter_turns; i = i+1u) {
+            doubled = vec2<i32>(-doubled.y, doubled.x);
+        }
+        source = (doubled + size - vec2<i32>(1, 1)) >> vec2<u32>(1u, 1u);
+    } else if (edit.kind == EDIT_FLIP_HORIZONTAL) {
+        source.x = size.x - 1 - source.x;
+    } else if (edit.kind == EDIT_FLIP_VERTICAL) {
+        source.y = size.y - 1 - source.y;
+    }
+    if (any(source < vec2<i32>(0, 0)) || any(source >= size)) {
+        return 0u;
+    }
+    return textureLoad(edit_read_board, source, 0).r;
+}
+
+// Writes the read board to the write board with the edit made to every cell, counting the cells that
+// changed like a generation's
+@compute @workgroup_size(16, 16)
+fn edit_board(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+) {
+    let dim = vec2<u32>(textureDimensions(edit_read_board));
+    if (index == 0u) {
+        reset_tile_stats();
+    }
+    workgroupBarrier();
+
+    if (gid.x < dim.x && gid.y < dim.y) {
+        let alive = textureLoad(edit_read_board, vec2<i32>(gid.xy), 0).r;
+        store_edit(gid.xy, alive, edited_cell(gid.xy, alive, dim));
+    }
+    workgroupBarrier();
+
+    if (index == 0u) {
+        add_tile_stats();
+    }
+}
+
+// Adds the cells an edit of the board counted as born and died to the population and hash, without
+// computing a generation
+@compute @workgroup_size(1)
+fn add_edit_stats() {
+    add_population(atomicLoad(&stats.births), atomicLoad(&stats.deaths));
+    atomicStore(&stats.births, 0u);
+    atomicStore(&stats.deaths, 0u);
+    stats.hash_low = stats.hash_low+atomicLoad(&stats.hash_change_low);
+    stats.hash_high = stats.hash_high+atomicLoad(&stats.hash_change_high);
+    atomicStore(&stats.hash_change_low, 0u);
+    atomicStore(&stats.hash_change_high, 0u);
+}
+// Packed boards hold 32 cells per u32: bit i of word w in row y is the cell at (w*32+i, y). Cells
+// past the right edge in the last word of a row are always 0
+
+fn packed_words_per_row(width: u32) -> u32 {
+    return (width+31u)/32u;
+}
+
+@group(0) @binding(0)
+var<storage, read> last_words: array<u32>;
+
+@group(0) @binding(1)
+var<storage, read_write> new_words: array<u32>;
+
+// Width and height of the packed board, in cells
+@group(0) @binding(4)
+var<uniform> packed_size: vec2<u32>;
+
+// 1 if the cell at `cell` is alive, 0 if it's dead, resolving cells past the edge
+fn packed_neighbour(cell: vec2<i32>) -> u32 {
+    let neighbour = resolve_neighbour(cell, vec2<i32>(packed_size));
+    if (neighbour.z == NEIGHBOUR_CELL) {
+        let pos = vec2<u32>(neighbour.xy);
+        return (last_words[pos.y*packed_words_per_row(packed_size.x)+pos.x/32u] >> (pos.x%32u)) & 1u;
+    }
+    return select(0u, 1u, neighbour.z == NEIGHBOUR_ALIVE);
+}
+
+// Cells x to x+31 of row y, including the one just past the right edge if it's among them
+fn packed_row(x: i32, y: i32) -> u32 {
+    let dim = vec2<i32>(packed_size);
+    let past_edge = dim.x - x;
+    if (y >= 0 && y < dim.y) {
+        var row = last_words[u32(y)*packed_words_per_row(packed_size.x)+u32(x)/32u];
+        if (past_edge < 32) {
+            row = row | (packed_neighbour(vec2<i32>(dim.x, y)) << u32(past_edge));
+        }
+        return row;
+    }
+    // Above or below the board, where any cell can come from anywhere
+    var row = 0u;
+    for (var i = 0; i < 32 && i <= past_edge; i = i+1) {
+        row = row | (packed_neighbour(vec2<i32>(x+i, y)) << u32(i));
+    }
+    return row;
+}
+
+// The next generation of cells x to x+31 of row y
+fn next_packed_word(x: i32, y: i32) -> u32 {
+    let dim = vec2<i32>(packed_size);
+
+    // The 8 neighbours of every cell in the word, shifted into place
+    var neighbours: array<u32, 8>;
+    var alive = 0u;
+    var n = 0;
+    for (var dy = -1; dy <= 1; dy = dy+1) {
+        let row = packed_row(x, y+dy);
+        let west = (row << 1u) | packed_neighbour(vec2<i32>(x - 1, y+dy));
+        var east = row >> 1u;
+        if (x+32 <= dim.x) {
+            east = east | (packed_neighbour(vec2<i32>(x+32, y+dy)) << 31u);
+        }
+        neighbours[n] = west;
+        neighbours[n+1] = east;
+        n = n+2;
+        if (dy == 0) {
+            alive = row;
+        } else {
+            neighbours[n] = row;
+            n = n+1;
+        }
+    }
+
+    // Count neighbours 32 cells at a time, with bit i of count_n being bit n of cell i's count
+    var count_1 = 0u;
+    var count_2 = 0u;
+    var count_4 = 0u;
+    var count_8 = 0u;
+    for (var i = 0; i < 8; i = i+1) {
+        let carry_1 = count_1 & neighbours[i];
+        count_1 = count_1 ^ neighbours[i];
+        let carry_2 = count_2 & carry_1;
+        count_2 = count_2 ^ carry_1;
+        let carry_4 = count_4 & carry_2;
+        count_4 = count_4 ^ carry_2;
+        count_8 = count_8 | carry_4;
+    }
+
+    var next = 0u;
+    for (var count = 0u; count <= 8u; count = count+1u) {
+        let has_count = select(~count_1, count_1, (count & 1u) != 0u) & select(~count_2, count_2, (count & 2u) != 0u) & select(~count_4, count_4, (count & 4u) != 0u) & select(~count_8, count_8, (count & 8u) != 0u);
+        let born = select(0u, ~alive, ((rule.birth >> count) & 1u) == 1u);
+        let survives = select(0u, alive, ((rule.survival >> count) & 1u) == 1u);
+        next = next | (has_count & (born | survives));
+    }
+
+    let past_edge = dim.x - x;
+    if (past_edge < 32) {
+        next = next & ((1u << u32(past_edge)) - 1u);
+    }
+    return next;
+}
+
+// Keep in sync with COMPUTE_WORKGROUP_SIZE in game.rs
+@compute @workgroup_size(16, 16)
+fn compute_packed(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+) {
+    if (index == 0u) {
+        reset_tile_stats();
+    }
+    workgroupBarrier();
+
+    let words_per_row = packed_words_per_row(packed_size.x);
+    if (gid.x < words_per_row && gid.y < packed_size.y) {
+        let word = gid.y*words_per_row+gid.x;
+        let alive = last_words[word];
+        let next = next_packed_word(i32(gid.x)*32, i32(gid.y));
+        new_words[word] = next;
+        if (next != alive) {
+            for (var i = 0u; i < 32u; i = i+1u) {
+                count_change(vec2<u32>(gid.x*32u+i, gid.y), (alive >> i) & 1u, (next >> i) & 1u);
+            }
+        }
+    }
+    workgroupBarrier();
+
+    if (index == 0u) {
+        add_tile_stats();
+    }
+}
+
+@group(0) @binding(0)
+var<storage, read> render_words: array<u32>;
+
+@group(0) @binding(1)
+var<uniform> render_size: vec2<u32>;
+
+@fragment
+fn render_packed(in: VertexOutput) -> @location(0) vec4<f32> {
+    let cell = min(vec2<u32>(in.tex_coords*vec2<f32>(render_size)), render_size - vec2<u32>(1u, 1u));
+    let word = render_words[cell.y*packed_words_per_row(render_size.x)+cell.x/32u];
+    return cell_colour(((word >> (cell.x%32u)) & 1u) == 1u, cell, render_size);
+}
+
+@group(0) @binding(1)
+var<storage, read_write> toggle_words: array<u32>;
+
+@group(0) @binding(2)
+var<uniform> toggle_size: vec2<u32>;
+
+@compute @workgroup_size(1)
+fn toggle_packed() {
+    let pos = vec2<u32>(cell);
+    let word = pos.y*packed_words_per_row(toggle_size.x)+pos.x/32u;
+    let alive = (toggle_words[word] >> (pos.x%32u)) & 1u;
+    toggle_words[word] = toggle_words[word] ^ (1u << (pos.x%32u));
+    add_toggle(pos, 1u - alive, alive);
+}
+// Chunked boards keep each 256x256 chunk of an unbounded plane in a layer of a texture array.
+// CHUNK_NONE is the layer of chunks that aren't allocated, which are all dead
+
+// Keep in sync with CHUNK_SIZE and CHUNK_NONE in chunked_board.rs
+let CHUNK_SIZE: i32 = 256;
+let CHUNK_NONE: u32 = 0xffffffffu;
+
+@group(0) @binding(0)
+var last_chunks: texture_2d_array<u32>;
+
+@group(0) @binding(1)
+var new_chunks: texture_storage_2d_array<r32uint, write>;
+
+// Layers of each chunk being computed and of the 3x3 chunks around it, 9 per chunk in row major
+// order from the low x low y neighbour. The chunk's own layer is the middle one
+@group(0) @binding(3)
+var<storage, read> chunk_layers: array<u32>;
+
+// Set to 1 for the layer of every chunk that has live cells in it
+@group(0) @binding(4)
+var<storage, read_write> chunk_alive: array<atomic<u32>>;
+
+// 1 if the cell at `cell`, relative to the chunk's low corner, is alive. Cells up to one chunk
+// past the chunk come from its neighbours
+fn chunk_cell(chunk: u32, cell: vec2<i32>) -> u32 {
+    let side = select(vec2<i32>(0, 0), vec2<i32>(1, 1), cell >= vec2<i32>(CHUNK_SIZE, CHUNK_SIZE)) - select(vec2<i32>(0, 0), vec2<i32>(1, 1), cell < vec2<i32>(0, 0));
+    let layer = chunk_layers[chunk*9u+u32((side.y+1)*3+side.x+1)];
+    if (layer == CHUNK_NONE) {
+        return 0u;
+    }
+    return textureLoad(last_chunks, cell - side*CHUNK_SIZE, i32(layer), 0).r;
+}
+
+// Same as compute_board, for the chunk at index wid.z of chunk_layers
+@compute @workgroup_size(16, 16)
+fn compute_chunk(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_id) lid: vec3<u32>,
+    @builtin(local_invocation_index) index: u32,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let chunk = wid.z;
+    let origin = vec2<i32>(wid.xy)*WORKGROUP_SIZE - vec2<i32>(1, 1);
+    for (var i = i32(index); i < TILE_SIZE*TILE_SIZE; i = i+WORKGROUP_SIZE*WORKGROUP_SIZE) {
+        tile[i] = chunk_cell(chunk, origin+vec2<i32>(i%TILE_SIZE, i/TILE_SIZE));
+    }
+    workgroupBarrier();
+
+    let layer = chunk_layers[chunk*9u+4u];
+    let next = next_cell(vec2<i32>(lid.xy)+vec2<i32>(1, 1));
+    textureStore(new_chunks, vec2<i32>(gid.xy), i32(layer), vec4<u32>(next, 0u, 0u, 0u));
+    if (next == 1u) {
+        atomicStore(&chunk_alive[layer], 1u);
+    }
+}
+
+// A rectangle of the screen showing the cells from cell_origin to cell_origin+cell_extent of a
+// chunk. Chunk cells are taken modulo CHUNK_SIZE, so unallocated space can be drawn in one go
+struct ChunkInstance {
+    @location(0) position: vec2<f32>,
+    @location(1) size: vec2<f32>,
+    @location(2) cell_origin: vec2<f32>,
+    @location(3) cell_extent: vec2<f32>,
+    @location(4) layer: u32,
+}
+
+struct ChunkOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) cell: vec2<f32>,
+    @location(1) @interpolate(flat) layer: u32,
+}
+
+@vertex
+fn render_chunk_vert(
+    @builtin(vertex_index) vertex: u32,
+    instance: ChunkInstance,
+) -> ChunkOutput {
+    // Same corners in the same order as GameOfLifeVertex::QUAD
+    let corner = vec2<f32>(f32(1u - vertex/2u), f32(1u - vertex%2u));
+    var out: ChunkOutput;
+    out.position = vec4<f32>(instance.position+corner*instance.size, 0.0, 1.0);
+    out.cell = instance.cell_origin+corner*instance.cell_extent;
+    out.layer = instance.layer;
+    return out;
+}
+
+@group(0) @binding(0)
+var render_chunks: texture_2d_array<u32>;
+
+@fragment
+fn render_chunk(in: ChunkOutput) -> @location(0) vec4<f32> {
+    let cell = vec2<u32>(vec2<i32>(floor(in.cell)) & vec2<i32>(CHUNK_SIZE - 1, CHUNK_SIZE - 1));
+    var alive = false;
+    if (in.layer != CHUNK_NONE) {
+        alive = textureLoad(render_chunks, vec2<i32>(cell), i32(in.layer), 0).r == 1u;
+    }
+    return cell_colour(alive, cell, vec2<u32>(u32(CHUNK_SIZE), u32(CHUNK_SIZE)));
+}
@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+
+use bui::rect::{SizeAndCenter, Points};
+use bui_basic::containers::Fill;
+
+use crate::{overlay::OverlayVertex, stats::GenerationStats};
+
+pub const POPULATION_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+pub const BIRTHS_COLOR: [f32; 4] = [0.1, 0.6, 0.2, 1.0];
+pub const DEATHS_COLOR: [f32; 4] = [0.85, 0.15, 0.1, 1.0];
+
+/// How values are spread up the graph
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphScale {
+    Linear,
+    /// Logarithmic, so small numbers stay readable next to big ones
+    Log,
+}
+
+/// Line chart of the population, births and deaths over the last generations, drawn by the overlay.
+/// The latest generation is at the right and the chart scrolls as more come in
+pub struct Graph {
+    area: Points,
+    /// Generations the graph spans
+    length: u64,
+    scale: GraphScale,
+    samples: VecDeque<GenerationStats>,
+}
+
+impl Graph {
+    pub fn new(length: u64) -> Self {
+        Self {
+            area: SizeAndCenter::ZERO.into(),
+            length: length.max(2),
+            scale: GraphScale::Linear,
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    pub fn scale(&self) -> GraphScale {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: GraphScale) {
+        self.scale = scale;
+    }
+
+    /// Adds the stats of the next generation. Stats of a generation before the latest one mean the board
+    /// went back, so the ones from then on are dropped
+    pub fn push(&mut self, stats: GenerationStats) {
+        if stats.generation == 0 {
+            self.samples.clear();
+        } else {
+            self.truncate(stats.generation-1);
+        }
+        self.samples.push_back(stats);
+        while self.samples.front().is_some_and(|first| first.generation+self.length <= stats.generation) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Drops the stats of generations after `generation`
+    pub fn truncate(&mut self, generation: u64) {
+        while self.samples.back().is_some_and(|last| last.generation > generation) {
+            self.samples.pop_back();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn samples(&self) -> &VecDeque<GenerationStats> {
+        &self.samples
+    }
+
+    /// Highest population, births or deaths in the graph, which is at the top of it
+    pub fn max(&self) -> u64 {
+        self.samples.iter()
+            .map(|stats| stats.population.max(stats.births).max(stats.deaths))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn x_of(&self, generation: u64, latest: u64) -> f32 {
+        let start = latest.saturating_sub(self.length-1);
+        let fraction = (generation-start) as f32/(self.length-1) as f32;
+        self.area.p1x+(self.area.p2x-self.area.p1x)*fraction
+    }
+
+    fn y_of(&self, value: u64, max: u64) -> f32 {
+        let fraction = match self.scale {
+            _ if max == 0 => 0.0,
+            GraphScale::Linear => (value as f64/max as f64) as f32,
+            GraphScale::Log => (value as f64).ln_1p() as f32/(max as f64).ln_1p() as f32,
+        };
+        self.area.p2y+(self.area.p1y-self.area.p2y)*fraction
+    }
+
+    /// Pairs of vertices for the segments of the population, births and deaths lines
+    pub fn lines(&self) -> Vec<OverlayVertex> {
+        let latest = match self.samples.back() {
+            Some(latest) => latest.generation,
+            None => return Vec::new(),
+        };
+        let max = self.max();
+        let colors = [DEATHS_COLOR, BIRTHS_COLOR, POPULATION_COLOR];
+        let mut vertices = Vec::with_capacity(self.samples.len().saturating_sub(1)*2*colors.len());
+        for (series, color) in colors.into_iter().enumerate() {
+            for (a, b) in self.samples.iter().zip(self.samples.iter().skip(1)) {
+                vertices.push(OverlayVertex::new(self.x_of(a.generation, latest), self.y_of(values(a)[series], max), color));
+                vertices.push(OverlayVertex::new(self.x_of(b.generation, latest), self.y_of(values(b)[series], max), color));
+            }
+        }
+        vertices
+    }
+}
+
+/// Deaths, births then population, in the order they're drawn so population is on top
+fn values(stats: &GenerationStats) -> [u64; 3] {
+    [stats.deaths, stats.births, stats.population]
+}
+
+impl Fill for Graph {
+    fn fill(&mut self, fill_target: SizeAndCenter) {
+        self.area = fill_target.into();
+    }
+}
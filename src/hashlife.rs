@@ -0,0 +1,366 @@
+use std::collections::HashMap;
+
+use crate::{rule::Rule, cpu_board::CpuBoard};
+
+/// Index of a node in `HashLife::nodes`
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+/// Largest k `HashLife::jump_pow2` can jump 2^k generations by, keeping coordinates within i64
+pub const MAX_JUMP_POW2: u32 = 60;
+
+/// Nodes kept before `HashLife` throws away everything the current pattern doesn't use
+const MAX_NODES: usize = 1 << 22;
+
+/// Square of 2^level cells, made of four squares of 2^(level-1) cells. Children are ordered
+/// low x low y, high x low y, low x high y, high x high y
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    level: u32,
+    children: [NodeId; 4],
+    population: u64,
+}
+
+/// Quadtree Game of Life engine using Gosper's HashLife algorithm.
+///
+/// Identical squares are stored once and how they evolve is memoised, so patterns with a lot of
+/// repetition in space and time can be run for 2^40 generations and beyond. The board is an
+/// unbounded plane centered on (0, 0), so boards converted from a `CpuBoard` or `GameOfLife` only
+/// match them while nothing reaches their edges.
+pub struct HashLife {
+    nodes: Vec<Node>,
+    interned: HashMap<[NodeId; 4], NodeId>,
+    /// Center of a node after 2^j generations, by (node, j)
+    results: HashMap<(NodeId, u32), NodeId>,
+    /// Empty node of each level
+    empty: Vec<NodeId>,
+    root: NodeId,
+    generation: u64,
+    rule: Rule,
+}
+
+impl HashLife {
+    /// Empty plane. Fails for rules where cells are born with no neighbours, which would fill the plane
+    pub fn new(rule: Rule) -> Result<Self, String> {
+        if rule.birth() & 1 == 1 {
+            return Err(format!("HashLife can't run {}, cells can't be born with 0 neighbours on an unbounded plane", rule))
+        }
+        let mut hashlife = Self {
+            nodes: Vec::new(),
+            interned: HashMap::new(),
+            results: HashMap::new(),
+            empty: Vec::new(),
+            root: DEAD,
+            generation: 0,
+            rule,
+        };
+        hashlife.reset_nodes();
+        hashlife.root = hashlife.empty(3);
+        Ok(hashlife)
+    }
+
+    /// Plane with the live cells of `board`, keeping their coordinates
+    pub fn from_board(board: &CpuBoard) -> Result<Self, String> {
+        let mut hashlife = Self::new(board.rule())?;
+        for (x, y) in board.alive_cells() {
+            hashlife.set(x as i64, y as i64, true);
+        }
+        Ok(hashlife)
+    }
+
+    /// Cells from (0, 0) to (width, height) as a `CpuBoard` with the same rule
+    pub fn to_board(&self, width: u32, height: u32) -> CpuBoard {
+        let mut board = CpuBoard::new(width, height);
+        board.set_rule(self.rule);
+        let half = self.half_size(self.root);
+        self.collect_alive(self.root, -half, -half, &mut |x, y| {
+            if x >= 0 && y >= 0 && x < width as i64 && y < height as i64 {
+                board.set(x as u32, y as u32, true);
+            }
+        }, (0, 0, width as i64, height as i64));
+        board
+    }
+
+    /// Coordinates of every live cell
+    pub fn alive_cells(&self) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        let half = self.half_size(self.root);
+        self.collect_alive(self.root, -half, -half, &mut |x, y| cells.push((x, y)), (-half, -half, half*2, half*2));
+        cells
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    /// Nodes currently stored, including ones the pattern no longer uses
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> bool {
+        let half = self.half_size(self.root);
+        if x < -half || y < -half || x >= half || y >= half {
+            return false
+        }
+        let mut node = self.root;
+        let (mut x, mut y) = (x+half, y+half);
+        while self.nodes[node as usize].level > 0 {
+            let quarter = 1 << (self.nodes[node as usize].level-1);
+            node = self.nodes[node as usize].children[quadrant(x >= quarter, y >= quarter)];
+            x %= quarter;
+            y %= quarter;
+        }
+        node == ALIVE
+    }
+
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        while !self.contains(x, y) {
+            self.root = self.expand(self.root);
+        }
+        let half = self.half_size(self.root);
+        self.root = self.set_in(self.root, x+half, y+half, alive);
+    }
+
+    /// Runs 2^k generations
+    pub fn jump_pow2(&mut self, k: u32) -> Result<(), String> {
+        if k > MAX_JUMP_POW2 {
+            return Err(format!("can't jump more than 2^{} generations at once", MAX_JUMP_POW2))
+        }
+        let generation = 1u64.checked_shl(k).and_then(|generations| self.generation.checked_add(generations)).ok_or_else(|| format!("jumping 2^{} generations from generation {} overflows", k, self.generation))?;
+        // The pattern can grow by 2^k cells each way, so it needs to be in the middle half of a
+        // root at least 2^(k+2) cells wide, which is the middle quarter once expanded again
+        while self.nodes[self.root as usize].level < k+2 || !self.is_padded(self.root) {
+            self.root = self.expand(self.root);
+        }
+        let expanded = self.expand(self.root);
+        self.root = self.successor(expanded, k);
+        self.generation = generation;
+        if self.nodes.len() > MAX_NODES {
+            self.collect_garbage();
+        }
+        Ok(())
+    }
+
+    /// Runs `n` generations, as a jump for each bit set in `n`
+    pub fn step(&mut self, n: u64) -> Result<(), String> {
+        for k in 0..u64::BITS {
+            if n >> k & 1 == 1 {
+                self.jump_pow2(k)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the nodes and memoised results the current pattern doesn't use
+    pub fn collect_garbage(&mut self) {
+        let old_nodes = std::mem::take(&mut self.nodes);
+        self.reset_nodes();
+        let mut moved = HashMap::new();
+        self.root = self.copy_node(&old_nodes, self.root, &mut moved);
+    }
+
+    fn reset_nodes(&mut self) {
+        self.nodes.clear();
+        self.interned.clear();
+        self.results.clear();
+        self.empty.clear();
+        self.nodes.push(Node { level: 0, children: [DEAD; 4], population: 0 });
+        self.nodes.push(Node { level: 0, children: [DEAD; 4], population: 1 });
+        self.empty.push(DEAD);
+    }
+
+    fn copy_node(&mut self, old_nodes: &[Node], node: NodeId, moved: &mut HashMap<NodeId, NodeId>) -> NodeId {
+        if node == DEAD || node == ALIVE {
+            return node
+        }
+        if let Some(&new) = moved.get(&node) {
+            return new
+        }
+        let old = old_nodes[node as usize];
+        let children = old.children.map(|child| self.copy_node(old_nodes, child, moved));
+        let new = self.join(children);
+        moved.insert(node, new);
+        new
+    }
+
+    fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+        if let Some(&node) = self.interned.get(&children) {
+            return node
+        }
+        let node = Node {
+            level: self.nodes[children[0] as usize].level+1,
+            children,
+            population: children.iter().map(|&child| self.nodes[child as usize].population).sum(),
+        };
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(node);
+        self.interned.insert(children, id);
+        id
+    }
+
+    fn empty(&mut self, level: u32) -> NodeId {
+        while self.empty.len() <= level as usize {
+            let smaller = *self.empty.last().unwrap();
+            let node = self.join([smaller; 4]);
+            self.empty.push(node);
+        }
+        self.empty[level as usize]
+    }
+
+    fn half_size(&self, node: NodeId) -> i64 {
+        1 << (self.nodes[node as usize].level-1)
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        let half = self.half_size(self.root);
+        x >= -half && y >= -half && x < half && y < half
+    }
+
+    /// Same cells centered in a node twice as wide
+    fn expand(&mut self, node: NodeId) -> NodeId {
+        let [low_low, high_low, low_high, high_high] = self.nodes[node as usize].children;
+        let empty = self.empty(self.nodes[node as usize].level-1);
+        let children = [
+            self.join([empty, empty, empty, low_low]),
+            self.join([empty, empty, high_low, empty]),
+            self.join([empty, low_high, empty, empty]),
+            self.join([high_high, empty, empty, empty]),
+        ];
+        self.join(children)
+    }
+
+    /// Whether all of the node's live cells are in its middle half
+    fn is_padded(&self, node: NodeId) -> bool {
+        let grid = self.grandchildren(node);
+        let middle = grid[1][1..3].iter().chain(&grid[2][1..3]).map(|&node| self.nodes[node as usize].population).sum::<u64>();
+        middle == self.nodes[node as usize].population
+    }
+
+    /// Grandchildren of a node as a 4x4 grid, indexed [y][x]
+    fn grandchildren(&self, node: NodeId) -> [[NodeId; 4]; 4] {
+        let mut grid = [[DEAD; 4]; 4];
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, grandchild) in row.iter_mut().enumerate() {
+                let child = self.nodes[node as usize].children[quadrant(x >= 2, y >= 2)];
+                *grandchild = self.nodes[child as usize].children[quadrant(x%2 == 1, y%2 == 1)];
+            }
+        }
+        grid
+    }
+
+    fn set_in(&mut self, node: NodeId, x: i64, y: i64, alive: bool) -> NodeId {
+        let level = self.nodes[node as usize].level;
+        if level == 0 {
+            return if alive { ALIVE } else { DEAD }
+        }
+        let quarter = 1 << (level-1);
+        let mut children = self.nodes[node as usize].children;
+        let index = quadrant(x >= quarter, y >= quarter);
+        children[index] = self.set_in(children[index], x%quarter, y%quarter, alive);
+        self.join(children)
+    }
+
+    /// Calls `alive` with the coordinates of every live cell of the node at (x, y) inside `window` (x, y, width, height)
+    fn collect_alive(&self, node: NodeId, x: i64, y: i64, alive: &mut impl FnMut(i64, i64), window: (i64, i64, i64, i64)) {
+        let Node { level, children, population } = self.nodes[node as usize];
+        let size = 1i64 << level;
+        if population == 0 || x >= window.0+window.2 || y >= window.1+window.3 || x+size <= window.0 || y+size <= window.1 {
+            return
+        }
+        if level == 0 {
+            alive(x, y);
+            return
+        }
+        let half = size/2;
+        for (index, &child) in children.iter().enumerate() {
+            self.collect_alive(child, x+half*(index as i64 & 1), y+half*(index as i64 >> 1), alive, window);
+        }
+    }
+
+    /// The middle half of a node after 2^j generations, where j is at most the node's level - 2
+    fn successor(&mut self, node: NodeId, j: u32) -> NodeId {
+        let Node { level, population, .. } = self.nodes[node as usize];
+        debug_assert!(level >= 2 && j+2 <= level);
+        if population == 0 {
+            return self.empty(level-1)
+        }
+        if let Some(&result) = self.results.get(&(node, j)) {
+            return result
+        }
+
+        let result = if level == 2 {
+            self.base_successor(node)
+        } else {
+            let grid = self.grandchildren(node);
+            // 3x3 overlapping nodes a level down, indexed [y][x]
+            let mut parts = [[DEAD; 3]; 3];
+            for y in 0..3 {
+                for x in 0..3 {
+                    parts[y][x] = self.join([grid[y][x], grid[y][x+1], grid[y+1][x], grid[y+1][x+1]]);
+                }
+            }
+            // At full speed both halves of the jump run 2^(j-1) generations, otherwise only the second runs
+            let full_speed = j+2 == level;
+            for row in parts.iter_mut() {
+                for part in row.iter_mut() {
+                    *part = if full_speed {
+                        self.successor(*part, j-1)
+                    } else {
+                        self.center(*part)
+                    };
+                }
+            }
+            let second_j = if full_speed { j-1 } else { j };
+            let mut quadrants = [DEAD; 4];
+            for (index, quadrant) in quadrants.iter_mut().enumerate() {
+                let (x, y) = (index & 1, index >> 1);
+                let combined = self.join([parts[y][x], parts[y][x+1], parts[y+1][x], parts[y+1][x+1]]);
+                *quadrant = self.successor(combined, second_j);
+            }
+            self.join(quadrants)
+        };
+        self.results.insert((node, j), result);
+        result
+    }
+
+    fn center(&mut self, node: NodeId) -> NodeId {
+        let grid = self.grandchildren(node);
+        self.join([grid[1][1], grid[1][2], grid[2][1], grid[2][2]])
+    }
+
+    /// Middle 2x2 cells of a 4x4 node after one generation
+    fn base_successor(&mut self, node: NodeId) -> NodeId {
+        let grid = self.grandchildren(node);
+        let mut next = [DEAD; 4];
+        for (index, cell) in next.iter_mut().enumerate() {
+            let (x, y) = (1+(index & 1), 1+(index >> 1));
+            let mut count = 0;
+            for dy in 0..3 {
+                for dx in 0..3 {
+                    if (dx != 1 || dy != 1) && grid[y+dy-1][x+dx-1] == ALIVE {
+                        count += 1;
+                    }
+                }
+            }
+            if self.rule.next(grid[y][x] == ALIVE, count) {
+                *cell = ALIVE;
+            }
+        }
+        self.join(next)
+    }
+}
+
+fn quadrant(high_x: bool, high_y: bool) -> usize {
+    high_x as usize | (high_y as usize) << 1
+}
@@ -0,0 +1,234 @@
+/// Heat at which the heatmap colour is halfway from cold to hot
+pub const HALF_HOT: f32 = 8.0;
+/// Bytes each cell's heat and the generation it was last added to take, matching `Heat` in game_of_life.wgsl
+const HEAT_SIZE: u64 = 8;
+
+/// How much of its heat a cell keeps each generation it doesn't change, for heat that halves every
+/// `half_life` generations, or never decays if it's None
+pub fn decay(half_life: Option<u32>) -> f32 {
+    match half_life {
+        Some(half_life) => 0.5f32.powf(1.0/half_life.max(1) as f32),
+        None => 1.0,
+    }
+}
+
+/// Colour of a cell with `heat`, from white when cold through yellow and red to black when hot. Same as
+/// heat_colour in game_of_life.wgsl
+pub fn heat_colour(heat: f32) -> [f32; 4] {
+    let t = heat/(heat+HALF_HOT);
+    let stops = [[1.0, 1.0, 1.0, 1.0], [1.0, 0.85, 0.2, 1.0], [0.85, 0.1, 0.05, 1.0], [0.1, 0.0, 0.0, 1.0]];
+    let scaled = t*(stops.len()-1) as f32;
+    let i = (scaled as usize).min(stops.len()-2);
+    let fraction = (scaled-i as f32).min(1.0);
+    [0, 1, 2, 3].map(|c| stops[i][c]+(stops[i+1][c]-stops[i][c])*fraction)
+}
+
+/// Matches Heatmap in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct HeatmapUniformData {
+    decay: f32,
+    /// 0 while heat isn't being added up or shown
+    enabled: u32,
+    /// Low bits of the generation the board is at, to decay heat up to
+    generation: u32,
+    width: u32,
+}
+
+bui::typed_uniform!(HeatmapUniform, HeatmapUniformData, "Heatmap Uniform");
+
+/// How many times each cell of a texture board changed, kept on the GPU. Heat decays lazily: a cell's heat
+/// is only brought up to date when it changes again or is drawn, from the generation it last changed at.
+/// The buffer for it is only made once the heatmap is first turned on
+pub(crate) struct Heatmap {
+    width: u32,
+    height: u32,
+    enabled: bool,
+    half_life: Option<u32>,
+    /// Whether `buffer` has room for every cell yet
+    allocated: bool,
+    buffer: wgpu::Buffer,
+    uniform: HeatmapUniform,
+    compute_layout: wgpu::BindGroupLayout,
+    render_layout: wgpu::BindGroupLayout,
+    compute_bind_group: wgpu::BindGroup,
+    render_bind_group: wgpu::BindGroup,
+}
+
+impl Heatmap {
+    pub(crate) fn new(device: &wgpu::Device, width: u32, height: u32) -> Self {
+        let layout = |label, visibility, read_only| device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ]
+        });
+        let compute_layout = layout("Heatmap compute bind group layout", wgpu::ShaderStages::COMPUTE, false);
+        let render_layout = layout("Heatmap render bind group layout", wgpu::ShaderStages::FRAGMENT, true);
+        let uniform = HeatmapUniform::new(device);
+        // Stands in until the heatmap is turned on
+        let buffer = Self::create_buffer(device, HEAT_SIZE);
+        let (compute_bind_group, render_bind_group) = Self::create_bind_groups(device, &compute_layout, &render_layout, &buffer, &uniform);
+
+        Self {
+            width,
+            height,
+            enabled: false,
+            half_life: None,
+            allocated: false,
+            buffer,
+            uniform,
+            compute_layout,
+            render_layout,
+            compute_bind_group,
+            render_bind_group,
+        }
+    }
+
+    fn create_buffer(device: &wgpu::Device, size: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Heatmap buffer"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn create_bind_groups(device: &wgpu::Device, compute_layout: &wgpu::BindGroupLayout, render_layout: &wgpu::BindGroupLayout, buffer: &wgpu::Buffer, uniform: &HeatmapUniform) -> (wgpu::BindGroup, wgpu::BindGroup) {
+        let bind_group = |label, layout| device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: uniform.binding(),
+                },
+            ]
+        });
+        (bind_group("Heatmap compute bind group", compute_layout), bind_group("Heatmap render bind group", render_layout))
+    }
+
+    pub(crate) fn compute_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.compute_layout
+    }
+
+    pub(crate) fn render_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.render_layout
+    }
+
+    pub(crate) fn compute_bind_group(&self) -> &wgpu::BindGroup {
+        &self.compute_bind_group
+    }
+
+    pub(crate) fn render_bind_group(&self) -> &wgpu::BindGroup {
+        &self.render_bind_group
+    }
+
+    fn buffer_size(&self) -> u64 {
+        self.width as u64*self.height as u64*HEAT_SIZE
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Starts or stops adding up and showing heat. Turning it on starts from no heat
+    pub(crate) fn set_enabled(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, enabled: bool) -> Result<(), String> {
+        if enabled && !self.enabled {
+            if !self.allocated {
+                let max_size = (device.limits().max_storage_buffer_binding_size as u64).min(device.limits().max_buffer_size);
+                if self.buffer_size() > max_size {
+                    return Err(format!("a {}x{} heatmap needs {} MiB, more than the maximum of {} MiB", self.width, self.height, self.buffer_size() >> 20, max_size >> 20))
+                }
+                self.buffer = Self::create_buffer(device, self.buffer_size());
+                (self.compute_bind_group, self.render_bind_group) = Self::create_bind_groups(device, &self.compute_layout, &self.render_layout, &self.buffer, &self.uniform);
+                self.allocated = true;
+            }
+            self.reset(queue);
+        }
+        self.enabled = enabled;
+        Ok(())
+    }
+
+    pub(crate) fn half_life(&self) -> Option<u32> {
+        self.half_life
+    }
+
+    /// Decays heat so it halves every `half_life` generations, or never if it's None. Heat built up so far
+    /// decays at the new rate too
+    pub(crate) fn set_half_life(&mut self, half_life: Option<u32>) {
+        self.half_life = half_life;
+    }
+
+    /// Cools every cell down to no heat
+    pub(crate) fn reset(&self, queue: &wgpu::Queue) {
+        let size = if self.allocated { self.buffer_size() } else { HEAT_SIZE };
+        queue.write_buffer(&self.buffer, 0, &vec![0; size as usize]);
+    }
+
+    /// Brings the uniform up to date with the board being at `generation`
+    pub(crate) fn set_uniform(&self, queue: &wgpu::Queue, generation: u64) {
+        self.uniform.set(&HeatmapUniformData {
+            decay: decay(self.half_life),
+            enabled: self.enabled as u32,
+            generation: generation as u32,
+            width: self.width,
+        }, queue);
+    }
+
+    /// Heat of every cell, row by row, decayed up to `generation`
+    pub(crate) fn read(&self, device: &wgpu::Device, queue: &wgpu::Queue, generation: u64) -> Vec<f32> {
+        if !self.enabled {
+            return vec![0.0; (self.width*self.height) as usize]
+        }
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Heatmap read buffer"),
+            size: self.buffer_size(),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Heatmap read command encoder")
+        });
+        command_encoder.copy_buffer_to_buffer(&self.buffer, 0, &read_buffer, 0, self.buffer_size());
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let read_slice = read_buffer.slice(..);
+        read_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map heatmap read buffer"));
+        device.poll(wgpu::Maintain::Wait);
+
+        let decay = decay(self.half_life);
+        let heat = {
+            let data = read_slice.get_mapped_range();
+            let words: &[u32] = bytemuck::cast_slice(&data);
+            words.chunks_exact(2)
+                .map(|cell| f32::from_bits(cell[0])*decay.powf((generation as u32).wrapping_sub(cell[1]) as f32))
+                .collect()
+        };
+        read_buffer.unmap();
+        heat
+    }
+}
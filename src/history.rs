@@ -0,0 +1,310 @@
+use std::collections::VecDeque;
+
+use crate::cpu_board::CpuBoard;
+
+/// A board and the generation it was at, kept on the CPU as the lengths of alternating runs of dead
+/// and live cells, starting with dead ones. Lengths are LEB128 varints, so sparse boards take a few bytes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    width: u32,
+    height: u32,
+    generation: u64,
+    runs: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn new(board: &CpuBoard, generation: u64) -> Self {
+        let mut builder = SnapshotBuilder::new(board.width(), board.height(), generation);
+        for y in 0..board.height() {
+            for x in 0..board.width() {
+                builder.push_run(board.get(x, y), 1);
+            }
+        }
+        builder.finish()
+    }
+
+    /// Rebuilds a snapshot of a `width` by `height` board from the `runs` it was stored as
+    pub fn from_runs(width: u32, height: u32, generation: u64, runs: Vec<u8>) -> Result<Self, String> {
+        let mut bytes = runs.iter();
+        let mut cells: u64 = 0;
+        while let Some(run) = read_varint(&mut bytes) {
+            cells = cells.checked_add(run).ok_or("runs overflow")?;
+        }
+        if cells != width as u64*height as u64 {
+            return Err(format!("runs cover {} cells of a {}x{} board", cells, width, height))
+        }
+        Ok(Self {
+            width,
+            height,
+            generation,
+            runs,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The lengths of runs of dead and live cells the board is stored as
+    pub fn runs(&self) -> &[u8] {
+        &self.runs
+    }
+
+    /// Bytes of memory the snapshot takes up
+    pub fn size(&self) -> usize {
+        std::mem::size_of::<Self>()+self.runs.len()
+    }
+
+    /// The board the snapshot was taken of, with the default rule and topology
+    pub fn to_board(&self) -> CpuBoard {
+        let mut board = CpuBoard::new(self.width, self.height);
+        let width = self.width as u64;
+        let mut bytes = self.runs.iter();
+        let mut index = 0;
+        let mut alive = false;
+        while let Some(run) = read_varint(&mut bytes) {
+            if alive {
+                for index in index..index+run {
+                    board.set((index%width) as u32, (index/width) as u32, true);
+                }
+            }
+            index += run;
+            alive = !alive;
+        }
+        board
+    }
+}
+
+/// Encodes the cells of a board into a snapshot as they're read, row by row, so boards read back from
+/// the GPU don't need a `CpuBoard` of a byte per cell first
+pub struct SnapshotBuilder {
+    width: u32,
+    height: u32,
+    generation: u64,
+    runs: Vec<u8>,
+    /// Whether the run being counted is of live cells
+    alive: bool,
+    run: u64,
+}
+
+impl SnapshotBuilder {
+    pub fn new(width: u32, height: u32, generation: u64) -> Self {
+        Self {
+            width,
+            height,
+            generation,
+            runs: Vec::new(),
+            alive: false,
+            run: 0,
+        }
+    }
+
+    /// Adds the next `cells` cells, all alive or all dead
+    pub fn push_run(&mut self, alive: bool, cells: u64) {
+        if cells == 0 {
+            return
+        }
+        if alive != self.alive {
+            push_varint(&mut self.runs, self.run);
+            self.alive = alive;
+            self.run = 0;
+        }
+        self.run += cells;
+    }
+
+    /// Adds the next `cells` cells from the bits of `word`, lowest first, a run at a time
+    pub fn push_bits(&mut self, mut word: u32, cells: u32) {
+        let mut left = cells;
+        while left > 0 {
+            let alive = word&1 == 1;
+            let run = if alive { word.trailing_ones() } else { word.trailing_zeros() }.min(left);
+            self.push_run(alive, run as u64);
+            word = word.checked_shr(run).unwrap_or(0);
+            left -= run;
+        }
+    }
+
+    pub fn finish(mut self) -> Snapshot {
+        push_varint(&mut self.runs, self.run);
+        self.runs.shrink_to_fit();
+        Snapshot {
+            width: self.width,
+            height: self.height,
+            generation: self.generation,
+            runs: self.runs,
+        }
+    }
+}
+
+fn push_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn read_varint<'a>(bytes: &mut impl Iterator<Item = &'a u8>) -> Option<u64> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.next()?;
+        value |= ((byte&0x7F) as u64) << shift;
+        if byte < 0x80 {
+            return Some(value)
+        }
+        shift += 7;
+    }
+}
+
+/// Snapshots of the board before each change, to undo back to, and of the changes undone, to redo.
+/// The oldest are dropped once they take up more than `budget` bytes
+pub struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    budget: usize,
+    size: usize,
+}
+
+impl History {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            budget,
+            size: 0,
+        }
+    }
+
+    /// Whether there is any budget for snapshots. Without one, there's no point taking them
+    pub fn is_enabled(&self) -> bool {
+        self.budget > 0
+    }
+
+    /// Bytes taken up by all the snapshots
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Records the board before a change. Whatever was undone can't be redone anymore
+    pub fn record(&mut self, snapshot: Snapshot) {
+        for undone in self.redo.drain(..) {
+            self.size -= undone.size();
+        }
+        self.size += snapshot.size();
+        self.undo.push_back(snapshot);
+        self.trim();
+    }
+
+    /// Swaps the `current` board for the one before the last change
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.pop_undo(current)?;
+        self.trim();
+        Some(snapshot)
+    }
+
+    /// Swaps the `current` board for the one after the last undone change
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        if self.redo.is_empty() {
+            return None
+        }
+        let snapshot = self.pop_redo(current);
+        self.trim();
+        Some(snapshot)
+    }
+
+    /// Whether there is a snapshot to go back to at or before `generation`
+    pub fn reaches(&self, generation: u64) -> bool {
+        self.undo.iter().any(|snapshot| snapshot.generation() <= generation)
+    }
+
+    /// The earliest and latest generations of all the snapshots, if there are any
+    pub fn generation_range(&self) -> Option<(u64, u64)> {
+        let generations = self.undo.iter().chain(self.redo.iter()).map(Snapshot::generation);
+        Some((generations.clone().min()?, generations.max()?))
+    }
+
+    /// Generation of the board `back_to` or `forward_to` would give to compute up to `generation` from, with
+    /// the current board at `current`. None if no snapshot goes back that far
+    pub fn computes_from(&self, current: u64, generation: u64) -> Option<u64> {
+        if generation < current {
+            self.undo.iter().rev().map(Snapshot::generation).find(|from| *from <= generation)
+        } else {
+            Some(self.redo.iter().rev().map(Snapshot::generation).take_while(|from| *from <= generation).last().unwrap_or(current))
+        }
+    }
+
+    /// Undoes changes until the board is at or before `generation`. Nothing changed after the returned
+    /// snapshot but computing generations, so computing up to `generation` from it gives the board then
+    pub fn back_to(&mut self, current: Snapshot, generation: u64) -> Option<Snapshot> {
+        if !self.reaches(generation) {
+            return None
+        }
+        let mut snapshot = current;
+        loop {
+            snapshot = self.pop_undo(snapshot).unwrap();
+            if snapshot.generation() <= generation {
+                self.trim();
+                return Some(snapshot)
+            }
+        }
+    }
+
+    /// Redoes changes up to the last one at or before `generation`, if there are any. Like `back_to`,
+    /// computing up to `generation` from the returned snapshot gives the board then
+    pub fn forward_to(&mut self, current: Snapshot, generation: u64) -> Option<Snapshot> {
+        if self.redo.last()?.generation() > generation {
+            return None
+        }
+        let mut snapshot = current;
+        while self.redo.last().is_some_and(|next| next.generation() <= generation) {
+            snapshot = self.pop_redo(snapshot);
+        }
+        self.trim();
+        Some(snapshot)
+    }
+
+    fn pop_undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let snapshot = self.undo.pop_back()?;
+        self.size -= snapshot.size();
+        self.size += current.size();
+        self.redo.push(current);
+        Some(snapshot)
+    }
+
+    fn pop_redo(&mut self, current: Snapshot) -> Snapshot {
+        let snapshot = self.redo.pop().unwrap();
+        self.size -= snapshot.size();
+        self.size += current.size();
+        self.undo.push_back(current);
+        snapshot
+    }
+
+    /// Drops the oldest snapshots, then the furthest redos, until the rest fit in the budget
+    fn trim(&mut self) {
+        while self.size > self.budget {
+            let dropped = match self.undo.pop_front() {
+                Some(snapshot) => snapshot,
+                None if !self.redo.is_empty() => self.redo.remove(0),
+                None => break,
+            };
+            self.size -= dropped.size();
+        }
+    }
+}
@@ -1,12 +1,31 @@
 use bui::{ttf::CachedFace};
 use bui_basic::{signal::{ResizedSignal, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal, CharacterInputSignal, SignalReciever, RedrawCallback}, containers::Init};
-use winit::{window::{Window, Fullscreen}, event_loop::ControlFlow, event::{Event, WindowEvent, MouseButton, ElementState, VirtualKeyCode, TouchPhase}};
+use winit::{window::{Window, Fullscreen}, event_loop::ControlFlow, event::{Event, WindowEvent, MouseButton, ElementState, VirtualKeyCode, ModifiersState, TouchPhase}};
 use log::info;
 use std::sync::{Arc, Mutex};
 
 pub mod setup;
 pub mod game;
+pub mod graph;
+pub mod heatmap;
 pub mod bui_view;
+pub mod chunked_board;
+pub mod cpu_board;
+pub mod cycles;
+pub mod edit;
+pub mod hashlife;
+pub mod history;
+pub mod overlay;
+pub mod packed_board;
+pub mod palette;
+pub mod rule;
+pub mod run_until;
+pub mod session;
+pub mod soup;
+pub mod stats;
+pub mod timeline;
+pub mod topology;
+pub mod trails;
 use bui_view::BuiView;
 
 fn resume(window: &Window, resumed: &mut bool, bui_view: &mut BuiView) {
@@ -14,6 +33,13 @@ fn resume(window: &Window, resumed: &mut bool, bui_view: &mut BuiView) {
     *resumed = true;
 }
 
+/// A key was pressed, sent as well as any character it types
+pub struct KeyPressedSignal {
+    pub key: VirtualKeyCode,
+    /// Modifier keys held down at the time
+    pub modifiers: ModifiersState,
+}
+
 pub struct ResumeCallback {
     resume: bool
 }
@@ -95,6 +121,7 @@ pub fn main() {
     };
 
     let mut resumed = false;
+    let mut modifiers = ModifiersState::empty();
     #[cfg(target_os="android")]
     let mut capitalized = false;
 
@@ -200,6 +227,9 @@ pub fn main() {
                             _ => {}
                         }
                     },
+                    WindowEvent::ModifiersChanged(new_modifiers) => {
+                        modifiers = *new_modifiers;
+                    },
                     WindowEvent::KeyboardInput {
                         input,
                         ..
@@ -211,6 +241,13 @@ pub fn main() {
                                 } else {
                                     window.set_fullscreen(Some(Fullscreen::Borderless(None)));
                                 },
+                                key if input.state == ElementState::Pressed => take_redraw_callback(
+                                    bui_view.take_signal(&mut KeyPressedSignal {
+                                        key,
+                                        modifiers,
+                                    }),
+                                    &window,
+                                ),
                                 _ => {}
                             }
                         }
@@ -362,8 +399,19 @@ pub fn main() {
                     _ => {}
                 }
             },
+            Event::MainEventsCleared => {
+                if resumed {
+                    take_redraw_callback(bui_view.update(), &window);
+                }
+            },
             Event::RedrawRequested(_) => {
                 bui_view.render();
+            },
+            Event::RedrawEventsCleared => {
+                // Wake up for the next generation while the game is running
+                if let Some(next_update) = bui_view.next_update() {
+                    *control_flow = ControlFlow::WaitUntil(next_update);
+                }
             }
             _ => {}
         }
@@ -0,0 +1,229 @@
+use bui::rect::Points;
+
+/// Rectangles the overlay has room for before its instance buffer grows
+const INITIAL_CAPACITY: usize = 64;
+/// Line vertices the overlay has room for before its line buffer grows
+const INITIAL_LINE_CAPACITY: usize = 1024;
+
+/// Flat coloured rectangles and lines drawn over the board and text, for the bits of UI text can't draw
+pub struct Overlay {
+    render_pipeline: wgpu::RenderPipeline,
+    line_pipeline: wgpu::RenderPipeline,
+    instance_buffer: wgpu::Buffer,
+    capacity: usize,
+    count: u32,
+    line_buffer: wgpu::Buffer,
+    line_capacity: usize,
+    line_count: u32,
+}
+
+impl Overlay {
+    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("overlay.wgsl"));
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Overlay render pipeline layout"),
+            bind_group_layouts: &[],
+            push_constant_ranges: &[],
+        });
+
+        Self {
+            render_pipeline: Self::create_pipeline(device, &shader, &pipeline_layout, texture_format, "overlay_vert", OverlayRect::desc(), wgpu::PrimitiveTopology::TriangleStrip),
+            line_pipeline: Self::create_pipeline(device, &shader, &pipeline_layout, texture_format, "line_vert", OverlayVertex::desc(), wgpu::PrimitiveTopology::LineList),
+            instance_buffer: Self::create_buffer(device, "Overlay instance buffer", INITIAL_CAPACITY*std::mem::size_of::<OverlayRect>()),
+            capacity: INITIAL_CAPACITY,
+            count: 0,
+            line_buffer: Self::create_buffer(device, "Overlay line buffer", INITIAL_LINE_CAPACITY*std::mem::size_of::<OverlayVertex>()),
+            line_capacity: INITIAL_LINE_CAPACITY,
+            line_count: 0,
+        }
+    }
+
+    fn create_pipeline(device: &wgpu::Device, shader: &wgpu::ShaderModule, layout: &wgpu::PipelineLayout, texture_format: wgpu::TextureFormat, vertex_entry_point: &str, buffer: wgpu::VertexBufferLayout, topology: wgpu::PrimitiveTopology) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Overlay render pipeline"),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: vertex_entry_point,
+                buffers: &[
+                    buffer,
+                ]
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: "overlay_frag",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL
+                })]
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        })
+    }
+
+    fn create_buffer(device: &wgpu::Device, label: &str, size: usize) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: size as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Replaces the rectangles drawn, later ones on top
+    pub fn set_rects(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, rects: &[OverlayRect]) {
+        if rects.len() > self.capacity {
+            self.capacity = rects.len().next_power_of_two();
+            self.instance_buffer = Self::create_buffer(device, "Overlay instance buffer", self.capacity*std::mem::size_of::<OverlayRect>());
+        }
+        queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(rects));
+        self.count = rects.len() as u32;
+    }
+
+    /// Replaces the lines drawn over the rectangles, each a pair of vertices
+    pub fn set_lines(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, vertices: &[OverlayVertex]) {
+        if vertices.len() > self.line_capacity {
+            self.line_capacity = vertices.len().next_power_of_two();
+            self.line_buffer = Self::create_buffer(device, "Overlay line buffer", self.line_capacity*std::mem::size_of::<OverlayVertex>());
+        }
+        queue.write_buffer(&self.line_buffer, 0, bytemuck::cast_slice(vertices));
+        self.line_count = vertices.len() as u32;
+    }
+
+    pub fn render(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        if self.count == 0 && self.line_count == 0 {
+            return
+        }
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Overlay render pass"),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: true,
+                    }
+                })
+            ],
+            depth_stencil_attachment: None,
+        });
+        if self.count > 0 {
+            render_pass.set_pipeline(&self.render_pipeline);
+            render_pass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+            render_pass.draw(0..4, 0..self.count);
+        }
+        if self.line_count > 0 {
+            render_pass.set_pipeline(&self.line_pipeline);
+            render_pass.set_vertex_buffer(0, self.line_buffer.slice(..));
+            render_pass.draw(0..self.line_count, 0..1);
+        }
+    }
+}
+
+/// Matches OverlayRect in overlay.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OverlayRect {
+    top_left: [f32; 2],
+    bottom_right: [f32; 2],
+    color: [f32; 4],
+}
+
+impl OverlayRect {
+    /// A rectangle covering `points`, in normalized device coordinates
+    pub fn new(points: Points, color: [f32; 4]) -> Self {
+        Self {
+            top_left: [points.p1x, points.p1y],
+            bottom_right: [points.p2x, points.p2y],
+            color,
+        }
+    }
+
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<OverlayRect>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 4]>() as wgpu::BufferAddress,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+            ]
+        }
+    }
+}
+
+/// End of a line drawn by the overlay. Matches OverlayVertex in overlay.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OverlayVertex {
+    position: [f32; 2],
+    color: [f32; 4],
+}
+
+impl OverlayVertex {
+    /// A vertex at `x`, `y` in normalized device coordinates
+    pub fn new(x: f32, y: f32, color: [f32; 4]) -> Self {
+        Self {
+            position: [x, y],
+            color,
+        }
+    }
+
+    pub fn position(&self) -> [f32; 2] {
+        self.position
+    }
+
+    pub fn color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        use std::mem;
+        wgpu::VertexBufferLayout {
+            array_stride: mem::size_of::<OverlayVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
+            ]
+        }
+    }
+}
@@ -0,0 +1,41 @@
+struct OverlayRect {
+    @location(0) top_left: vec2<f32>,
+    @location(1) bottom_right: vec2<f32>,
+    @location(2) color: vec4<f32>,
+}
+
+struct OverlayOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@vertex
+fn overlay_vert(
+    @builtin(vertex_index) vertex: u32,
+    rect: OverlayRect,
+) -> OverlayOutput {
+    // Top right, bottom right, top left then bottom left, like GameOfLifeVertex::QUAD
+    let corner = vec2<f32>(f32(1u - vertex/2u), f32(vertex%2u));
+    var out: OverlayOutput;
+    out.position = vec4<f32>(mix(rect.top_left, rect.bottom_right, corner), 0.0, 1.0);
+    out.color = rect.color;
+    return out;
+}
+
+struct OverlayVertex {
+    @location(0) position: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn line_vert(vertex: OverlayVertex) -> OverlayOutput {
+    var out: OverlayOutput;
+    out.position = vec4<f32>(vertex.position, 0.0, 1.0);
+    out.color = vertex.color;
+    return out;
+}
+
+@fragment
+fn overlay_frag(in: OverlayOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
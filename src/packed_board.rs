@@ -0,0 +1,709 @@
+use bui::rect::{SizeAndCenter, Points, FillAspect};
+use bui_basic::{signal::{SignalReciever, ResizedSignal, CursorMovedSignal, MouseLeftUpSignal}, containers::Fill};
+
+use crate::{rule::Rule, topology::Topology, cpu_board::CpuBoard, history::{Snapshot, SnapshotBuilder}, stats::{Stats, GenerationStats}, game::{RuleUniform, TopologyUniform, ToggleCellUniform, GameOfLifeVertex, GAME_OF_LIFE_VERTEX_SIZE, COMPUTE_WORKGROUP_SIZE, MAX_GENERATIONS_PER_SUBMIT, dispatch_size}};
+
+bui::typed_uniform!(BoardSizeUniform, [u32; 2], "Packed Board Size Uniform");
+
+/// Game of Life board packed 32 cells to a `u32` in storage buffers.
+///
+/// Takes 1 bit per cell instead of the 1 to 4 bytes of `GameOfLife`'s textures, so boards
+/// far bigger than textures allow fit on the GPU. Steps 32 cells per invocation with bitwise operations.
+pub struct PackedGameOfLife {
+    words_a: wgpu::Buffer,
+    words_b: wgpu::Buffer,
+    compute_pipeline: wgpu::ComputePipeline,
+    board_bind_group_a: wgpu::BindGroup,
+    board_bind_group_b: wgpu::BindGroup,
+    render_pipeline: wgpu::RenderPipeline,
+    render_bind_group_a: wgpu::BindGroup,
+    render_bind_group_b: wgpu::BindGroup,
+    active_words: Words,
+    generation: u64,
+    width: u32,
+    height: u32,
+    aspect: f32,
+    vertices: [GameOfLifeVertex; 4],
+    vertex_buffer: wgpu::Buffer,
+    resx: f32,
+    resy: f32,
+    area: Points,
+    mousex: f32,
+    mousey: f32,
+    toggle_pipeline: wgpu::ComputePipeline,
+    toggle_cell_uniform: ToggleCellUniform,
+    toggle_bind_group_a: wgpu::BindGroup,
+    toggle_bind_group_b: wgpu::BindGroup,
+    rule: Rule,
+    rule_uniform: RuleUniform,
+    topology: Topology,
+    topology_uniform: TopologyUniform,
+    size_uniform: BoardSizeUniform,
+    stats: Stats,
+}
+
+enum Words {
+    A,
+    B,
+}
+
+impl PackedGameOfLife {
+    /// Checks that a `width` by `height` board fits within the device's `limits`
+    pub fn check_size(width: u32, height: u32, limits: &wgpu::Limits) -> Result<(), String> {
+        let max_dispatch = limits.max_compute_workgroups_per_dimension as u64*COMPUTE_WORKGROUP_SIZE as u64;
+        if words_per_row(width) as u64 > max_dispatch {
+            return Err(format!("width exceeds the maximum of {}", max_dispatch*32))
+        }
+        if height as u64 > max_dispatch {
+            return Err(format!("height exceeds the maximum of {}", max_dispatch))
+        }
+        let max_size = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        if buffer_size(width, height) > max_size {
+            return Err(format!("a {}x{} board needs {} MiB per buffer, more than the maximum of {} MiB", width, height, buffer_size(width, height) >> 20, max_size >> 20))
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(device: &wgpu::Device, texture_format: wgpu::TextureFormat, width: u32, height: u32, rule: Rule, topology: Topology, resx: f32, resy: f32) -> Self {
+        let shader = device.create_shader_module(wgpu::include_wgsl!("game_of_life.wgsl"));
+
+        let words_a = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Packed Game of Life words A"),
+            size: buffer_size(width, height),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let words_b = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Packed Game of Life words B"),
+            size: buffer_size(width, height),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let board_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Packed Game of Life board bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                Stats::layout_entry(),
+            ]
+        });
+
+        let rule_uniform = RuleUniform::new(device);
+        let topology_uniform = TopologyUniform::new(device);
+        let size_uniform = BoardSizeUniform::new(device);
+        let stats = Stats::new(device, &shader, &board_bind_group_layout);
+
+        let board_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &board_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: words_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: words_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rule_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: topology_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: size_uniform.binding()
+                },
+                stats.bind_group_entry(),
+            ],
+            label: Some("Packed Game of Life board bind group A")
+        });
+
+        let board_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &board_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: words_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: words_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: rule_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: topology_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: size_uniform.binding()
+                },
+                stats.bind_group_entry(),
+            ],
+            label: Some("Packed Game of Life board bind group B")
+        });
+
+        let compute_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Packed Game of Life compute pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Packed Game of Life compute pipeline layout"),
+                bind_group_layouts: &[
+                    &board_bind_group_layout
+                ],
+                push_constant_ranges: &[],
+            })),
+            module: &shader,
+            entry_point: "compute_packed"
+        });
+
+        let render_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Packed Game of Life render bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }
+            ]
+        });
+
+        let render_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Packed Game of Life render bind group A"),
+            layout: &render_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: words_a.as_entire_binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: size_uniform.binding()
+                },
+            ]
+        });
+
+        let render_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Packed Game of Life render bind group B"),
+            layout: &render_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: words_b.as_entire_binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: size_uniform.binding()
+                },
+            ]
+        });
+
+        let vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Packed Game of Life vertex buffer"),
+            size: GAME_OF_LIFE_VERTEX_SIZE*4,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Packed Game of Life render pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Packed Game of Life render pipeline layout"),
+                bind_group_layouts: &[&render_bind_group_layout],
+                push_constant_ranges: &[],
+            })),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "render_vert",
+                buffers: &[
+                    GameOfLifeVertex::desc(),
+                ]
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "render_packed",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL
+                })]
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+        });
+
+        let toggle_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Packed Game of Life toggle bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                Stats::layout_entry(),
+            ]
+        });
+
+        let toggle_cell_uniform = ToggleCellUniform::new(device);
+
+        let toggle_bind_group_a = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Packed Game of Life toggle bind group A"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: toggle_cell_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: words_a.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: size_uniform.binding()
+                },
+                stats.bind_group_entry(),
+            ]
+        });
+
+        let toggle_bind_group_b = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Packed Game of Life toggle bind group B"),
+            layout: &toggle_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: toggle_cell_uniform.binding()
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: words_b.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: size_uniform.binding()
+                },
+                stats.bind_group_entry(),
+            ]
+        });
+
+        let toggle_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Packed Game of Life toggle pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Packed Game of Life toggle pipeline layout"),
+                bind_group_layouts: &[
+                    &toggle_bind_group_layout
+                ],
+                push_constant_ranges: &[]
+            })),
+            module: &shader,
+            entry_point: "toggle_packed"
+        });
+
+        Self {
+            words_a,
+            words_b,
+            compute_pipeline,
+            board_bind_group_a,
+            board_bind_group_b,
+            render_pipeline,
+            render_bind_group_a,
+            render_bind_group_b,
+            active_words: Words::A,
+            generation: 0,
+            width,
+            height,
+            aspect: width as f32/height as f32,
+            vertices: GameOfLifeVertex::QUAD,
+            vertex_buffer,
+            resx,
+            resy,
+            area: SizeAndCenter::ZERO.into(),
+            mousex: 0.0,
+            mousey: 0.0,
+            toggle_pipeline,
+            toggle_cell_uniform,
+            toggle_bind_group_a,
+            toggle_bind_group_b,
+            rule,
+            rule_uniform,
+            topology,
+            topology_uniform,
+            size_uniform,
+            stats,
+        }
+    }
+
+    pub fn compute(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.compute_n(device, queue, 1);
+    }
+
+    /// Computes `n` generations, recording up to `MAX_GENERATIONS_PER_SUBMIT` of them per command buffer.
+    /// The stats of each command buffer's generations are read back after it
+    pub fn compute_n(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, n: u64) {
+        let mut remaining = n;
+        while remaining > 0 {
+            let generations = remaining.min(MAX_GENERATIONS_PER_SUBMIT);
+            remaining -= generations;
+            self.stats.start(queue, self.generation);
+
+            let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Packed Game of Life compute command encoder")
+            });
+
+            let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("Packed Game of Life compute pass")
+            });
+
+            for _ in 0..generations {
+                compute_pass.set_pipeline(&self.compute_pipeline);
+                match self.active_words {
+                    Words::A => {
+                        compute_pass.set_bind_group(0, &self.board_bind_group_a, &[]);
+                        self.active_words = Words::B;
+                    },
+                    Words::B => {
+                        compute_pass.set_bind_group(0, &self.board_bind_group_b, &[]);
+                        self.active_words = Words::A;
+                    },
+                }
+                compute_pass.dispatch_workgroups(dispatch_size(words_per_row(self.width)), dispatch_size(self.height), 1);
+                self.stats.next(&mut compute_pass);
+            }
+
+            drop(compute_pass);
+            queue.submit(std::iter::once(command_encoder.finish()));
+            self.generation += generations;
+            self.stats.read_back(device, queue, self.generation, generations);
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the generation counter, for when the board was replaced with a later generation
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    pub fn render(&mut self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView, loadop: wgpu::LoadOp<wgpu::Color>) {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Packed Game of Life render pass"),
+            color_attachments: &[
+                Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: loadop,
+                        store: true,
+                    }
+                })
+            ],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        match self.active_words {
+            Words::A => render_pass.set_bind_group(0, &self.render_bind_group_a, &[]),
+            Words::B => render_pass.set_bind_group(0, &self.render_bind_group_b, &[]),
+        }
+        render_pass.draw(0..4, 0..1);
+    }
+
+    pub fn construct(&self, queue: &wgpu::Queue) {
+        queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice::<GameOfLifeVertex, u8>(&self.vertices));
+        self.rule_uniform.set(&[self.rule.birth() as u32, self.rule.survival() as u32], queue);
+        self.topology_uniform.set(&[self.topology.x.shader_value(), self.topology.y.shader_value()], queue);
+        self.size_uniform.set(&[self.width, self.height], queue);
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Flips the cell at (x, y) in place
+    pub fn toggle(&mut self, x: i32, y: i32, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.toggle_cell_uniform.set(&[x, y], queue);
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Packed Game of Life toggle command encoder")
+        });
+
+        let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: Some("Packed Game of Life toggle pass")
+        });
+
+        compute_pass.set_pipeline(&self.toggle_pipeline);
+        match self.active_words {
+            Words::A => compute_pass.set_bind_group(0, &self.toggle_bind_group_a, &[]),
+            Words::B => compute_pass.set_bind_group(0, &self.toggle_bind_group_b, &[]),
+        }
+        compute_pass.dispatch_workgroups(1, 1, 1);
+
+        drop(compute_pass);
+        queue.submit(std::iter::once(command_encoder.finish()));
+        self.stats.read_back(device, queue, self.generation, 0);
+    }
+
+    fn active_buffer(&self) -> &wgpu::Buffer {
+        match self.active_words {
+            Words::A => &self.words_a,
+            Words::B => &self.words_b,
+        }
+    }
+
+    /// Copies the active board back to the CPU. Blocks until the GPU is done with it
+    pub fn read_board(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> CpuBoard {
+        let mut board = CpuBoard::new(self.width, self.height);
+        board.set_rule(self.rule);
+        board.set_topology(self.topology);
+        self.read_words(device, queue, |words| {
+            let words_per_row = words_per_row(self.width) as usize;
+            for y in 0..self.height {
+                for x in 0..self.width {
+                    let word = words[y as usize*words_per_row+x as usize/32];
+                    board.set(x, y, word >> (x%32) & 1 == 1);
+                }
+            }
+        });
+        board
+    }
+
+    /// Snapshots the active board a run of cells at a time from the words copied back, without making a
+    /// `CpuBoard` of a byte per cell
+    pub fn snapshot(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Snapshot {
+        let mut builder = SnapshotBuilder::new(self.width, self.height, self.generation);
+        self.read_words(device, queue, |words| {
+            for row in words.chunks(words_per_row(self.width) as usize) {
+                for (i, word) in row.iter().enumerate() {
+                    builder.push_bits(*word, (self.width-i as u32*32).min(32));
+                }
+            }
+        });
+        builder.finish()
+    }
+
+    /// Copies the active board back to the CPU and calls `read` with its words. Blocks until the GPU is done
+    /// with it
+    fn read_words(&self, device: &wgpu::Device, queue: &wgpu::Queue, read: impl FnOnce(&[u32])) {
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Packed Game of Life read buffer"),
+            size: buffer_size(self.width, self.height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Packed Game of Life read command encoder")
+        });
+        command_encoder.copy_buffer_to_buffer(self.active_buffer(), 0, &read_buffer, 0, buffer_size(self.width, self.height));
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let read_slice = read_buffer.slice(..);
+        read_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map Packed Game of Life read buffer"));
+        device.poll(wgpu::Maintain::Wait);
+
+        read(bytemuck::cast_slice(&read_slice.get_mapped_range()));
+        read_buffer.unmap();
+    }
+
+    /// Replaces the active board with the cells of `board`
+    pub fn write_board(&mut self, queue: &wgpu::Queue, board: &CpuBoard) {
+        assert!(board.width() == self.width && board.height() == self.height, "can't write a {}x{} board to a {}x{} Packed Game of Life", board.width(), board.height(), self.width, self.height);
+        let words_per_row = words_per_row(self.width) as usize;
+        let mut words = vec![0u32; words_per_row*self.height as usize];
+        for (x, y) in board.alive_cells() {
+            words[y as usize*words_per_row+x as usize/32] |= 1 << (x%32);
+        }
+        queue.write_buffer(self.active_buffer(), 0, bytemuck::cast_slice(&words));
+        self.stats.set_board(queue, self.generation, board);
+    }
+
+    /// Picks up the stats that have been copied back since this was last called, without waiting for the GPU
+    pub fn poll_stats(&mut self, device: &wgpu::Device) {
+        self.stats.poll(device);
+    }
+
+    /// Population of the board, and the births and deaths that led to it, as of the last stats picked up
+    pub fn stats(&self) -> GenerationStats {
+        self.stats.latest()
+    }
+
+    /// Stats of every generation picked up since this was last called, oldest first
+    pub fn take_stats(&mut self) -> Vec<GenerationStats> {
+        self.stats.take()
+    }
+
+    /// Whether there are stats still being copied back
+    pub fn stats_pending(&self) -> bool {
+        self.stats.is_pending()
+    }
+}
+
+impl Fill for PackedGameOfLife {
+    fn fill(&mut self, fill_target: SizeAndCenter) {
+        let points: Points = FillAspect {
+            placement_area: fill_target,
+            centerx: 0.0,
+            centery: 0.0,
+            resx: self.resx,
+            resy: self.resy,
+            aspect: self.aspect
+        }.into();
+        self.area = points;
+        self.vertices[0].position = [points.p2x, points.p1y];
+        self.vertices[1].position = [points.p2x, points.p2y];
+        self.vertices[2].position = [points.p1x, points.p1y];
+        self.vertices[3].position = [points.p1x, points.p2y];
+    }
+}
+
+impl SignalReciever<ResizedSignal, ()> for PackedGameOfLife {
+    fn take_signal(&mut self, signal: &mut ResizedSignal) {
+        self.resx = signal.resx;
+        self.resy = signal.resy;
+    }
+}
+
+impl SignalReciever<CursorMovedSignal, ()> for PackedGameOfLife {
+    fn take_signal(&mut self, signal: &mut CursorMovedSignal) {
+        self.mousex = signal.norm_posx;
+        self.mousey = signal.norm_posy;
+    }
+}
+
+impl SignalReciever<MouseLeftUpSignal, Option<(i32, i32)>> for PackedGameOfLife {
+    fn take_signal(&mut self, _signal: &mut MouseLeftUpSignal) -> Option<(i32, i32)> {
+        if self.mousex >= self.area.p1x && self.mousex < self.area.p2x && self.mousey <= self.area.p1y && self.mousey > self.area.p2y {
+            Some((
+                ((self.mousex-self.area.p1x)/(self.area.p2x-self.area.p1x)*self.width as f32) as i32,
+                ((self.mousey-self.area.p2y)/(self.area.p1y-self.area.p2y)*self.height as f32) as i32
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn words_per_row(width: u32) -> u32 {
+    width.div_ceil(32)
+}
+
+/// Size of one of the buffers holding a packed board, in bytes
+fn buffer_size(width: u32, height: u32) -> u64 {
+    words_per_row(width) as u64*height as u64*4
+}
@@ -0,0 +1,87 @@
+/// Colours of cells by how many generations they've been alive, or how long ago they died
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AgePalette {
+    pub name: &'static str,
+    /// Colour of cells just born, turning to `old` over `alive_span` generations
+    pub young: [f32; 4],
+    pub old: [f32; 4],
+    pub alive_span: f32,
+    /// Colour of cells that just died, fading to `background` over `dead_span` generations
+    pub dead: [f32; 4],
+    pub background: [f32; 4],
+    pub dead_span: f32,
+}
+
+impl AgePalette {
+    /// Fresh cells burn bright and leave glowing trails, settled ones cool down to a deep red
+    pub const EMBERS: AgePalette = AgePalette {
+        name: "Embers",
+        young: [1.0, 0.95, 0.6, 1.0],
+        old: [0.55, 0.05, 0.05, 1.0],
+        alive_span: 50.0,
+        dead: [0.9, 0.35, 0.05, 1.0],
+        background: [0.05, 0.02, 0.02, 1.0],
+        dead_span: 20.0,
+    };
+
+    /// New growth is light green, old growth dark, and dead cells leave grey marks on white
+    pub const MOSS: AgePalette = AgePalette {
+        name: "Moss",
+        young: [0.55, 0.9, 0.3, 1.0],
+        old: [0.05, 0.3, 0.1, 1.0],
+        alive_span: 100.0,
+        dead: [0.6, 0.6, 0.6, 1.0],
+        background: [1.0, 1.0, 1.0, 1.0],
+        dead_span: 10.0,
+    };
+
+    pub const PRESETS: [AgePalette; 2] = [AgePalette::EMBERS, AgePalette::MOSS];
+
+    /// Colour of a cell that's been alive, or dead, for `age` generations. Same as age_colour in game_of_life.wgsl
+    pub fn colour(&self, alive: bool, age: u32) -> [f32; 4] {
+        let (from, to, span) = if alive {
+            (self.young, self.old, self.alive_span)
+        } else {
+            (self.dead, self.background, self.dead_span)
+        };
+        let fraction = (age as f32/span).min(1.0);
+        [0, 1, 2, 3].map(|i| from[i]+(to[i]-from[i])*fraction)
+    }
+
+    pub(crate) fn uniform(palette: Option<&AgePalette>, generation: u64) -> AgePaletteUniformData {
+        match palette {
+            Some(palette) => AgePaletteUniformData {
+                young: palette.young,
+                old: palette.old,
+                dead: palette.dead,
+                background: palette.background,
+                alive_span: palette.alive_span.max(1.0),
+                dead_span: palette.dead_span.max(1.0),
+                generation: generation as u32,
+                enabled: 1,
+            },
+            None => AgePaletteUniformData {
+                generation: generation as u32,
+                ..bytemuck::Zeroable::zeroed()
+            },
+        }
+    }
+}
+
+/// Matches AgePalette in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AgePaletteUniformData {
+    young: [f32; 4],
+    old: [f32; 4],
+    dead: [f32; 4],
+    background: [f32; 4],
+    alive_span: f32,
+    dead_span: f32,
+    /// Low bits of the generation the board is at, to take the generations cells changed at from
+    generation: u32,
+    /// 0 to colour cells by where they are instead
+    enabled: u32,
+}
+
+bui::typed_uniform!(AgePaletteUniform, AgePaletteUniformData, "Age Palette Uniform");
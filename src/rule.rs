@@ -0,0 +1,144 @@
+use std::{fmt, str::FromStr};
+
+/// Outer-totalistic (Life-like) rule.
+///
+/// Bit `n` of `birth` is set when a dead cell with `n` live neighbours is born and bit `n` of
+/// `survival` is set when a live cell with `n` live neighbours survives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    pub fn new(birth: &[u32], survival: &[u32]) -> Self {
+        let mut rule = Rule {
+            birth: 0,
+            survival: 0,
+        };
+        for count in birth {
+            assert!(*count <= 8, "a cell can't have {} neighbours", count);
+            rule.birth |= 1 << count;
+        }
+        for count in survival {
+            assert!(*count <= 8, "a cell can't have {} neighbours", count);
+            rule.survival |= 1 << count;
+        }
+        rule
+    }
+
+    /// Parses B/S (`B36/S23`, `B3S23`) or S/B (`23/36`) notation
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let rule: String = rule.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+        if rule.is_empty() {
+            return Err("rule is empty".to_string())
+        }
+
+        let (birth, survival) = if rule.contains('B') || rule.contains('S') {
+            let mut birth = None;
+            let mut survival = None;
+            let mut parts = Vec::new();
+            let mut start = 0;
+            for (i, c) in rule.char_indices().skip(1) {
+                if c == 'B' || c == 'S' {
+                    parts.push(&rule[start..i]);
+                    start = i;
+                }
+            }
+            parts.push(&rule[start..]);
+            for part in parts {
+                let part = part.trim_end_matches('/');
+                if let Some(counts) = part.strip_prefix('B') {
+                    if birth.replace(counts).is_some() {
+                        return Err(format!("rule \"{}\" has more than one birth (B) part", rule))
+                    }
+                } else if let Some(counts) = part.strip_prefix('S') {
+                    if survival.replace(counts).is_some() {
+                        return Err(format!("rule \"{}\" has more than one survival (S) part", rule))
+                    }
+                } else {
+                    return Err(format!("failed to parse \"{}\" as B/S rule", rule))
+                }
+            }
+            match (birth, survival) {
+                (Some(birth), Some(survival)) => (birth, survival),
+                (None, _) => return Err(format!("rule \"{}\" is missing the birth (B) part", rule)),
+                (_, None) => return Err(format!("rule \"{}\" is missing the survival (S) part", rule)),
+            }
+        } else {
+            match rule.split_once('/') {
+                Some((survival, birth)) => (birth, survival),
+                None => return Err(format!("failed to parse \"{}\" as B/S or S/B rule", rule))
+            }
+        };
+
+        Ok(Rule {
+            birth: Self::parse_counts(birth)?,
+            survival: Self::parse_counts(survival)?,
+        })
+    }
+
+    fn parse_counts(counts: &str) -> Result<u16, String> {
+        let mut mask = 0;
+        for c in counts.chars() {
+            match c.to_digit(10) {
+                Some(count) if count <= 8 => mask |= 1 << count,
+                _ => return Err(format!("'{}' is not a neighbour count between 0 and 8", c))
+            }
+        }
+        Ok(mask)
+    }
+
+    pub fn birth(&self) -> u16 {
+        self.birth
+    }
+
+    pub fn survival(&self) -> u16 {
+        self.survival
+    }
+
+    pub fn next(&self, alive: bool, count: u32) -> bool {
+        if alive {
+            self.survival >> count & 1 == 1
+        } else {
+            self.birth >> count & 1 == 1
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        for count in 0..=8 {
+            if self.birth >> count & 1 == 1 {
+                write!(f, "{}", count)?;
+            }
+        }
+        write!(f, "/S")?;
+        for count in 0..=8 {
+            if self.survival >> count & 1 == 1 {
+                write!(f, "{}", count)?;
+            }
+        }
+        Ok(())
+    }
+}
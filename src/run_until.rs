@@ -0,0 +1,115 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::stats::GenerationStats;
+
+/// What running until stops on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopCondition {
+    Generation(u64),
+    PopulationAbove(u64),
+    PopulationBelow(u64),
+    /// No cells changing from one generation to the next
+    Static,
+    /// The board coming back to how it was at an earlier generation
+    Cycle,
+}
+
+impl StopCondition {
+    /// Parses `gen <n>` (or just `<n>`), `pop > <n>`, `pop < <n>`, `static` or `cycle`
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        let condition = condition.trim().to_lowercase();
+        let unknown = || format!("unknown condition \"{}\", expected gen <n>, pop > <n>, pop < <n>, static or cycle", condition);
+        let number = |number: &str| number.trim().parse::<u64>().map_err(|_| format!("failed to parse \"{}\" as a number", number.trim()));
+        match condition.as_str() {
+            "static" => return Ok(StopCondition::Static),
+            "cycle" => return Ok(StopCondition::Cycle),
+            _ => {},
+        }
+        if let Some(population) = condition.strip_prefix("population").or_else(|| condition.strip_prefix("pop")) {
+            let population = population.trim_start();
+            return match (population.strip_prefix('>'), population.strip_prefix('<')) {
+                (Some(threshold), _) => Ok(StopCondition::PopulationAbove(number(threshold)?)),
+                (_, Some(threshold)) => Ok(StopCondition::PopulationBelow(number(threshold)?)),
+                _ => Err(unknown()),
+            }
+        }
+        let generation = condition.strip_prefix("generation").or_else(|| condition.strip_prefix("gen")).unwrap_or(&condition);
+        if generation.trim().starts_with(|c: char| c.is_ascii_digit()) {
+            Ok(StopCondition::Generation(number(generation)?))
+        } else {
+            Err(unknown())
+        }
+    }
+}
+
+impl FromStr for StopCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for StopCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopCondition::Generation(generation) => write!(f, "gen {}", generation),
+            StopCondition::PopulationAbove(threshold) => write!(f, "pop > {}", threshold),
+            StopCondition::PopulationBelow(threshold) => write!(f, "pop < {}", threshold),
+            StopCondition::Static => write!(f, "static"),
+            StopCondition::Cycle => write!(f, "cycle"),
+        }
+    }
+}
+
+/// Checks the stats of each generation of a run against a `StopCondition`, remembering what it needs of the
+/// generations before. Only the population and hash the compute shaders count are looked at, so the board
+/// never has to be read back
+pub struct RunUntil {
+    condition: StopCondition,
+    /// Hash and generation of the last board checked
+    previous: Option<(u64, u64)>,
+    /// Generation each board was first checked at, by hash. Only kept for `StopCondition::Cycle`
+    seen: HashMap<u64, u64>,
+}
+
+impl RunUntil {
+    pub fn new(condition: StopCondition) -> Self {
+        Self {
+            condition,
+            previous: None,
+            seen: HashMap::new(),
+        }
+    }
+
+    pub fn condition(&self) -> StopCondition {
+        self.condition
+    }
+
+    /// Whether checking needs the stats of every generation, or only which generation it is
+    pub fn needs_stats(&self) -> bool {
+        !matches!(self.condition, StopCondition::Generation(_))
+    }
+
+    /// Checks the `stats` of a generation, returning why to stop if the condition holds. Generations have
+    /// to be checked one after the other for `Static` and `Cycle`
+    pub fn check(&mut self, stats: &GenerationStats) -> Option<String> {
+        let GenerationStats { generation, population, hash, .. } = *stats;
+        match self.condition {
+            StopCondition::Generation(target) => (generation >= target).then(|| format!("reached generation {}", target)),
+            StopCondition::PopulationAbove(threshold) => (population > threshold).then(|| format!("population {} is above {}", population, threshold)),
+            StopCondition::PopulationBelow(threshold) => (population < threshold).then(|| format!("population {} is below {}", population, threshold)),
+            StopCondition::Static => {
+                let previous = self.previous.replace((hash, generation));
+                match previous {
+                    Some((previous_hash, previous_generation)) if previous_hash == hash => Some(format!("the board is static since generation {}", previous_generation)),
+                    _ => None,
+                }
+            },
+            StopCondition::Cycle => {
+                self.seen.insert(hash, generation)
+                    .map(|start| format!("the board repeats every {} generations since generation {}", generation-start, start))
+            },
+        }
+    }
+}
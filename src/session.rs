@@ -0,0 +1,123 @@
+use std::{fmt::Write, path::PathBuf};
+
+use crate::{history::Snapshot, rule::Rule, topology::Topology};
+
+/// First line of every session file
+const HEADER: &str = "wgpu_game_of_life session 1";
+
+/// A named board to come back to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bookmark {
+    pub name: String,
+    pub snapshot: Snapshot,
+}
+
+/// File the bookmarks of a game are saved to, so they're still there next time the same board is set up.
+///
+/// It's text, starting with `HEADER` and the board's settings, then a line for each bookmark of its
+/// generation, its snapshot's runs in hex and its name:
+///
+/// ```text
+/// wgpu_game_of_life session 1
+/// size 30 30
+/// rule B3/S23
+/// edges plane
+/// bookmark 1103 8b0701... before collision
+/// ```
+pub struct Session {
+    path: PathBuf,
+    dimensions: Option<(u32, u32)>,
+    rule: Rule,
+    topology: Topology,
+}
+
+impl Session {
+    /// Session at `path` for a board of `dimensions`, or an unbounded board if there are none
+    pub fn new(path: PathBuf, dimensions: Option<(u32, u32)>, rule: Rule, topology: Topology) -> Self {
+        Self {
+            path,
+            dimensions,
+            rule,
+            topology,
+        }
+    }
+
+    fn settings(&self) -> [String; 3] {
+        [
+            match self.dimensions {
+                Some((width, height)) => format!("size {} {}", width, height),
+                None => "size inf inf".to_string(),
+            },
+            format!("rule {}", self.rule),
+            format!("edges {}", self.topology),
+        ]
+    }
+
+    /// Reads the bookmarks saved in the file, or none if there is no file yet. Fails if the file is
+    /// for a different board
+    pub fn load(&self) -> Result<Vec<Bookmark>, String> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(format!("failed to read session {}: {}", self.path.display(), err)),
+        };
+        self.parse(&text).map_err(|err| format!("failed to load session {}: {}", self.path.display(), err))
+    }
+
+    fn parse(&self, text: &str) -> Result<Vec<Bookmark>, String> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err("it isn't a session file".to_string())
+        }
+        for setting in self.settings() {
+            match lines.next() {
+                Some(line) if line == setting => {},
+                Some(line) => return Err(format!("it's for a board with {}, not {}", line, setting)),
+                None => return Err("it ends early".to_string()),
+            }
+        }
+
+        let mut bookmarks = Vec::new();
+        for line in lines.filter(|line| !line.trim().is_empty()) {
+            let mut fields = line.splitn(4, ' ');
+            let (generation, runs, name) = match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                (Some("bookmark"), Some(generation), Some(runs), name) => (generation, runs, name.unwrap_or("")),
+                _ => return Err(format!("\"{}\" isn't a bookmark", line)),
+            };
+            let generation = generation.parse().map_err(|_| format!("\"{}\" isn't a generation", generation))?;
+            let runs = parse_hex(runs)?;
+            let (width, height) = self.dimensions.ok_or("unbounded boards can't have bookmarks")?;
+            bookmarks.push(Bookmark {
+                name: name.to_string(),
+                snapshot: Snapshot::from_runs(width, height, generation, runs)?,
+            });
+        }
+        Ok(bookmarks)
+    }
+
+    /// Writes `bookmarks` to the file, replacing what was there
+    pub fn save(&self, bookmarks: &[Bookmark]) -> Result<(), String> {
+        let mut text = format!("{}\n", HEADER);
+        for setting in self.settings() {
+            text.push_str(&setting);
+            text.push('\n');
+        }
+        for bookmark in bookmarks {
+            write!(text, "bookmark {} ", bookmark.snapshot.generation()).unwrap();
+            for byte in bookmark.snapshot.runs() {
+                write!(text, "{:02x}", byte).unwrap();
+            }
+            writeln!(text, " {}", bookmark.name.replace('\n', " ")).unwrap();
+        }
+        std::fs::write(&self.path, text).map_err(|err| format!("failed to save session {}: {}", self.path.display(), err))
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    hex.as_bytes().chunks(2)
+        .map(|pair| match std::str::from_utf8(pair) {
+            Ok(pair) if pair.len() == 2 => u8::from_str_radix(pair, 16).ok(),
+            _ => None,
+        }.ok_or_else(|| format!("\"{}\" isn't hex", hex)))
+        .collect()
+}
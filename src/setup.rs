@@ -1,15 +1,28 @@
-use std::sync::{Arc, Mutex};
+use std::{path::PathBuf, sync::{Arc, Mutex}};
 
 use bui::{ttf::CachedFace, rect::{SizeAndCenter, FillAspect, Points}, text::TextRenderer, renderer::Renderer};
 use bui_basic::{text::Text, text_input::TextInput, button::{Button, ClickedCallback}, containers::{Init, Fill}, signal::{SignalReciever, ResizedSignal, RedrawCallback, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal, CharacterInputSignal}, construct::{LineTarget, StandardConstructTarget, Construct}};
 use winit::window::Window;
 
+use crate::{rule::Rule, topology::Topology, game::Storage};
+
+/// MiB of memory the undo history takes up at most, unless changed
+const DEFAULT_HISTORY_MIB: usize = 256;
+
 pub struct Setup {
     title: Text,
     sizex_label: Text,
     sizex_input: TextInput,
     sizey_label: Text,
     sizey_input: TextInput,
+    rule_label: Text,
+    rule_input: TextInput,
+    topology_label: Text,
+    topology_input: TextInput,
+    history_label: Text,
+    history_input: TextInput,
+    session_label: Text,
+    session_input: TextInput,
     go_button: Button<Text>,
     error: Text,
     setup_area: SizeAndCenter,
@@ -29,6 +42,14 @@ impl Setup {
             sizex_input: TextInput::new_with_res(initial_size.clone(), face.clone(), resx, resy),
             sizey_label: Text::new_with_res("Size Y:", face.clone(), resx, resy),
             sizey_input: TextInput::new_with_res(initial_size, face.clone(), resx, resy),
+            rule_label: Text::new_with_res("Rule:", face.clone(), resx, resy),
+            rule_input: TextInput::new_with_res(Rule::CONWAY.to_string(), face.clone(), resx, resy),
+            topology_label: Text::new_with_res("Edges:", face.clone(), resx, resy),
+            topology_input: TextInput::new_with_res(Topology::PLANE.to_string(), face.clone(), resx, resy),
+            history_label: Text::new_with_res("History MiB:", face.clone(), resx, resy),
+            history_input: TextInput::new_with_res(DEFAULT_HISTORY_MIB.to_string(), face.clone(), resx, resy),
+            session_label: Text::new_with_res("Session file:", face.clone(), resx, resy),
+            session_input: TextInput::new_with_res(String::new(), face.clone(), resx, resy),
             go_button: Button::new(Text::new_with_res("Go", face.clone(), resx, resy), SizeAndCenter::ZERO),
             error: Text::new_with_res("", face, resx, resy),
             setup_area: SizeAndCenter::ZERO,
@@ -55,6 +76,14 @@ impl Setup {
         text_lines.append(self.sizex_input.construct());
         text_lines.append(self.sizey_label.construct());
         text_lines.append(self.sizey_input.construct());
+        text_lines.append(self.rule_label.construct());
+        text_lines.append(self.rule_input.construct());
+        text_lines.append(self.topology_label.construct());
+        text_lines.append(self.topology_input.construct());
+        text_lines.append(self.history_label.construct());
+        text_lines.append(self.history_input.construct());
+        text_lines.append(self.session_label.construct());
+        text_lines.append(self.session_input.construct());
         text_lines.append(self.go_button.construct());
         text_lines.append(self.error.construct());
         text_renderer.set_line_buffer(renderer.queue(), text_lines.0.as_slice());
@@ -90,34 +119,60 @@ impl Setup {
         }
     }
 
-    pub fn get_dimensions(&self) -> Result<(u32, u32), &str> {
+    /// Width and height of the board, or None if both are "inf" for an unbounded one
+    pub fn get_dimensions(&self) -> Result<Option<(u32, u32)>, String> {
+        match (self.sizex_input.get_text().trim(), self.sizey_input.get_text().trim()) {
+            ("inf", "inf") => return Ok(None),
+            ("inf", _) | (_, "inf") => return Err("width and height must both be inf for an unbounded board".to_string()),
+            _ => {}
+        }
+
         let width = match self.sizex_input.get_text().parse() {
             Ok(width) => if width > 0 {
-                if width > 8192 {
-                    return Err("width exceeds the maximum of 8192")
-                } else {
-                    width
-                }
+                width
             } else {
-                return Err("width must be greater than 0")
+                return Err("width must be greater than 0".to_string())
             },
-            Err(_) => return Err("failed to parse width as whole number")
+            Err(_) => return Err("failed to parse width as whole number or inf".to_string())
         };
 
         let height = match self.sizey_input.get_text().parse() {
             Ok(height) => if height > 0 {
-                if height > 8192 {
-                    return Err("height exceeds the maximum of 8192")
-                } else {
-                    height
-                }
+                height
             } else {
-                return Err("height must be greater than 0")
+                return Err("height must be greater than 0".to_string())
             },
-            Err(_) => return Err("failed to parse height as whole number")
+            Err(_) => return Err("failed to parse height as whole number or inf".to_string())
         };
 
-        Ok((width, height))
+        Storage::check_size(width, height, &self.renderer.as_ref().unwrap().device().limits())?;
+
+        Ok(Some((width, height)))
+    }
+
+    pub fn get_rule(&self) -> Result<Rule, String> {
+        Rule::parse(self.rule_input.get_text())
+    }
+
+    pub fn get_topology(&self) -> Result<Topology, String> {
+        Topology::parse(self.topology_input.get_text())
+    }
+
+    /// Bytes of memory the undo history can take up. 0 turns it off
+    pub fn get_history_budget(&self) -> Result<usize, String> {
+        let mib: usize = match self.history_input.get_text().trim().parse() {
+            Ok(mib) => mib,
+            Err(_) => return Err("failed to parse history size as a whole number of MiB".to_string())
+        };
+        mib.checked_mul(1 << 20).ok_or_else(|| format!("{} MiB of history doesn't fit in memory", mib))
+    }
+
+    /// File to save bookmarks to, if one was given
+    pub fn get_session_path(&self) -> Option<PathBuf> {
+        match self.session_input.get_text().trim() {
+            "" => None,
+            path => Some(PathBuf::from(path)),
+        }
     }
 
     pub fn set_error(&mut self, err: String) {
@@ -178,16 +233,72 @@ impl Fill for Setup {
             p2y: 0.5
         }.into()));
 
+        self.rule_label.fill(self.setup_area.get_relative(Points {
+            p1x: -0.9,
+            p1y: 0.45,
+            p2x: -0.025,
+            p2y: 0.35
+        }.into()));
+
+        self.rule_input.fill(self.setup_area.get_relative(Points {
+            p1x: 0.025,
+            p1y: 0.45,
+            p2x: 0.9,
+            p2y: 0.35
+        }.into()));
+
+        self.topology_label.fill(self.setup_area.get_relative(Points {
+            p1x: -0.9,
+            p1y: 0.3,
+            p2x: -0.025,
+            p2y: 0.2
+        }.into()));
+
+        self.topology_input.fill(self.setup_area.get_relative(Points {
+            p1x: 0.025,
+            p1y: 0.3,
+            p2x: 0.9,
+            p2y: 0.2
+        }.into()));
+
+        self.history_label.fill(self.setup_area.get_relative(Points {
+            p1x: -0.9,
+            p1y: 0.15,
+            p2x: -0.025,
+            p2y: 0.05
+        }.into()));
+
+        self.history_input.fill(self.setup_area.get_relative(Points {
+            p1x: 0.025,
+            p1y: 0.15,
+            p2x: 0.9,
+            p2y: 0.05
+        }.into()));
+
+        self.session_label.fill(self.setup_area.get_relative(Points {
+            p1x: -0.9,
+            p1y: 0.0,
+            p2x: -0.025,
+            p2y: -0.1
+        }.into()));
+
+        self.session_input.fill(self.setup_area.get_relative(Points {
+            p1x: 0.025,
+            p1y: 0.0,
+            p2x: 0.9,
+            p2y: -0.1
+        }.into()));
+
         self.go_button.fill(self.setup_area.get_relative(Points {
             p1x: -0.6,
-            p1y: 0.45,
+            p1y: -0.15,
             p2x: 0.6,
-            p2y: 0.35
+            p2y: -0.25
         }.into()));
 
         self.error.fill(self.setup_area.get_relative(Points {
             p1x: -0.9,
-            p1y: 0.4,
+            p1y: -0.2,
             p2x: 0.9,
             p2y: -0.9,
         }.into()));
@@ -209,6 +320,14 @@ impl SignalReciever<ResizedSignal, RedrawCallback> for Setup {
         self.sizex_input.take_signal(signal);
         self.sizey_label.take_signal(signal);
         self.sizey_input.take_signal(signal);
+        self.rule_label.take_signal(signal);
+        self.rule_input.take_signal(signal);
+        self.topology_label.take_signal(signal);
+        self.topology_input.take_signal(signal);
+        self.history_label.take_signal(signal);
+        self.history_input.take_signal(signal);
+        self.session_label.take_signal(signal);
+        self.session_input.take_signal(signal);
         self.go_button.take_signal(signal);
         self.error.take_signal(signal);
 
@@ -223,9 +342,17 @@ impl SignalReciever<CursorMovedSignal, RedrawCallback> for Setup {
         let mut reconstruct_signal = self.title.take_signal(signal).0;
         reconstruct_signal.or(self.sizex_label.take_signal(signal).0);
         reconstruct_signal.or(self.sizey_label.take_signal(signal).0);
+        reconstruct_signal.or(self.rule_label.take_signal(signal).0);
+        reconstruct_signal.or(self.topology_label.take_signal(signal).0);
+        reconstruct_signal.or(self.history_label.take_signal(signal).0);
+        reconstruct_signal.or(self.session_label.take_signal(signal).0);
         reconstruct_signal.or(self.error.take_signal(signal).0);
         self.sizex_input.take_signal(signal);
         self.sizey_input.take_signal(signal);
+        self.rule_input.take_signal(signal);
+        self.topology_input.take_signal(signal);
+        self.history_input.take_signal(signal);
+        self.session_input.take_signal(signal);
         self.go_button.take_signal(signal);
 
         if reconstruct_signal.get_reconstruct() {
@@ -242,6 +369,10 @@ impl SignalReciever<MouseLeftDownSignal, RedrawCallback> for Setup {
         let mut reconstruct_signal = self.title.take_signal(signal).0;
         reconstruct_signal.or(self.sizex_label.take_signal(signal).0);
         reconstruct_signal.or(self.sizey_label.take_signal(signal).0);
+        reconstruct_signal.or(self.rule_label.take_signal(signal).0);
+        reconstruct_signal.or(self.topology_label.take_signal(signal).0);
+        reconstruct_signal.or(self.history_label.take_signal(signal).0);
+        reconstruct_signal.or(self.session_label.take_signal(signal).0);
         reconstruct_signal.or(self.error.take_signal(signal).0);
         self.go_button.take_signal(signal);
 
@@ -261,6 +392,14 @@ impl SignalReciever<MouseLeftUpSignal, SetupLeftUp> for Setup {
         self.sizex_input.take_signal(signal);
         self.sizey_label.take_signal(signal);
         self.sizey_input.take_signal(signal);
+        self.rule_label.take_signal(signal);
+        self.rule_input.take_signal(signal);
+        self.topology_label.take_signal(signal);
+        self.topology_input.take_signal(signal);
+        self.history_label.take_signal(signal);
+        self.history_input.take_signal(signal);
+        self.session_label.take_signal(signal);
+        self.session_input.take_signal(signal);
         self.error.take_signal(signal);
         if self.go_button.take_signal(signal) == ClickedCallback::Clicked {
             SetupLeftUp::Go
@@ -279,6 +418,10 @@ impl SignalReciever<CharacterInputSignal, RedrawCallback> for Setup {
     fn take_signal(&mut self, signal: &mut CharacterInputSignal) -> RedrawCallback {
         let mut reconstruct_signal = self.sizex_input.take_signal(signal);
         reconstruct_signal.or(self.sizey_input.take_signal(signal));
+        reconstruct_signal.or(self.rule_input.take_signal(signal));
+        reconstruct_signal.or(self.topology_input.take_signal(signal));
+        reconstruct_signal.or(self.history_input.take_signal(signal));
+        reconstruct_signal.or(self.session_input.take_signal(signal));
 
         if reconstruct_signal.get_reconstruct() {
             self.construct();
@@ -0,0 +1,328 @@
+use std::fmt;
+
+use crate::{cpu_board::CpuBoard, stats::mix_hash};
+
+/// Steps density is counted in, so it can be compared exactly against the top 24 bits of a cell's hash
+pub const DENSITY_STEPS: u32 = 1 << 24;
+
+/// Rectangle of cells with its top left corner at `x`, `y`. Corners can be anywhere an unbounded board's cells
+/// can, including left of and above the origin
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: i64,
+    pub y: i64,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    /// Corners of the part of the region on a `width` by `height` board, the second one past the region
+    pub fn clip(&self, width: u32, height: u32) -> ([u32; 2], [u32; 2]) {
+        let clamp = |coordinate: i64, size: u32| coordinate.clamp(0, size as i64) as u32;
+        let min = [clamp(self.x, width), clamp(self.y, height)];
+        let max = [clamp(self.x+self.width as i64, width), clamp(self.y+self.height as i64, height)];
+        (min, max)
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{} {}x{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+/// Cells of the board a soup fills
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoupArea {
+    Board,
+    Region(Region),
+    /// A box of `width` by `height` cells in the middle of the board
+    Centre {
+        width: u32,
+        height: u32,
+    },
+}
+
+/// Symmetries a soup can be made with, named like in Life search tools. Each of the symmetry's transforms
+/// maps the soup's box onto itself
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// No symmetry
+    C1,
+    /// Rotating by 180 degrees
+    C2,
+    /// Rotating by 90 degrees, in a square box
+    C4,
+    /// Mirroring x, so the right half is the left half flipped
+    D2X,
+    /// Mirroring y, so the bottom half is the top half flipped
+    D2Y,
+    /// Mirroring both x and y
+    D4,
+    /// Rotating by 90 degrees and mirroring, in a square box
+    D8,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 7] = [Symmetry::C1, Symmetry::C2, Symmetry::C4, Symmetry::D2X, Symmetry::D2Y, Symmetry::D4, Symmetry::D8];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Symmetry::C1 => "C1",
+            Symmetry::C2 => "C2",
+            Symmetry::C4 => "C4",
+            Symmetry::D2X => "D2x",
+            Symmetry::D2Y => "D2y",
+            Symmetry::D4 => "D4",
+            Symmetry::D8 => "D8",
+        }
+    }
+
+    /// Bit i is set for each transform i of `transform` the symmetry is made of
+    pub fn transforms(&self) -> u32 {
+        match self {
+            Symmetry::C1 => 0b1,
+            Symmetry::C2 => 0b101,
+            Symmetry::C4 => 0b1111,
+            Symmetry::D2X => 0b10001,
+            Symmetry::D2Y => 0b100001,
+            Symmetry::D4 => 0b110101,
+            Symmetry::D8 => 0b11111111,
+        }
+    }
+
+    /// Whether the symmetry turns the box sideways, so it only works in a square one
+    pub fn needs_square(&self) -> bool {
+        matches!(self, Symmetry::C4 | Symmetry::D8)
+    }
+}
+
+impl fmt::Display for Symmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Where the `i`th transform of a box `size` big takes `cell`: nothing, rotating by 90, 180 or 270 degrees,
+/// mirroring x or y, or mirroring along either diagonal. Same as soup_transform in game_of_life.wgsl
+pub fn transform(i: u32, cell: [u32; 2], size: [u32; 2]) -> [u32; 2] {
+    let far = [size[0]-1, size[1]-1];
+    match i {
+        1 => [far[0]-cell[1], cell[0]],
+        2 => [far[0]-cell[0], far[1]-cell[1]],
+        3 => [cell[1], far[1]-cell[0]],
+        4 => [far[0]-cell[0], cell[1]],
+        5 => [cell[0], far[1]-cell[1]],
+        6 => [cell[1], cell[0]],
+        7 => [far[0]-cell[1], far[1]-cell[0]],
+        _ => cell,
+    }
+}
+
+/// Random cells filling a board or part of it. Whether each cell is alive only depends on the seed and
+/// where it is in the soup, so the same soup comes out the same on the CPU and every GPU backend
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Soup {
+    /// Percentage of cells that are alive
+    pub density: f64,
+    pub seed: u64,
+    pub symmetry: Symmetry,
+    pub area: SoupArea,
+}
+
+impl Soup {
+    /// Parses `<density>%`, optionally followed by `seed <n>`, a symmetry and `in <x>,<y> <width>x<height>`,
+    /// or `in <width>x<height>` for a box in the middle of the board. Without a seed, `seed` is used
+    pub fn parse(soup: &str, seed: u64) -> Result<Self, String> {
+        let soup = soup.trim().to_lowercase();
+        let (soup, area) = match soup.split_once(" in ") {
+            Some((soup, area)) => (soup.trim(), Self::parse_area(area.trim())?),
+            None => (soup.as_str(), SoupArea::Board),
+        };
+        let mut symmetry = Symmetry::C1;
+        let mut words = Vec::new();
+        for word in soup.split_whitespace() {
+            match Symmetry::ALL.into_iter().find(|symmetry| symmetry.name().to_lowercase() == word) {
+                Some(found) => symmetry = found,
+                None => words.push(word),
+            }
+        }
+        let soup = words.join(" ");
+        let (density, seed) = match soup.split_once("seed") {
+            Some((density, seed)) => (density.trim(), parse_seed(seed.trim())?),
+            None => (soup.as_str(), seed),
+        };
+        let density = density.strip_suffix('%').unwrap_or(density).trim();
+        let density = density.parse::<f64>().map_err(|_| format!("failed to parse \"{}\" as a density", density))?;
+        if !(0.0..=100.0).contains(&density) {
+            return Err(format!("density {}% isn't between 0% and 100%", density))
+        }
+        Ok(Self {
+            density,
+            seed,
+            symmetry,
+            area,
+        })
+    }
+
+    fn parse_area(area: &str) -> Result<SoupArea, String> {
+        let invalid = || format!("failed to parse \"{}\" as a region, expected <x>,<y> <width>x<height> or <width>x<height>", area);
+        let number = |number: &str| number.trim().parse::<u32>().map_err(|_| invalid());
+        let coordinate = |coordinate: &str| coordinate.trim().parse::<i64>().map_err(|_| invalid());
+        let (corner, size) = match area.split_once(' ') {
+            Some((corner, size)) => (Some(corner), size),
+            None => (None, area),
+        };
+        let (width, height) = size.trim().split_once('x').ok_or_else(invalid)?;
+        let (width, height) = (number(width)?, number(height)?);
+        let (x, y) = match corner {
+            Some(corner) => corner.split_once(',').ok_or_else(invalid)?,
+            None => return Ok(SoupArea::Centre {
+                width,
+                height,
+            }),
+        };
+        let region = Region {
+            x: coordinate(x)?,
+            y: coordinate(y)?,
+            width,
+            height,
+        };
+        if region.x.checked_add(region.width as i64).is_none() || region.y.checked_add(region.height as i64).is_none() {
+            return Err(format!("region \"{}\" goes past the largest coordinate", region))
+        }
+        Ok(SoupArea::Region(region))
+    }
+
+    /// Cells are alive where the top 24 bits of their hash are below this
+    pub fn threshold(&self) -> u32 {
+        (self.density/100.0*DENSITY_STEPS as f64).round() as u32
+    }
+
+    /// Checks that the soup can be made on a `width` by `height` board. Symmetric soups can't be cut off by
+    /// the edge of the board, and some of them need a square box
+    pub fn check(&self, width: u32, height: u32) -> Result<(), String> {
+        let size = match self.area {
+            SoupArea::Board => [width, height],
+            SoupArea::Region(Region { width, height, .. }) | SoupArea::Centre { width, height } => [width, height],
+        };
+        let (min, max) = self.clip(width, height);
+        if self.symmetry != Symmetry::C1 && [max[0]-min[0], max[1]-min[1]] != size {
+            Err(format!("a {} soup can't go past the edge of the board", self.symmetry))
+        } else {
+            self.check_box(size)
+        }
+    }
+
+    /// Checks that the soup's symmetry works in a box `size` big
+    pub fn check_box(&self, size: [u32; 2]) -> Result<(), String> {
+        if self.symmetry.needs_square() && size[0] != size[1] {
+            Err(format!("a {} soup needs a square box, not {}x{}", self.symmetry, size[0], size[1]))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether the cell at `x`, `y` is alive in the soup filling the box from `min` to just before `max`.
+    /// Same as fill_soup in game_of_life.wgsl
+    pub fn alive(&self, x: u32, y: u32, min: [u32; 2], max: [u32; 2]) -> bool {
+        self.alive_in_box([x-min[0], y-min[1]], [max[0]-min[0], max[1]-min[1]], min)
+    }
+
+    /// Whether the cell `x`, `y` from the corner of `region` is alive in the soup filling all of it, wherever it
+    /// is on an unbounded board. Cells hash their coordinates wrapped to 32 bits, so regions at or right of
+    /// and below the origin get the same cells as on a bounded board
+    pub fn alive_in_region(&self, region: &Region, x: u32, y: u32) -> bool {
+        self.alive_in_box([x, y], [region.width, region.height], [region.x as u32, region.y as u32])
+    }
+
+    fn alive_in_box(&self, cell: [u32; 2], size: [u32; 2], corner: [u32; 2]) -> bool {
+        // Every cell the symmetry maps onto each other takes the hash of the first of them
+        let first = (0..8)
+            .filter(|i| self.symmetry.transforms() >> i & 1 == 1)
+            .map(|i| transform(i, cell, size))
+            .min_by_key(|cell| (cell[1], cell[0]))
+            .unwrap();
+        soup_hash(self.seed, corner[0].wrapping_add(first[0]), corner[1].wrapping_add(first[1])) >> 8 < self.threshold()
+    }
+
+    /// Replaces the cells of `board` in the soup's area with the soup's. Needs `check` to pass for the board
+    pub fn fill(&self, board: &mut CpuBoard) {
+        let (min, max) = self.clip(board.width(), board.height());
+        for y in min[1]..max[1] {
+            for x in min[0]..max[0] {
+                board.set(x, y, self.alive(x, y, min, max));
+            }
+        }
+    }
+
+    /// Corners of the cells the soup fills on a `width` by `height` board, the second one past them
+    pub fn clip(&self, width: u32, height: u32) -> ([u32; 2], [u32; 2]) {
+        match self.area {
+            SoupArea::Board => ([0, 0], [width, height]),
+            SoupArea::Region(region) => region.clip(width, height),
+            SoupArea::Centre { width: box_width, height: box_height } => Region {
+                x: (width.saturating_sub(box_width)/2) as i64,
+                y: (height.saturating_sub(box_height)/2) as i64,
+                width: box_width,
+                height: box_height,
+            }.clip(width, height),
+        }
+    }
+
+    pub(crate) fn uniform(&self, width: u32, height: u32) -> SoupUniformData {
+        let (min, max) = self.clip(width, height);
+        SoupUniformData {
+            seed_low: self.seed as u32,
+            seed_high: (self.seed >> 32) as u32,
+            threshold: self.threshold(),
+            transforms: self.symmetry.transforms(),
+            min,
+            max,
+        }
+    }
+}
+
+impl fmt::Display for Soup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}% seed {}", self.density, self.seed)?;
+        if self.symmetry != Symmetry::C1 {
+            write!(f, " {}", self.symmetry)?;
+        }
+        match self.area {
+            SoupArea::Board => Ok(()),
+            SoupArea::Region(region) => write!(f, " in {}", region),
+            SoupArea::Centre { width, height } => write!(f, " in {}x{}", width, height),
+        }
+    }
+}
+
+/// Parses a seed in decimal, or hex starting with 0x
+fn parse_seed(seed: &str) -> Result<u64, String> {
+    match seed.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => seed.parse::<u64>(),
+    }.map_err(|_| format!("failed to parse \"{}\" as a seed", seed))
+}
+
+/// Hash deciding whether the cell at `x`, `y` is alive in a soup with `seed`. Keep in sync with soup_hash in
+/// game_of_life.wgsl
+pub fn soup_hash(seed: u64, x: u32, y: u32) -> u32 {
+    mix_hash(x ^ mix_hash(y ^ mix_hash(seed as u32 ^ mix_hash((seed >> 32) as u32))))
+}
+
+/// Matches Soup in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SoupUniformData {
+    seed_low: u32,
+    seed_high: u32,
+    threshold: u32,
+    /// `Symmetry::transforms` of the soup's symmetry
+    transforms: u32,
+    /// Corners of the cells filled, the second one past them
+    min: [u32; 2],
+    max: [u32; 2],
+}
+
+bui::typed_uniform!(SoupUniform, SoupUniformData, "Soup Uniform");
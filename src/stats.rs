@@ -0,0 +1,284 @@
+use std::{collections::VecDeque, sync::{Arc, atomic::{AtomicBool, Ordering}}};
+
+use crate::{cpu_board::CpuBoard, game::MAX_GENERATIONS_PER_SUBMIT};
+
+/// Binding of the stats in the board and toggle bind groups. Keep in sync with stats in game_of_life.wgsl
+pub(crate) const STATS_BINDING: u32 = 5;
+/// Bytes of `Stats` in game_of_life.wgsl before its generations
+const HEADER_SIZE: u64 = 40;
+/// Offset of the generation in `Stats`
+const GENERATION_OFFSET: u64 = 4;
+/// Offset of the population, followed by the hash, in `Stats`
+const POPULATION_OFFSET: u64 = 24;
+/// Bytes of each of the generations, matching `GenerationStats` in game_of_life.wgsl
+const GENERATION_SIZE: u64 = 24;
+const BUFFER_SIZE: u64 = HEADER_SIZE+MAX_GENERATIONS_PER_SUBMIT*GENERATION_SIZE;
+/// Most readbacks waiting on the GPU at once. Generations computed while there are this many aren't read back
+const MAX_PENDING_READBACKS: usize = 16;
+/// Most generations read back and not taken yet, past which the oldest are dropped, leaving a gap like skipped readbacks
+const MAX_UNTAKEN_GENERATIONS: usize = 1 << 16;
+
+/// Live cells of a generation, how many cells were born and died to get to it and a hash of the board.
+/// Boards with the same cells have the same hash
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerationStats {
+    pub generation: u64,
+    pub population: u64,
+    pub births: u64,
+    pub deaths: u64,
+    pub hash: u64,
+}
+
+impl GenerationStats {
+    /// Stats of `board` at `generation`, as the compute shaders would count them. Nothing was born or died
+    pub fn of_board(board: &CpuBoard, generation: u64) -> Self {
+        Self {
+            generation,
+            population: board.population() as u64,
+            births: 0,
+            deaths: 0,
+            hash: board_hash(board),
+        }
+    }
+}
+
+pub(crate) fn mix_hash(value: u32) -> u32 {
+    let mut x = value;
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846ca68b);
+    x ^= x >> 16;
+    x
+}
+
+/// What a live cell adds to the hash of a board. Keep in sync with cell_hash in game_of_life.wgsl
+fn cell_hash(x: u32, y: u32) -> u64 {
+    let low = mix_hash(x ^ mix_hash(y));
+    low as u64 | (mix_hash(low ^ 0x9e3779b9) as u64) << 32
+}
+
+/// Hash of `board` as the compute shaders keep it, the sum of each live cell's hash in each half.
+/// Only the cells count, not the rule or topology
+pub fn board_hash(board: &CpuBoard) -> u64 {
+    let (low, high) = board.alive_cells().into_iter()
+        .map(|(x, y)| cell_hash(x, y))
+        .fold((0u32, 0u32), |(low, high), hash| (low.wrapping_add(hash as u32), high.wrapping_add((hash >> 32) as u32)));
+    low as u64 | (high as u64) << 32
+}
+
+struct Readback {
+    buffer: wgpu::Buffer,
+    /// Generation of the board once the commands read back were done
+    generation: u64,
+    /// Generations computed by those commands, the last of which is `generation`
+    generations: u64,
+    /// Boards written after the commands, before the next readback
+    written: Vec<GenerationStats>,
+    mapped: Arc<AtomicBool>,
+}
+
+/// Population, births, deaths and hash the compute shaders count for every generation, copied back to the
+/// CPU without waiting on the GPU. `poll` picks up whatever has been copied since it was last called
+pub(crate) struct Stats {
+    buffer: wgpu::Buffer,
+    next_pipeline: wgpu::ComputePipeline,
+    pending: VecDeque<Readback>,
+    /// Staging buffers that are done with, to reuse
+    free: Vec<wgpu::Buffer>,
+    latest: GenerationStats,
+    untaken: VecDeque<GenerationStats>,
+}
+
+impl Stats {
+    /// Stats for a board computed with `shader`, whose board bind groups have `Stats::layout_entry` in `board_bind_group_layout`
+    pub(crate) fn new(device: &wgpu::Device, shader: &wgpu::ShaderModule, board_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Stats buffer"),
+            size: BUFFER_SIZE,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let next_pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("Next stats pipeline"),
+            layout: Some(&device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Next stats pipeline layout"),
+                bind_group_layouts: &[
+                    board_bind_group_layout
+                ],
+                push_constant_ranges: &[],
+            })),
+            module: shader,
+            entry_point: "next_stats"
+        });
+
+        Self {
+            buffer,
+            next_pipeline,
+            pending: VecDeque::new(),
+            free: Vec::new(),
+            latest: GenerationStats::default(),
+            untaken: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn layout_entry() -> wgpu::BindGroupLayoutEntry {
+        wgpu::BindGroupLayoutEntry {
+            binding: STATS_BINDING,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }
+    }
+
+    pub(crate) fn bind_group_entry(&self) -> wgpu::BindGroupEntry<'_> {
+        wgpu::BindGroupEntry {
+            binding: STATS_BINDING,
+            resource: self.buffer.as_entire_binding(),
+        }
+    }
+
+    /// Starts recording generations from the start of the buffer, before the commands computing them are submitted
+    /// on a board at `generation`
+    pub(crate) fn start(&self, queue: &wgpu::Queue, generation: u64) {
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(&[0u32, generation as u32]));
+    }
+
+    /// Records the generation just computed. Needs a board bind group set in the pass
+    pub(crate) fn next<'a>(&'a self, compute_pass: &mut wgpu::ComputePass<'a>) {
+        compute_pass.set_pipeline(&self.next_pipeline);
+        compute_pass.dispatch_workgroups(1, 1, 1);
+    }
+
+    /// Sets the population and hash to `board`'s, for when the board was replaced with it at `generation`.
+    /// Its stats come after those of the commands before in what `take` returns
+    pub(crate) fn set_board(&mut self, queue: &wgpu::Queue, generation: u64, board: &CpuBoard) {
+        let stats = GenerationStats::of_board(board, generation);
+        queue.write_buffer(&self.buffer, GENERATION_OFFSET, bytemuck::bytes_of(&(generation as u32)));
+        queue.write_buffer(&self.buffer, POPULATION_OFFSET, bytemuck::cast_slice(&[stats.population, stats.hash]));
+        match self.pending.back_mut() {
+            Some(readback) => readback.written.push(stats),
+            None => {
+                self.latest = stats;
+                self.push_untaken(stats);
+            },
+        }
+    }
+
+    fn push_untaken(&mut self, stats: GenerationStats) {
+        self.untaken.push_back(stats);
+        if self.untaken.len() > MAX_UNTAKEN_GENERATIONS {
+            self.untaken.pop_front();
+        }
+    }
+
+    /// Copies the stats of the `generations` recorded since `start` back once the GPU is done with them,
+    /// the board being at `generation` after them. While too many readbacks are pending they're skipped,
+    /// leaving a gap in the generations taken that `CycleDetector` starts over after
+    pub(crate) fn read_back(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, generation: u64, generations: u64) {
+        if self.pending.len() >= MAX_PENDING_READBACKS {
+            return
+        }
+        let buffer = self.free.pop().unwrap_or_else(|| device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Stats read buffer"),
+            size: BUFFER_SIZE,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        let generations = generations.min(MAX_GENERATIONS_PER_SUBMIT);
+        let size = HEADER_SIZE+generations*GENERATION_SIZE;
+
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Stats read command encoder")
+        });
+        command_encoder.copy_buffer_to_buffer(&self.buffer, 0, &buffer, 0, size);
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let mapped = Arc::new(AtomicBool::new(false));
+        let mapped_callback = mapped.clone();
+        buffer.slice(..size).map_async(wgpu::MapMode::Read, move |result| {
+            result.expect("failed to map stats read buffer");
+            mapped_callback.store(true, Ordering::Release);
+        });
+        self.pending.push_back(Readback {
+            buffer,
+            generation,
+            generations,
+            written: Vec::new(),
+            mapped,
+        });
+    }
+
+    /// Takes in the stats that have been copied back, in the order they were computed. Toggling cells
+    /// or writing the board adds stats for the generation it happened at, with no births or deaths
+    pub(crate) fn poll(&mut self, device: &wgpu::Device) {
+        device.poll(wgpu::Maintain::Poll);
+        while self.pending.front().is_some_and(|readback| readback.mapped.load(Ordering::Acquire)) {
+            let readback = self.pending.pop_front().unwrap();
+            let mut stats = Vec::with_capacity(readback.generations as usize+readback.written.len()+1);
+            {
+                let size = HEADER_SIZE+readback.generations*GENERATION_SIZE;
+                let data = readback.buffer.slice(..size).get_mapped_range();
+                let words: &[u32] = bytemuck::cast_slice(&data);
+                let first_generation = readback.generation+1-readback.generations;
+                for (i, generation) in words[(HEADER_SIZE/4) as usize..].chunks_exact(6).enumerate() {
+                    stats.push(GenerationStats {
+                        generation: first_generation+i as u64,
+                        population: generation[0] as u64 | (generation[1] as u64) << 32,
+                        births: generation[2] as u64,
+                        deaths: generation[3] as u64,
+                        hash: generation[4] as u64 | (generation[5] as u64) << 32,
+                    });
+                }
+                if readback.generations == 0 {
+                    // Toggled cells, which changes the population without computing a generation
+                    stats.push(GenerationStats {
+                        generation: readback.generation,
+                        population: words[6] as u64 | (words[7] as u64) << 32,
+                        births: 0,
+                        deaths: 0,
+                        hash: words[8] as u64 | (words[9] as u64) << 32,
+                    });
+                }
+            }
+            readback.buffer.unmap();
+            self.free.push(readback.buffer);
+            stats.extend(readback.written);
+
+            for stats in stats {
+                // The births and deaths of a generation stay in the latest stats while its cells are toggled
+                let (births, deaths) = if stats.births+stats.deaths == 0 && stats.generation == self.latest.generation {
+                    (self.latest.births, self.latest.deaths)
+                } else {
+                    (stats.births, stats.deaths)
+                };
+                self.latest = GenerationStats {
+                    births,
+                    deaths,
+                    ..stats
+                };
+                self.push_untaken(stats);
+            }
+        }
+    }
+
+    /// Stats of the board as of the last readback
+    pub(crate) fn latest(&self) -> GenerationStats {
+        self.latest
+    }
+
+    /// Stats of every generation read back and board written since this was last called
+    pub(crate) fn take(&mut self) -> Vec<GenerationStats> {
+        self.untaken.drain(..).collect()
+    }
+
+    /// Whether there are stats still being copied back
+    pub(crate) fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
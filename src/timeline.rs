@@ -0,0 +1,174 @@
+use bui::rect::{SizeAndCenter, Points};
+use bui_basic::{containers::Fill, signal::{SignalReciever, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal}};
+
+use crate::overlay::OverlayRect;
+
+const TRACK_COLOR: [f32; 4] = [0.75, 0.75, 0.75, 1.0];
+const PAST_COLOR: [f32; 4] = [0.45, 0.45, 0.45, 1.0];
+const BOOKMARK_COLOR: [f32; 4] = [0.95, 0.55, 0.1, 1.0];
+const CURRENT_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+const TARGET_COLOR: [f32; 4] = [0.1, 0.35, 0.95, 1.0];
+/// Width of the marks on the timeline, in normalized device coordinates
+const MARK_WIDTH: f32 = 0.006;
+/// How close to a bookmark a click has to be to go to it, in normalized device coordinates
+const BOOKMARK_REACH: f32 = 0.01;
+
+/// Bar spanning the generations that can be gone to, marking the current one and bookmarked ones.
+/// Dragging along it picks a generation to go to when the mouse is let go
+pub struct Timeline {
+    area: Points,
+    mousex: f32,
+    mousey: f32,
+    dragging: bool,
+    start: u64,
+    end: u64,
+    current: u64,
+    bookmarks: Vec<u64>,
+}
+
+/// What was picked on the timeline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelinePick {
+    Generation(u64),
+    /// Index of the bookmark, in the order they were given to `set_generations`
+    Bookmark(usize),
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self {
+            area: SizeAndCenter::ZERO.into(),
+            mousex: 0.0,
+            mousey: 0.0,
+            dragging: false,
+            start: 0,
+            end: 0,
+            current: 0,
+            bookmarks: Vec::new(),
+        }
+    }
+
+    /// Sets the generations the timeline spans from `start` to `end`, where it's at and where the bookmarks are
+    pub fn set_generations(&mut self, start: u64, end: u64, current: u64, bookmarks: Vec<u64>) {
+        self.start = start;
+        self.end = end.max(start);
+        self.current = current;
+        self.bookmarks = bookmarks;
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    fn x_of(&self, generation: u64) -> f32 {
+        if self.end == self.start {
+            return self.area.p2x
+        }
+        let fraction = (generation.clamp(self.start, self.end)-self.start) as f64/(self.end-self.start) as f64;
+        self.area.p1x+(self.area.p2x-self.area.p1x)*fraction as f32
+    }
+
+    fn generation_at(&self, x: f32) -> u64 {
+        let fraction = ((x-self.area.p1x)/(self.area.p2x-self.area.p1x)).clamp(0.0, 1.0) as f64;
+        self.start+((self.end-self.start) as f64*fraction).round() as u64
+    }
+
+    /// The bookmark closest to `x`, if one is close enough to click
+    fn bookmark_at(&self, x: f32) -> Option<usize> {
+        self.bookmarks.iter().enumerate()
+            .map(|(index, generation)| (index, (self.x_of(*generation)-x).abs()))
+            .filter(|(_, distance)| *distance <= BOOKMARK_REACH)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+    }
+
+    /// What letting go of the mouse now would pick, while dragging
+    pub fn hovered(&self) -> Option<TimelinePick> {
+        if !self.dragging {
+            return None
+        }
+        Some(match self.bookmark_at(self.mousex) {
+            Some(bookmark) => TimelinePick::Bookmark(bookmark),
+            None => TimelinePick::Generation(self.generation_at(self.mousex)),
+        })
+    }
+
+    fn mark(&self, x: f32, height: f32, color: [f32; 4]) -> OverlayRect {
+        let middle = (self.area.p1y+self.area.p2y)/2.0;
+        let half_height = (self.area.p1y-self.area.p2y)/2.0*height;
+        OverlayRect::new(Points {
+            p1x: x-MARK_WIDTH/2.0,
+            p1y: middle+half_height,
+            p2x: x+MARK_WIDTH/2.0,
+            p2y: middle-half_height,
+        }, color)
+    }
+
+    pub fn rects(&self) -> Vec<OverlayRect> {
+        let middle = (self.area.p1y+self.area.p2y)/2.0;
+        let track_height = (self.area.p1y-self.area.p2y)/6.0;
+        let current_x = self.x_of(self.current);
+        let mut rects = vec![
+            OverlayRect::new(Points {
+                p1x: self.area.p1x,
+                p1y: middle+track_height,
+                p2x: self.area.p2x,
+                p2y: middle-track_height,
+            }, TRACK_COLOR),
+            OverlayRect::new(Points {
+                p1x: self.area.p1x,
+                p1y: middle+track_height,
+                p2x: current_x,
+                p2y: middle-track_height,
+            }, PAST_COLOR),
+        ];
+        for generation in &self.bookmarks {
+            rects.push(self.mark(self.x_of(*generation), 0.7, BOOKMARK_COLOR));
+        }
+        rects.push(self.mark(current_x, 1.0, CURRENT_COLOR));
+        match self.hovered() {
+            Some(TimelinePick::Generation(generation)) => rects.push(self.mark(self.x_of(generation), 1.0, TARGET_COLOR)),
+            Some(TimelinePick::Bookmark(bookmark)) => rects.push(self.mark(self.x_of(self.bookmarks[bookmark]), 1.0, TARGET_COLOR)),
+            None => {},
+        }
+        rects
+    }
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fill for Timeline {
+    fn fill(&mut self, fill_target: SizeAndCenter) {
+        self.area = fill_target.into();
+    }
+}
+
+/// Returns whether what's picked changed, so the timeline needs redrawing
+impl SignalReciever<CursorMovedSignal, bool> for Timeline {
+    fn take_signal(&mut self, signal: &mut CursorMovedSignal) -> bool {
+        let hovered = self.hovered();
+        self.mousex = signal.norm_posx;
+        self.mousey = signal.norm_posy;
+        self.hovered() != hovered
+    }
+}
+
+/// Returns whether dragging started
+impl SignalReciever<MouseLeftDownSignal, bool> for Timeline {
+    fn take_signal(&mut self, _signal: &mut MouseLeftDownSignal) -> bool {
+        self.dragging = self.mousex >= self.area.p1x-BOOKMARK_REACH && self.mousex <= self.area.p2x+BOOKMARK_REACH && self.mousey <= self.area.p1y && self.mousey >= self.area.p2y;
+        self.dragging
+    }
+}
+
+impl SignalReciever<MouseLeftUpSignal, Option<TimelinePick>> for Timeline {
+    fn take_signal(&mut self, _signal: &mut MouseLeftUpSignal) -> Option<TimelinePick> {
+        let picked = self.hovered();
+        self.dragging = false;
+        picked
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+/// What a neighbour past the edge of the board along one axis is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// Always dead
+    Dead,
+    /// The cell on the opposite edge
+    Wrap,
+    /// The cell on the opposite edge, mirrored along the other axis (like Golly's twisted edges)
+    Flip,
+    /// The cell on the edge itself, as if the board was reflected past it
+    Mirror,
+    /// Always alive
+    Alive,
+}
+
+impl Edge {
+    /// Value used for the edge in `game_of_life.wgsl`
+    pub fn shader_value(&self) -> u32 {
+        match self {
+            Edge::Dead => 0,
+            Edge::Wrap => 1,
+            Edge::Flip => 2,
+            Edge::Mirror => 3,
+            Edge::Alive => 4,
+        }
+    }
+
+    fn parse(edge: &str) -> Result<Self, String> {
+        match edge {
+            "dead" => Ok(Edge::Dead),
+            "wrap" => Ok(Edge::Wrap),
+            "flip" => Ok(Edge::Flip),
+            "mirror" => Ok(Edge::Mirror),
+            "alive" => Ok(Edge::Alive),
+            _ => Err(format!("unknown edge \"{}\", expected dead, wrap, flip, mirror or alive", edge))
+        }
+    }
+}
+
+impl fmt::Display for Edge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edge::Dead => write!(f, "dead"),
+            Edge::Wrap => write!(f, "wrap"),
+            Edge::Flip => write!(f, "flip"),
+            Edge::Mirror => write!(f, "mirror"),
+            Edge::Alive => write!(f, "alive"),
+        }
+    }
+}
+
+/// What a neighbour of a cell turned out to be
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbour {
+    Cell(u32, u32),
+    Dead,
+    Alive,
+}
+
+/// How the edges of the board are joined
+///
+/// A neighbour past the left or right edge is resolved with `x` first, then if it's still past the
+/// top or bottom edge (for corners, or after a flip) it's resolved with `y`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Topology {
+    /// Left and right edges
+    pub x: Edge,
+    /// Top and bottom edges
+    pub y: Edge,
+}
+
+impl Topology {
+    pub const PLANE: Topology = Topology {
+        x: Edge::Dead,
+        y: Edge::Dead,
+    };
+
+    pub const TORUS: Topology = Topology {
+        x: Edge::Wrap,
+        y: Edge::Wrap,
+    };
+
+    pub const KLEIN_BOTTLE: Topology = Topology {
+        x: Edge::Flip,
+        y: Edge::Wrap,
+    };
+
+    pub const CROSS_SURFACE: Topology = Topology {
+        x: Edge::Flip,
+        y: Edge::Flip,
+    };
+
+    pub const CYLINDER: Topology = Topology {
+        x: Edge::Wrap,
+        y: Edge::Dead,
+    };
+
+    const NAMED: [(&'static str, Topology); 5] = [
+        ("plane", Topology::PLANE),
+        ("torus", Topology::TORUS),
+        ("klein", Topology::KLEIN_BOTTLE),
+        ("cross", Topology::CROSS_SURFACE),
+        ("cylinder", Topology::CYLINDER),
+    ];
+
+    /// Parses a named topology (`plane`, `torus`, `klein`, `cross`, `cylinder`) or an edge for each
+    /// axis as `<x>/<y>` (`wrap/alive`)
+    pub fn parse(topology: &str) -> Result<Self, String> {
+        let topology = topology.trim().to_lowercase();
+        if let Some((_, named)) = Self::NAMED.iter().find(|(name, _)| *name == topology) {
+            return Ok(*named)
+        }
+        match topology.split_once('/') {
+            Some((x, y)) => Ok(Topology {
+                x: Edge::parse(x.trim())?,
+                y: Edge::parse(y.trim())?,
+            }),
+            None => Err(format!("unknown topology \"{}\", expected plane, torus, klein, cross, cylinder or <x edge>/<y edge>", topology))
+        }
+    }
+
+    /// Finds what the neighbour at (x, y) is. Only works for neighbours at most one cell past an edge
+    pub fn resolve(&self, x: i64, y: i64, width: u32, height: u32) -> Neighbour {
+        let (width, height) = (width as i64, height as i64);
+        let (mut x, mut y) = (x, y);
+        if x < 0 || x >= width {
+            match self.x {
+                Edge::Dead => return Neighbour::Dead,
+                Edge::Alive => return Neighbour::Alive,
+                Edge::Wrap => x = x.rem_euclid(width),
+                Edge::Flip => {
+                    x = x.rem_euclid(width);
+                    y = height-1-y;
+                },
+                Edge::Mirror => x = x.clamp(0, width-1),
+            }
+        }
+        if y < 0 || y >= height {
+            match self.y {
+                Edge::Dead => return Neighbour::Dead,
+                Edge::Alive => return Neighbour::Alive,
+                Edge::Wrap => y = y.rem_euclid(height),
+                Edge::Flip => {
+                    y = y.rem_euclid(height);
+                    x = width-1-x;
+                },
+                Edge::Mirror => y = y.clamp(0, height-1),
+            }
+        }
+        Neighbour::Cell(x as u32, y as u32)
+    }
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self::PLANE
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match Self::NAMED.iter().find(|(_, named)| named == self) {
+            Some((name, _)) => write!(f, "{}", name),
+            None => write!(f, "{}/{}", self.x, self.y),
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::cpu_board::CpuBoard;
+
+/// Longest trail that can be shown
+pub const MAX_TRAIL_LENGTH: u32 = 16;
+/// Most bytes the ring of past generations can take up
+pub const MAX_TRAIL_SIZE: u64 = 512 << 20;
+
+/// Checks that the ring of a trail `length` generations long fits in `MAX_TRAIL_SIZE` for a `width` by
+/// `height` board stored in `format`
+pub fn check_size(width: u32, height: u32, format: wgpu::TextureFormat, length: u32) -> Result<(), String> {
+    let size = width as u64*height as u64*format.describe().block_size as u64*length as u64;
+    if size > MAX_TRAIL_SIZE {
+        Err(format!("a trail of {} generations of a {}x{} board needs {} MiB, more than the maximum of {} MiB", length, width, height, size >> 20, MAX_TRAIL_SIZE >> 20))
+    } else {
+        Ok(())
+    }
+}
+
+/// Opacity a cell alive `age` generations ago is drawn with in a trail `length` generations long, fading
+/// out evenly so the generation past the end of the trail would be invisible. Same as trail_opacity in
+/// game_of_life.wgsl
+pub fn opacity(age: u32, length: u32) -> f32 {
+    1.0-age as f32/(length+1) as f32
+}
+
+/// Matches Trails in game_of_life.wgsl
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TrailsUniformData {
+    /// Layers in the ring, 0 while trails are off
+    length: u32,
+    /// Layer the next past generation goes in, so the one before it holds the last generation
+    head: u32,
+    /// Layers holding past generations, which is less than `length` until the ring fills up
+    count: u32,
+    _padding: u32,
+}
+
+bui::typed_uniform!(TrailsUniform, TrailsUniformData, "Trails Uniform");
+
+/// Ring of the last few generations of a texture board, drawn faintly behind the board so moving objects
+/// leave trails. The ring is a texture array with a layer per generation, made when the trail length is set
+pub(crate) struct Trails {
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+    length: u32,
+    head: u32,
+    count: u32,
+    ring: wgpu::Texture,
+    uniform: TrailsUniform,
+    layout: wgpu::BindGroupLayout,
+    bind_group: wgpu::BindGroup,
+}
+
+impl Trails {
+    pub(crate) fn new(device: &wgpu::Device, width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Trails bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        sample_type: wgpu::TextureSampleType::Uint,
+                    },
+                    count: None
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ]
+        });
+        let uniform = TrailsUniform::new(device);
+        // Stands in until trails are turned on
+        let ring = Self::create_ring(device, 1, 1, 1, format);
+        let bind_group = Self::create_bind_group(device, &layout, &ring, &uniform);
+
+        Self {
+            width,
+            height,
+            format,
+            length: 0,
+            head: 0,
+            count: 0,
+            ring,
+            uniform,
+            layout,
+            bind_group,
+        }
+    }
+
+    fn create_ring(device: &wgpu::Device, width: u32, height: u32, layers: u32, format: wgpu::TextureFormat) -> wgpu::Texture {
+        device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Trails ring"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: layers,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+        })
+    }
+
+    fn create_bind_group(device: &wgpu::Device, layout: &wgpu::BindGroupLayout, ring: &wgpu::Texture, uniform: &TrailsUniform) -> wgpu::BindGroup {
+        // A single layer would be viewed as a plain 2D texture by default
+        let ring_view = ring.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Trails bind group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&ring_view)
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: uniform.binding(),
+                },
+            ]
+        })
+    }
+
+    pub(crate) fn layout(&self) -> &wgpu::BindGroupLayout {
+        &self.layout
+    }
+
+    pub(crate) fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    pub(crate) fn length(&self) -> u32 {
+        self.length
+    }
+
+    /// Keeps the last `length` generations, or turns trails off if it's 0. The trail starts over from the
+    /// next generation
+    pub(crate) fn set_length(&mut self, device: &wgpu::Device, length: u32) -> Result<(), String> {
+        let max_length = MAX_TRAIL_LENGTH.min(device.limits().max_texture_array_layers);
+        if length > max_length {
+            return Err(format!("trail length exceeds the maximum of {}", max_length))
+        }
+        check_size(self.width, self.height, self.format, length)?;
+        if length != self.length {
+            self.ring = match length {
+                0 => Self::create_ring(device, 1, 1, 1, self.format),
+                length => Self::create_ring(device, self.width, self.height, length, self.format),
+            };
+            self.bind_group = Self::create_bind_group(device, &self.layout, &self.ring, &self.uniform);
+            self.length = length;
+        }
+        self.clear();
+        Ok(())
+    }
+
+    /// Forgets past generations, for when the board is replaced
+    pub(crate) fn clear(&mut self) {
+        self.head = 0;
+        self.count = 0;
+    }
+
+    /// Whether the generation `remaining` generations before the board is done being computed ends up in the
+    /// trail
+    pub(crate) fn records(&self, remaining: u64) -> bool {
+        remaining <= self.length as u64
+    }
+
+    /// Copies `board` into the ring as the latest past generation, pushing out the oldest once it's full
+    pub(crate) fn record(&mut self, encoder: &mut wgpu::CommandEncoder, board: &wgpu::Texture) {
+        if self.length == 0 {
+            return;
+        }
+        encoder.copy_texture_to_texture(board.as_image_copy(), wgpu::ImageCopyTexture {
+            texture: &self.ring,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: 0,
+                y: 0,
+                z: self.head,
+            },
+            aspect: wgpu::TextureAspect::All,
+        }, wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        });
+        self.head = (self.head+1)%self.length;
+        self.count = (self.count+1).min(self.length);
+    }
+
+    /// The past generations in the ring, the last one first
+    pub(crate) fn read(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Vec<CpuBoard> {
+        if self.count == 0 {
+            return Vec::new()
+        }
+        let texel_size = self.format.describe().block_size as u32;
+        let bytes_per_row = (self.width*texel_size).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)*wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let layer_size = bytes_per_row as usize*self.height as usize;
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Trails read buffer"),
+            size: layer_size as u64*self.length as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut command_encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Trails read command encoder")
+        });
+        command_encoder.copy_texture_to_buffer(self.ring.as_image_copy(), wgpu::ImageCopyBuffer {
+            buffer: &read_buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: std::num::NonZeroU32::new(bytes_per_row),
+                rows_per_image: std::num::NonZeroU32::new(self.height),
+            }
+        }, wgpu::Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: self.length,
+        });
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let read_slice = read_buffer.slice(..);
+        read_slice.map_async(wgpu::MapMode::Read, |result| result.expect("failed to map trails read buffer"));
+        device.poll(wgpu::Maintain::Wait);
+
+        let boards = {
+            let data = read_slice.get_mapped_range();
+            (1..=self.count).map(|age| {
+                let layer = &data[((self.head+self.length-age)%self.length) as usize*layer_size..];
+                let mut board = CpuBoard::new(self.width, self.height);
+                for y in 0..self.height {
+                    for x in 0..self.width {
+                        // Alive cells are 1, so the first byte is enough whatever the format
+                        board.set(x, y, layer[(y*bytes_per_row+x*texel_size) as usize] == 1);
+                    }
+                }
+                board
+            }).collect()
+        };
+        read_buffer.unmap();
+        boards
+    }
+
+    pub(crate) fn set_uniform(&self, queue: &wgpu::Queue) {
+        self.uniform.set(&TrailsUniformData {
+            length: self.length,
+            head: self.head,
+            count: self.count,
+            _padding: 0,
+        }, queue);
+    }
+}
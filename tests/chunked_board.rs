@@ -0,0 +1,201 @@
+mod common;
+
+use wgpu_game_of_life::{chunked_board::{ChunkedGameOfLife, CHUNK_SIZE}, cpu_board::CpuBoard, hashlife::HashLife, rule::Rule, topology::Topology};
+
+const GLIDER: [(u32, u32); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+fn chunked_game_of_life(device: &wgpu::Device, queue: &wgpu::Queue, rule: Rule) -> ChunkedGameOfLife {
+    let mut game_of_life = ChunkedGameOfLife::new(device, wgpu::TextureFormat::Rgba8UnormSrgb, rule, 1.0, 1.0);
+    game_of_life.construct(queue);
+    game_of_life
+}
+
+/// Writes `board` at (x, y) on a chunked board and a HashLife plane, runs both for `generations`
+/// and compares the `margin` cells around where the board was
+fn assert_chunked_matches_hashlife(board: &CpuBoard, x: i64, y: i64, generations: u64, margin: u32) {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, board.rule());
+    game_of_life.write_region(&device, &queue, x, y, board).unwrap();
+    assert_eq!(&game_of_life.read_region(&device, &queue, x, y, board.width(), board.height()), board, "board didn't survive a round trip to the GPU");
+
+    let mut hashlife = HashLife::new(board.rule()).unwrap();
+    for (cellx, celly) in board.alive_cells() {
+        hashlife.set(x+cellx as i64, y+celly as i64, true);
+    }
+    game_of_life.compute_n(&device, &queue, generations).unwrap();
+    hashlife.step(generations).unwrap();
+
+    let (width, height) = (board.width()+margin*2, board.height()+margin*2);
+    let (startx, starty) = (x-margin as i64, y-margin as i64);
+    let mut expected = CpuBoard::new(width, height);
+    expected.set_rule(board.rule());
+    for celly in 0..height {
+        for cellx in 0..width {
+            expected.set(cellx, celly, hashlife.get(startx+cellx as i64, starty+celly as i64));
+        }
+    }
+    assert_eq!(game_of_life.read_region(&device, &queue, startx, starty, width, height), expected, "chunked board and HashLife disagree after {} generations", generations);
+    assert_eq!(game_of_life.alive_cells(&device, &queue).len() as u64, hashlife.population(), "cells escaped the compared region");
+    assert_eq!(game_of_life.generation(), generations);
+}
+
+#[test]
+fn glider_crosses_chunks() {
+    // Heads towards low x and y, crossing into chunks with negative coordinates
+    let mut board = CpuBoard::new(3, 3);
+    for (x, y) in GLIDER {
+        board.set(2-x, 2-y, true);
+    }
+    assert_chunked_matches_hashlife(&board, 4, 4, 600, 160);
+}
+
+#[test]
+fn soup_across_chunk_corner_matches_hashlife() {
+    let soup = common::random_board(100, 100, 0.4, 3, Rule::CONWAY, Topology::PLANE);
+    assert_chunked_matches_hashlife(&soup, -50, -50, 300, 200);
+}
+
+#[test]
+fn rules_match_hashlife() {
+    for rule in ["B36/S23", "B3678/S34678", "B2/S"] {
+        let soup = common::random_board(40, 40, 0.3, 11, Rule::parse(rule).unwrap(), Topology::PLANE);
+        assert_chunked_matches_hashlife(&soup, CHUNK_SIZE as i64-20, -20, 40, 80);
+    }
+}
+
+#[test]
+fn toggle_far_from_origin() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, Rule::CONWAY);
+    let (x, y) = (1 << 40, -(1 << 40));
+    // A block, which stays put
+    for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        game_of_life.toggle(x+dx, y+dy, &device, &queue).unwrap();
+    }
+    game_of_life.compute_n(&device, &queue, 10).unwrap();
+    let mut cells = game_of_life.alive_cells(&device, &queue);
+    cells.sort_unstable();
+    assert_eq!(cells, vec![(x, y), (x, y+1), (x+1, y), (x+1, y+1)]);
+
+    game_of_life.toggle(x, y, &device, &queue).unwrap();
+    assert!(!game_of_life.read_region(&device, &queue, x, y, 1, 1).get(0, 0));
+}
+
+#[test]
+fn empty_chunks_are_freed() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, Rule::CONWAY);
+    assert_eq!(game_of_life.chunk_count(), 0);
+    // A blinker in the middle of a chunk only needs that chunk and the ones around it
+    let mut blinker = CpuBoard::new(3, 1);
+    for x in 0..3 {
+        blinker.set(x, 0, true);
+    }
+    game_of_life.write_region(&device, &queue, 100, 100, &blinker).unwrap();
+    assert_eq!(game_of_life.chunk_count(), 9);
+    game_of_life.compute_n(&device, &queue, 1000).unwrap();
+    assert_eq!(game_of_life.chunk_count(), 9);
+
+    game_of_life.write_region(&device, &queue, 99, 99, &CpuBoard::new(5, 5)).unwrap();
+    game_of_life.compute(&device, &queue).unwrap();
+    assert_eq!(game_of_life.chunk_count(), 0);
+    assert_eq!(game_of_life.generation(), 1001);
+}
+
+#[test]
+fn grows_past_initial_chunks() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, Rule::CONWAY);
+    let mut block = CpuBoard::new(2, 2);
+    for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+        block.set(x, y, true);
+    }
+    // Every block needs 9 chunks of its own
+    let blocks: Vec<(i64, i64)> = (0..6).map(|i| (i*1000-3000, 77-i*5000)).collect();
+    for &(x, y) in &blocks {
+        game_of_life.write_region(&device, &queue, x, y, &block).unwrap();
+    }
+    assert_eq!(game_of_life.chunk_count(), 54);
+    game_of_life.compute_n(&device, &queue, 130).unwrap();
+    for &(x, y) in &blocks {
+        assert_eq!(game_of_life.read_region(&device, &queue, x, y, 2, 2), block);
+    }
+    assert_eq!(game_of_life.alive_cells(&device, &queue).len(), 24);
+}
+
+#[test]
+fn running_out_of_chunks_stops_before_losing_cells() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, Rule::CONWAY);
+    let mut glider = CpuBoard::new(3, 3);
+    for (x, y) in GLIDER {
+        glider.set(x, y, true);
+    }
+    // As many gliders as there's room for the 9 chunks around, each about to cross into the next chunk
+    // diagonally, which needs 5 more
+    let gliders = device.limits().max_texture_array_layers.min(1024) as i64/9;
+    for i in 0..gliders {
+        game_of_life.write_region(&device, &queue, i*4*CHUNK_SIZE as i64+240, 240, &glider).unwrap();
+    }
+    assert!(game_of_life.compute_n(&device, &queue, 1000).is_err());
+    assert!(game_of_life.generation() < 1000);
+    assert_eq!(game_of_life.alive_cells(&device, &queue).len() as i64, gliders*5);
+}
+
+#[test]
+fn jump_matches_hashlife() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = chunked_game_of_life(&device, &queue, Rule::CONWAY);
+    let mut hashlife = HashLife::new(Rule::CONWAY).unwrap();
+    let mut glider = CpuBoard::new(3, 3);
+    for (x, y) in GLIDER {
+        glider.set(x, y, true);
+        hashlife.set(x as i64-7, y as i64-7, true);
+    }
+    game_of_life.write_region(&device, &queue, -7, -7, &glider).unwrap();
+
+    // Far enough that the glider lands thousands of chunks away
+    game_of_life.jump_pow2(&device, &queue, 20).unwrap();
+    hashlife.jump_pow2(20).unwrap();
+    let mut cells = game_of_life.alive_cells(&device, &queue);
+    cells.sort_unstable();
+    let mut expected = hashlife.alive_cells();
+    expected.sort_unstable();
+    assert_eq!(cells, expected);
+    assert_eq!(game_of_life.generation(), 1 << 20);
+    assert_eq!(game_of_life.chunk_count(), 9);
+
+    // And it keeps flying from there
+    game_of_life.compute_n(&device, &queue, 8).unwrap();
+    hashlife.step(8).unwrap();
+    let mut cells = game_of_life.alive_cells(&device, &queue);
+    cells.sort_unstable();
+    let mut expected = hashlife.alive_cells();
+    expected.sort_unstable();
+    assert_eq!(cells, expected);
+}
+
+#[test]
+fn unbounded_boards_need_a_plane_without_b0() {
+    assert!(ChunkedGameOfLife::check(Rule::CONWAY, Topology::PLANE).is_ok());
+    assert!(ChunkedGameOfLife::check(Rule::CONWAY, Topology::TORUS).is_err());
+    assert!(ChunkedGameOfLife::check(Rule::parse("B0123/S").unwrap(), Topology::PLANE).is_err());
+}
@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::GameOfLife, packed_board::PackedGameOfLife, rule::Rule, topology::Topology};
+
+/// Headless device for the tests that run on the GPU, or None if there is no adapter. With REQUIRE_GPU set
+/// to anything but 0, a missing adapter fails the test instead of skipping it
+pub fn device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all));
+    let adapter = match futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())) {
+        Some(adapter) => adapter,
+        None => return skip("No GPU adapter available".to_string()),
+    };
+    match futures::executor::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+        label: Some("Test device"),
+        // Lets boards be R8Uint where the adapter can store to it
+        features: adapter.features() & wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+        limits: adapter.limits(),
+    }, None)) {
+        Ok(device) => Some(device),
+        Err(err) => skip(format!("Failed to get a GPU device: {}", err)),
+    }
+}
+
+fn skip(reason: String) -> Option<(wgpu::Device, wgpu::Queue)> {
+    if std::env::var("REQUIRE_GPU").map_or(false, |require| require != "0") {
+        panic!("{}, and REQUIRE_GPU is set", reason);
+    }
+    eprintln!("{}, skipping", reason);
+    None
+}
+
+pub fn game_of_life(device: &wgpu::Device, queue: &wgpu::Queue, board: &CpuBoard) -> GameOfLife {
+    let mut game_of_life = GameOfLife::new(device, wgpu::TextureFormat::Rgba8UnormSrgb, board.width(), board.height(), board.rule(), board.topology(), 1.0, 1.0);
+    game_of_life.construct(queue);
+    game_of_life.write_board(queue, board);
+    game_of_life
+}
+
+pub fn packed_game_of_life(device: &wgpu::Device, queue: &wgpu::Queue, board: &CpuBoard) -> PackedGameOfLife {
+    let mut game_of_life = PackedGameOfLife::new(device, wgpu::TextureFormat::Rgba8UnormSrgb, board.width(), board.height(), board.rule(), board.topology(), 1.0, 1.0);
+    game_of_life.construct(queue);
+    game_of_life.write_board(queue, board);
+    game_of_life
+}
+
+/// Deterministic board with roughly `density` of its cells alive
+pub fn random_board(width: u32, height: u32, density: f32, seed: u64, rule: Rule, topology: Topology) -> CpuBoard {
+    let mut state = seed;
+    let mut board = CpuBoard::new(width, height);
+    board.set_rule(rule);
+    board.set_topology(topology);
+    for y in 0..height {
+        for x in 0..width {
+            // xorshift64*
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            let random = state.wrapping_mul(0x2545F4914F6CDD1D) >> 40;
+            board.set(x, y, (random as f32/(1 << 24) as f32) < density);
+        }
+    }
+    board
+}
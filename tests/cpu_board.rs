@@ -0,0 +1,107 @@
+use wgpu_game_of_life::cpu_board::CpuBoard;
+
+fn translated(cells: &[(u32, u32)], dx: u32, dy: u32) -> Vec<(u32, u32)> {
+    cells.iter().map(|&(x, y)| (x+dx, y+dy)).collect()
+}
+
+const BLINKER: [(u32, u32); 3] = [(1, 0), (1, 1), (1, 2)];
+const BLOCK: [(u32, u32); 4] = [(0, 0), (1, 0), (0, 1), (1, 1)];
+const GLIDER: [(u32, u32); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+const R_PENTOMINO: [(u32, u32); 5] = [(1, 0), (2, 0), (0, 1), (1, 1), (1, 2)];
+
+#[test]
+fn empty_board_stays_empty() {
+    let mut board = CpuBoard::new(8, 8);
+    board.step_n(10);
+    assert_eq!(board, CpuBoard::new(8, 8));
+}
+
+#[test]
+fn get_set_and_toggle() {
+    let mut board = CpuBoard::new(4, 3);
+    assert!(!board.get(3, 2));
+    board.set(3, 2, true);
+    assert!(board.get(3, 2));
+    board.toggle(3, 2);
+    assert!(!board.get(3, 2));
+    board.toggle(0, 0);
+    assert_eq!(board.alive_cells(), vec![(0, 0)]);
+    assert_eq!(board.population(), 1);
+}
+
+#[test]
+#[should_panic]
+fn get_outside_of_board_panics() {
+    CpuBoard::new(4, 4).get(4, 0);
+}
+
+#[test]
+fn block_is_still() {
+    let block = CpuBoard::with_cells(6, 6, &translated(&BLOCK, 2, 2));
+    let mut board = block.clone();
+    for _ in 0..5 {
+        board.step();
+        assert_eq!(board, block);
+    }
+}
+
+#[test]
+fn blinker_has_period_two() {
+    let vertical = CpuBoard::with_cells(5, 5, &translated(&BLINKER, 1, 1));
+    let horizontal = CpuBoard::with_cells(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+    let mut board = vertical.clone();
+    board.step();
+    assert_eq!(board, horizontal);
+    board.step();
+    assert_eq!(board, vertical);
+}
+
+#[test]
+fn glider_moves_diagonally() {
+    let mut board = CpuBoard::with_cells(12, 12, &GLIDER);
+    for generation in 1..=4 {
+        board.step();
+        assert_eq!(board.population(), 5, "generation {}", generation);
+    }
+    assert_eq!(board, CpuBoard::with_cells(12, 12, &translated(&GLIDER, 1, 1)));
+    board.step_n(16);
+    assert_eq!(board, CpuBoard::with_cells(12, 12, &translated(&GLIDER, 5, 5)));
+}
+
+#[test]
+fn r_pentomino_population() {
+    let mut board = CpuBoard::with_cells(160, 160, &translated(&R_PENTOMINO, 80, 80));
+    let expected = [5, 6, 7, 9, 8, 9, 12, 11, 18, 11, 11, 10, 13, 16, 19, 19, 23, 25, 35, 25];
+    for (generation, population) in expected.iter().enumerate() {
+        assert_eq!(board.population(), *population, "generation {}", generation);
+        board.step();
+    }
+    board.step_n(200-expected.len() as u64);
+    assert_eq!(board.population(), 120);
+}
+
+#[test]
+fn cells_outside_of_board_are_dead() {
+    // Half of the blinker's horizontal phase falls off the left edge, so it dies out
+    let mut board = CpuBoard::with_cells(5, 5, &[(0, 1), (0, 2), (0, 3)]);
+    board.step();
+    assert_eq!(board, CpuBoard::with_cells(5, 5, &[(0, 2), (1, 2)]));
+    board.step();
+    assert_eq!(board, CpuBoard::new(5, 5));
+}
+
+#[test]
+fn single_row_board() {
+    let mut board = CpuBoard::with_cells(5, 1, &[(1, 0), (2, 0), (3, 0)]);
+    board.step();
+    assert_eq!(board, CpuBoard::with_cells(5, 1, &[(2, 0)]));
+    board.step();
+    assert_eq!(board, CpuBoard::new(5, 1));
+}
+
+#[test]
+fn single_cell_board() {
+    let mut board = CpuBoard::with_cells(1, 1, &[(0, 0)]);
+    board.step();
+    assert_eq!(board, CpuBoard::new(1, 1));
+}
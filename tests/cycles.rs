@@ -0,0 +1,108 @@
+use wgpu_game_of_life::{cpu_board::CpuBoard, cycles::{Cycle, CycleDetector}, stats::{GenerationStats, board_hash}, topology::Topology};
+
+fn stats(board: &CpuBoard, generation: u64) -> GenerationStats {
+    GenerationStats {
+        generation,
+        population: board.population() as u64,
+        births: 0,
+        deaths: 0,
+        hash: board_hash(board),
+    }
+}
+
+/// Steps `board` until `detector` finds a cycle, returning the generation it was found at and the cycle
+fn run(board: &mut CpuBoard, detector: &mut CycleDetector, limit: u64) -> Option<(u64, Cycle)> {
+    for generation in 0..=limit {
+        if generation > 0 {
+            board.step();
+        }
+        if let Some(cycle) = detector.push(stats(board, generation)) {
+            return Some((generation, cycle))
+        }
+    }
+    None
+}
+
+#[test]
+fn finds_still_lifes() {
+    // A pre-block settles into a block after a generation
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 4), (5, 4), (4, 5)]);
+    let mut detector = CycleDetector::new(100);
+    assert_eq!(run(&mut board, &mut detector, 10), Some((2, Cycle { start: 1, period: 1, exact: true })));
+    assert_eq!(detector.cycle().unwrap().to_string(), "still life since generation 1");
+}
+
+#[test]
+fn finds_oscillators() {
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 5), (5, 5), (6, 5)]);
+    assert_eq!(run(&mut board, &mut CycleDetector::new(100), 10), Some((2, Cycle { start: 0, period: 2, exact: true })));
+
+    // A glider on a 12x12 torus comes back where it started after 48 generations
+    let mut board = CpuBoard::with_cells(12, 12, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    board.set_topology(Topology::TORUS);
+    assert_eq!(run(&mut board, &mut CycleDetector::new(100), 100), Some((48, Cycle { start: 0, period: 48, exact: true })));
+}
+
+#[test]
+fn longer_periods_than_the_window_are_not_found() {
+    let mut board = CpuBoard::with_cells(12, 12, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    board.set_topology(Topology::TORUS);
+    assert_eq!(run(&mut board, &mut CycleDetector::new(40), 100), None);
+}
+
+#[test]
+fn hashes_tell_moved_patterns_apart() {
+    // The same pattern somewhere else is a different board
+    let a = CpuBoard::with_cells(10, 10, &[(1, 1), (2, 1), (3, 1)]);
+    let b = CpuBoard::with_cells(10, 10, &[(1, 2), (2, 2), (3, 2)]);
+    let c = CpuBoard::with_cells(10, 10, &[(2, 1), (3, 1), (4, 1)]);
+    assert_ne!(board_hash(&a), board_hash(&b));
+    assert_ne!(board_hash(&a), board_hash(&c));
+    assert_eq!(board_hash(&CpuBoard::new(10, 10)), 0);
+}
+
+#[test]
+fn edits_forget_later_generations() {
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 5), (5, 5), (6, 5)]);
+    let mut detector = CycleDetector::new(100);
+    assert!(run(&mut board, &mut detector, 10).is_some());
+
+    // Going back to generation 1 forgets the cycle found at generation 2
+    board.step();
+    assert_eq!(detector.push(stats(&board, 1)), None);
+    assert_eq!(detector.cycle(), None);
+
+    // Editing the board at generation 1 starts over from it, even though the lone cell dies and leaves the board of generation 0
+    board.toggle(0, 0);
+    assert_eq!(detector.push(stats(&board, 1)), None);
+    for generation in 2..=3 {
+        board.step();
+        assert_eq!(detector.push(stats(&board, generation)), None);
+    }
+    board.step();
+    assert_eq!(detector.push(stats(&board, 4)), Some(Cycle { start: 2, period: 2, exact: true }));
+}
+
+#[test]
+fn skipped_stats_make_cycles_inexact() {
+    // A pre-block is a block from generation 1 on, but stats for generations 1 to 4 never came
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 4), (5, 4), (4, 5)]);
+    let mut detector = CycleDetector::new(100);
+    assert_eq!(detector.push(stats(&board, 0)), None);
+    board.step_n(5);
+    assert_eq!(detector.push(stats(&board, 5)), None);
+    board.step();
+    let cycle = detector.push(stats(&board, 6)).unwrap();
+    assert_eq!(cycle, Cycle { start: 5, period: 1, exact: false });
+    assert_eq!(cycle.to_string(), "still life since generation 5 or earlier");
+
+    // Cycles found starting after the first generation past the gap are exact again
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 4), (5, 4), (4, 5)]);
+    let mut detector = CycleDetector::new(100);
+    assert_eq!(detector.push(stats(&CpuBoard::new(10, 10), 0)), None);
+    assert_eq!(detector.push(stats(&board, 7)), None);
+    board.step();
+    assert_eq!(detector.push(stats(&board, 8)), None);
+    board.step();
+    assert_eq!(detector.push(stats(&board, 9)), Some(Cycle { start: 8, period: 1, exact: true }));
+}
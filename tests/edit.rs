@@ -0,0 +1,65 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, edit::Edit, game::Storage, rule::Rule, stats::board_hash, topology::Topology};
+
+#[test]
+fn parses_edits() {
+    assert_eq!(Edit::parse(" Clear "), Ok(Edit::Clear));
+    assert_eq!(Edit::parse("shift -3, 12"), Ok(Edit::Shift { dx: -3, dy: 12 }));
+    assert_eq!(Edit::parse("rotate 270"), Ok(Edit::Rotate(3)));
+    assert_eq!(Edit::parse("flip h"), Ok(Edit::FlipHorizontal));
+    for edit in [Edit::Clear, Edit::Invert, Edit::Shift { dx: 5, dy: -1 }, Edit::Rotate(1), Edit::Rotate(2), Edit::FlipHorizontal, Edit::FlipVertical] {
+        assert_eq!(Edit::parse(&edit.to_string()), Ok(edit));
+    }
+    assert!(Edit::parse("rotate 45").is_err());
+    assert!(Edit::parse("shift 3").is_err());
+    assert!(Edit::parse("clear 3").is_err());
+    assert!(Edit::parse("flip z").is_err());
+}
+
+#[test]
+fn edits_move_cells() {
+    // Clockwise as shown, with y going up
+    let board = CpuBoard::with_cells(5, 5, &[(0, 0), (1, 0)]);
+    assert_eq!(Edit::Rotate(1).apply(&board), CpuBoard::with_cells(5, 5, &[(0, 4), (0, 3)]));
+    assert_eq!(Edit::Shift { dx: -1, dy: 1 }.apply(&board), CpuBoard::with_cells(5, 5, &[(4, 1), (0, 1)]));
+
+    let board = common::random_board(30, 30, 0.4, 3, Rule::CONWAY, Topology::TORUS);
+    let mut rotated = board.clone();
+    for _ in 0..3 {
+        rotated = Edit::Rotate(1).apply(&rotated);
+        assert_ne!(rotated, board);
+    }
+    assert_eq!(Edit::Rotate(1).apply(&rotated), board);
+    assert_eq!(Edit::Rotate(3).apply(&Edit::Rotate(1).apply(&board)), board);
+
+    let board = common::random_board(31, 20, 0.4, 4, Rule::CONWAY, Topology::TORUS);
+    let flipped = Edit::FlipVertical.apply(&Edit::FlipHorizontal.apply(&board));
+    assert_eq!(Edit::Rotate(2).apply(&board), flipped);
+    assert_eq!(Edit::Shift { dx: 40, dy: -7 }.apply(&Edit::Shift { dx: -40, dy: 7 }.apply(&board)), board);
+    assert_eq!(Edit::Invert.apply(&board).population(), 31*20-board.population());
+    assert_eq!(Edit::Clear.apply(&board).population(), 0);
+}
+
+#[test]
+fn gpu_edits_match_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = common::random_board(45, 30, 0.35, 9, Rule::CONWAY, Topology::TORUS);
+    let mut texture = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    let mut packed = Storage::Packed(common::packed_game_of_life(&device, &queue, &board));
+    for edit in ["rotate 90", "shift -17,4", "flip h", "invert", "rotate 180", "flip v", "rotate 270", "clear"] {
+        let edit = Edit::parse(edit).unwrap();
+        board = edit.apply(&board);
+        for game_of_life in [&mut texture, &mut packed] {
+            game_of_life.edit(&device, &queue, &edit).unwrap();
+            assert_eq!(game_of_life.read_board(&device, &queue).unwrap(), board, "board differs after {}", edit);
+            device.poll(wgpu::Maintain::Wait);
+            game_of_life.poll_stats(&device);
+            let stats = game_of_life.stats().unwrap();
+            assert_eq!((stats.population, stats.hash), (board.population() as u64, board_hash(&board)));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::{GameOfLife, Storage}, rule::Rule, topology::{Edge, Topology}};
+
+fn assert_gpu_matches_cpu(board: CpuBoard, generations: u32) {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    assert_eq!(game_of_life.read_board(&device, &queue), board, "board didn't survive a round trip to the GPU");
+
+    let mut board = board;
+    for generation in 1..=generations {
+        game_of_life.compute(&device, &queue);
+        board.step();
+        assert_eq!(game_of_life.read_board(&device, &queue), board, "GPU and CPU disagree at generation {} with topology {}", generation, board.topology());
+    }
+}
+
+#[test]
+fn toggle_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = CpuBoard::new(7, 5);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    for (x, y) in [(0, 0), (6, 4), (3, 2), (0, 0)] {
+        game_of_life.toggle(x as i32, y as i32, &device, &queue);
+        board.toggle(x, y);
+        assert_eq!(game_of_life.read_board(&device, &queue), board);
+    }
+}
+
+#[test]
+fn board_format_falls_back_to_r32uint() {
+    let instance = wgpu::Instance::new(wgpu::util::backend_bits_from_env().unwrap_or_else(wgpu::Backends::all));
+    let adapter = match futures::executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())) {
+        Some(adapter) => adapter,
+        None => return,
+    };
+    // Without adapter specific format features R8Uint can't be a storage texture
+    let (device, _queue) = futures::executor::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).unwrap();
+    assert_eq!(GameOfLife::board_format(&device), wgpu::TextureFormat::R32Uint);
+}
+
+#[test]
+fn glider_matches_cpu() {
+    assert_gpu_matches_cpu(CpuBoard::with_cells(12, 10, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]), 40);
+}
+
+#[test]
+fn rules_match_cpu() {
+    for rule in ["B3/S23", "B36/S23", "B3678/S34678", "B2/S"] {
+        assert_gpu_matches_cpu(common::random_board(37, 29, 0.4, 7, Rule::parse(rule).unwrap(), Topology::PLANE), 10);
+    }
+}
+
+#[test]
+fn topologies_match_cpu() {
+    let edges = [Edge::Dead, Edge::Wrap, Edge::Flip, Edge::Mirror, Edge::Alive];
+    for x in edges {
+        for y in edges {
+            assert_gpu_matches_cpu(common::random_board(23, 17, 0.35, 42, Rule::CONWAY, Topology { x, y }), 8);
+        }
+    }
+}
+
+#[test]
+fn tiny_boards_match_cpu() {
+    for (width, height) in [(1, 1), (1, 5), (5, 1), (2, 2)] {
+        for topology in [Topology::PLANE, Topology::TORUS, Topology::CROSS_SURFACE, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+            assert_gpu_matches_cpu(common::random_board(width, height, 0.5, 3, Rule::CONWAY, topology), 4);
+        }
+    }
+}
+
+#[test]
+fn boards_across_several_tiles_match_cpu() {
+    // Tiles are 16x16, so these have full tiles, partial tiles and cells on both sides of tile edges
+    for (width, height) in [(16, 16), (17, 33), (32, 15), (100, 70)] {
+        for topology in [Topology::PLANE, Topology::TORUS, Topology::KLEIN_BOTTLE, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+            assert_gpu_matches_cpu(common::random_board(width, height, 0.4, width as u64*height as u64, Rule::CONWAY, topology), 6);
+        }
+    }
+}
+
+#[test]
+fn compute_n_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = common::random_board(16, 16, 0.3, 11, Rule::CONWAY, Topology::TORUS);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    for n in [0, 1, 2, 7, 1030] {
+        game_of_life.compute_n(&device, &queue, n);
+        board.step_n(n);
+        assert_eq!(game_of_life.read_board(&device, &queue), board, "after {} more generations", n);
+    }
+    assert_eq!(game_of_life.generation(), 1040);
+}
+
+#[test]
+fn hashlife_jump_matches_compute() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let soup = common::random_board(12, 12, 0.4, 9, Rule::CONWAY, Topology::PLANE);
+    let mut board = CpuBoard::new(128, 128);
+    for (x, y) in soup.alive_cells() {
+        board.set(x+58, y+58, true);
+    }
+    let mut computed = common::game_of_life(&device, &queue, &board);
+    let mut jumped = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    computed.compute_n(&device, &queue, 3);
+    jumped.compute_n(&device, &queue, 3).unwrap();
+    computed.compute_n(&device, &queue, 32);
+    jumped.jump_pow2(&device, &queue, 5).unwrap();
+    assert_eq!(jumped.read_board(&device, &queue), Some(computed.read_board(&device, &queue)));
+    assert_eq!(jumped.generation(), 35);
+
+    let mut torus = board.clone();
+    torus.set_topology(Topology::TORUS);
+    assert!(Storage::Texture(common::game_of_life(&device, &queue, &torus)).jump_pow2(&device, &queue, 5).is_err());
+
+    // A glider would fly off the board, where computing would have turned it into a block at the edge
+    let glider = CpuBoard::with_cells(20, 20, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    let mut flying = Storage::Texture(common::game_of_life(&device, &queue, &glider));
+    assert!(flying.jump_pow2(&device, &queue, 7).is_err());
+    assert_eq!(flying.read_board(&device, &queue), Some(glider));
+    assert_eq!(flying.generation(), 0);
+}
+
+#[test]
+fn tile_tracking_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    // Gliders leave through the edges of mostly empty boards, so tiles wake up on the far side
+    for topology in [Topology::PLANE, Topology::TORUS, Topology::KLEIN_BOTTLE, Topology::CROSS_SURFACE, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+        let mut board = CpuBoard::with_cells(70, 50, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        board.set_topology(topology);
+        for (x, y) in common::random_board(10, 10, 0.4, 5, Rule::CONWAY, Topology::PLANE).alive_cells() {
+            board.set(x+40, y+25, true);
+        }
+        let mut game_of_life = common::game_of_life(&device, &queue, &board);
+        for (n, toggle) in [(30, (66, 47)), (1, (20, 3)), (75, (35, 35)), (120, (0, 49))] {
+            game_of_life.compute_n(&device, &queue, n);
+            board.step_n(n);
+            assert_eq!(game_of_life.read_board(&device, &queue), board, "at generation {} with topology {}", game_of_life.generation(), topology);
+            game_of_life.toggle(toggle.0 as i32, toggle.1 as i32, &device, &queue);
+            board.toggle(toggle.0, toggle.1);
+        }
+
+        // Writing a new board wakes every tile again
+        board = common::random_board(70, 50, 0.1, 8, Rule::CONWAY, topology);
+        game_of_life.write_board(&queue, &board);
+        game_of_life.compute_n(&device, &queue, 40);
+        board.step_n(40);
+        assert_eq!(game_of_life.read_board(&device, &queue), board, "after writing a board with topology {}", topology);
+    }
+}
+
+#[test]
+fn tile_tracking_can_be_switched() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = common::random_board(48, 48, 0.3, 13, Rule::CONWAY, Topology::TORUS);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    for track_tiles in [false, true, false, true] {
+        game_of_life.set_tile_tracking(&queue, track_tiles);
+        game_of_life.compute_n(&device, &queue, 25);
+        board.step_n(25);
+        assert_eq!(game_of_life.read_board(&device, &queue), board, "with tile tracking {}", if track_tiles { "on" } else { "off" });
+    }
+}
@@ -0,0 +1,83 @@
+use bui::rect::SizeAndCenter;
+use bui_basic::containers::Fill;
+use wgpu_game_of_life::{graph::{Graph, GraphScale, POPULATION_COLOR}, stats::GenerationStats};
+
+fn stats(generation: u64, population: u64) -> GenerationStats {
+    GenerationStats {
+        generation,
+        population,
+        births: population/2,
+        deaths: population/4,
+        hash: 0,
+    }
+}
+
+fn graph(length: u64) -> Graph {
+    let mut graph = Graph::new(length);
+    // Spans x and y from 0 to 1
+    graph.fill(SizeAndCenter {
+        sx: 0.5,
+        sy: 0.5,
+        cx: 0.5,
+        cy: 0.5,
+    });
+    graph
+}
+
+fn generations(graph: &Graph) -> Vec<u64> {
+    graph.samples().iter().map(|stats| stats.generation).collect()
+}
+
+#[test]
+fn keeps_the_last_generations() {
+    let mut graph = graph(5);
+    for generation in 0..8 {
+        graph.push(stats(generation, 10));
+    }
+    assert_eq!(generations(&graph), vec![3, 4, 5, 6, 7]);
+
+    // Generations that weren't read back leave a gap
+    graph.push(stats(9, 10));
+    assert_eq!(generations(&graph), vec![5, 6, 7, 9]);
+}
+
+#[test]
+fn going_back_drops_later_generations() {
+    let mut graph = graph(10);
+    for generation in 0..6 {
+        graph.push(stats(generation, 10));
+    }
+    graph.truncate(3);
+    assert_eq!(generations(&graph), vec![0, 1, 2, 3]);
+    graph.push(stats(2, 10));
+    assert_eq!(generations(&graph), vec![0, 1, 2]);
+    graph.push(stats(0, 10));
+    assert_eq!(generations(&graph), vec![0]);
+}
+
+#[test]
+fn lines_fill_the_area() {
+    let mut graph = graph(3);
+    assert!(graph.lines().is_empty());
+    graph.push(stats(0, 0));
+    graph.push(stats(1, 40));
+    graph.push(stats(2, 20));
+    assert_eq!(graph.max(), 40);
+
+    let lines = graph.lines();
+    // Two segments for each of population, births and deaths
+    assert_eq!(lines.len(), 12);
+    let population: Vec<[f32; 2]> = lines.iter().filter(|vertex| vertex.color() == POPULATION_COLOR).map(|vertex| vertex.position()).collect();
+    assert_eq!(population, vec![[0.0, 0.0], [0.5, 1.0], [0.5, 1.0], [1.0, 0.5]]);
+}
+
+#[test]
+fn log_scale_lifts_small_numbers() {
+    let mut graph = graph(2);
+    graph.push(stats(0, 9));
+    graph.push(stats(1, 99));
+    let y = |graph: &Graph| graph.lines().iter().find(|vertex| vertex.color() == POPULATION_COLOR).unwrap().position()[1];
+    assert!((y(&graph)-9.0/99.0).abs() < 1e-6);
+    graph.set_scale(GraphScale::Log);
+    assert!((y(&graph)-0.5).abs() < 1e-6);
+}
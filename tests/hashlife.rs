@@ -0,0 +1,122 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, hashlife::HashLife, rule::Rule, topology::Topology};
+
+const GLIDER: [(u32, u32); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+/// Random soup in the middle of a board wide enough that nothing reaches its edges in `generations`
+fn soup(seed: u64, rule: Rule) -> CpuBoard {
+    let soup = common::random_board(16, 16, 0.4, seed, rule, Topology::PLANE);
+    let mut board = CpuBoard::new(176, 176);
+    board.set_rule(rule);
+    for (x, y) in soup.alive_cells() {
+        board.set(x+80, y+80, true);
+    }
+    board
+}
+
+#[test]
+fn round_trips_boards() {
+    let board = soup(1, Rule::CONWAY);
+    let hashlife = HashLife::from_board(&board).unwrap();
+    assert_eq!(hashlife.to_board(176, 176), board);
+    assert_eq!(hashlife.population(), board.population() as u64);
+    assert_eq!(hashlife.generation(), 0);
+}
+
+#[test]
+fn get_and_set() {
+    let mut hashlife = HashLife::new(Rule::CONWAY).unwrap();
+    for (x, y) in [(0, 0), (-1, -1), (1_000_000, -3), (-(1 << 40), 1 << 40)] {
+        assert!(!hashlife.get(x, y));
+        hashlife.set(x, y, true);
+        assert!(hashlife.get(x, y));
+    }
+    assert_eq!(hashlife.population(), 4);
+    hashlife.set(0, 0, false);
+    assert!(!hashlife.get(0, 0));
+    assert_eq!(hashlife.population(), 3);
+}
+
+#[test]
+fn jumps_match_cpu() {
+    for seed in [1, 2, 3] {
+        let mut board = soup(seed, Rule::CONWAY);
+        let mut hashlife = HashLife::from_board(&board).unwrap();
+        for k in 0..6 {
+            hashlife.jump_pow2(k).unwrap();
+            board.step_n(1 << k);
+            assert_eq!(hashlife.to_board(176, 176), board, "soup {} after jumping 2^{} to generation {}", seed, k, hashlife.generation());
+        }
+        assert_eq!(hashlife.generation(), 63);
+    }
+}
+
+#[test]
+fn steps_match_cpu() {
+    let mut board = soup(4, Rule::CONWAY);
+    let mut hashlife = HashLife::from_board(&board).unwrap();
+    for n in [1, 2, 3, 5, 7, 13, 0, 29] {
+        hashlife.step(n).unwrap();
+        board.step_n(n);
+        assert_eq!(hashlife.to_board(176, 176), board, "after {} more generations", n);
+    }
+}
+
+#[test]
+fn rules_match_cpu() {
+    for rule in ["B36/S23", "B3678/S34678", "B2/S", "B34/S34"] {
+        let rule = Rule::parse(rule).unwrap();
+        let mut board = soup(5, rule);
+        let mut hashlife = HashLife::from_board(&board).unwrap();
+        for n in [1, 4, 11] {
+            hashlife.step(n).unwrap();
+            board.step_n(n);
+            assert_eq!(hashlife.to_board(176, 176), board, "{} at generation {}", rule, hashlife.generation());
+        }
+    }
+}
+
+#[test]
+fn refuses_b0_rules() {
+    assert!(HashLife::new(Rule::parse("B0/S8").unwrap()).is_err());
+    let mut board = CpuBoard::new(4, 4);
+    board.set_rule(Rule::parse("B03/S23").unwrap());
+    assert!(HashLife::from_board(&board).is_err());
+}
+
+#[test]
+fn glider_flies_for_2_to_the_40() {
+    let mut hashlife = HashLife::from_board(&CpuBoard::with_cells(3, 3, &GLIDER)).unwrap();
+    hashlife.jump_pow2(40).unwrap();
+    assert_eq!(hashlife.generation(), 1 << 40);
+    assert_eq!(hashlife.population(), 5);
+    // Moves one cell diagonally every 4 generations
+    let offset = 1 << 38;
+    for (x, y) in GLIDER {
+        assert!(hashlife.get(x as i64+offset, y as i64+offset));
+    }
+}
+
+#[test]
+fn block_is_still_after_2_to_the_60() {
+    let block = CpuBoard::with_cells(2, 2, &[(0, 0), (1, 0), (0, 1), (1, 1)]);
+    let mut hashlife = HashLife::from_board(&block).unwrap();
+    hashlife.jump_pow2(60).unwrap();
+    assert_eq!(hashlife.to_board(2, 2), block);
+    assert_eq!(hashlife.population(), 4);
+    assert!(hashlife.jump_pow2(61).is_err());
+}
+
+#[test]
+fn garbage_collection_keeps_the_pattern() {
+    let mut board = soup(6, Rule::CONWAY);
+    let mut hashlife = HashLife::from_board(&board).unwrap();
+    hashlife.step(20).unwrap();
+    let nodes = hashlife.node_count();
+    hashlife.collect_garbage();
+    assert!(hashlife.node_count() < nodes);
+    hashlife.step(20).unwrap();
+    board.step_n(40);
+    assert_eq!(hashlife.to_board(176, 176), board);
+}
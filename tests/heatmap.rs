@@ -0,0 +1,46 @@
+mod common;
+
+use wgpu_game_of_life::{heatmap::{decay, heat_colour}, rule::Rule, topology::Topology};
+
+#[test]
+fn heat_halves_every_half_life() {
+    assert_eq!(decay(None), 1.0);
+    assert!((decay(Some(10)).powi(10)-0.5).abs() < 1e-5);
+    assert_eq!(decay(Some(1)), 0.5);
+    assert_eq!(heat_colour(0.0), [1.0, 1.0, 1.0, 1.0]);
+    assert!(heat_colour(1e9).iter().zip([0.1, 0.0, 0.0, 1.0]).all(|(a, b)| (a-b).abs() < 1e-3));
+}
+
+#[test]
+fn heat_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = common::random_board(40, 35, 0.3, 5, Rule::CONWAY, Topology::TORUS);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    let mut untracked_game_of_life = common::game_of_life(&device, &queue, &board);
+    untracked_game_of_life.set_tile_tracking(&queue, false);
+    for game_of_life in [&mut game_of_life, &mut untracked_game_of_life] {
+        game_of_life.set_heatmap(&device, &queue, true).unwrap();
+    }
+
+    let mut heat = vec![0.0; (board.width()*board.height()) as usize];
+    for _ in 0..12 {
+        let before = board.clone();
+        board.step();
+        for y in 0..board.height() {
+            for x in 0..board.width() {
+                if before.get(x, y) != board.get(x, y) {
+                    heat[(y*board.width()+x) as usize] += 1.0;
+                }
+            }
+        }
+    }
+    for game_of_life in [&mut game_of_life, &mut untracked_game_of_life] {
+        game_of_life.compute_n(&device, &queue, 12);
+        assert_eq!(game_of_life.read_heat(&device, &queue), heat);
+        game_of_life.reset_heat(&queue);
+        assert!(game_of_life.read_heat(&device, &queue).iter().all(|heat| *heat == 0.0));
+    }
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::Storage, history::{History, Snapshot, SnapshotBuilder}, rule::Rule, topology::Topology};
+
+fn board(generation: u64) -> Snapshot {
+    Snapshot::new(&common::random_board(20, 20, 0.3, generation+1, Rule::CONWAY, Topology::PLANE), generation)
+}
+
+#[test]
+fn snapshots_round_trip() {
+    let mut full = CpuBoard::new(9, 4);
+    for y in 0..4 {
+        for x in 0..9 {
+            full.set(x, y, true);
+        }
+    }
+    for board in [CpuBoard::new(1, 1), CpuBoard::new(300, 200), full, common::random_board(123, 45, 0.4, 6, Rule::CONWAY, Topology::PLANE)] {
+        let snapshot = Snapshot::new(&board, 17);
+        assert_eq!(snapshot.to_board(), board);
+        assert_eq!(snapshot.generation(), 17);
+    }
+}
+
+#[test]
+fn sparse_snapshots_are_small() {
+    let board = CpuBoard::with_cells(4096, 4096, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (4000, 4000)]);
+    let snapshot = Snapshot::new(&board, 0);
+    assert!(snapshot.size() < 256, "{} bytes", snapshot.size());
+    assert_eq!(snapshot.to_board(), board);
+}
+
+#[test]
+fn undo_and_redo_swap_boards() {
+    let mut history = History::new(1 << 20);
+    assert!(!history.can_undo());
+    history.record(board(0));
+    history.record(board(1));
+
+    assert_eq!(history.undo(board(2)), Some(board(1)));
+    assert_eq!(history.undo(board(1)), Some(board(0)));
+    assert_eq!(history.undo(board(0)), None);
+    assert_eq!(history.redo(board(0)), Some(board(1)));
+    assert_eq!(history.redo(board(1)), Some(board(2)));
+    assert!(!history.can_redo());
+
+    // A new change forgets what was undone
+    assert_eq!(history.undo(board(2)), Some(board(1)));
+    history.record(board(1));
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(board(5)), Some(board(1)));
+    assert_eq!(history.undo(board(1)), Some(board(0)));
+}
+
+#[test]
+fn back_undoes_past_a_generation() {
+    let mut history = History::new(1 << 20);
+    for generation in [0, 0, 3, 10] {
+        history.record(board(generation));
+    }
+    assert!(history.reaches(0));
+    assert_eq!(history.computes_from(12, 9), Some(3));
+    assert_eq!(history.computes_from(12, 15), Some(12));
+    assert_eq!(history.back_to(board(12), 9), Some(board(3)));
+    assert_eq!(history.back_to(board(3), 0), Some(board(0)));
+    assert_eq!(history.back_to(board(0), 0), Some(board(0)));
+    assert_eq!(history.back_to(board(0), 0), None);
+
+    // Everything undone on the way can be redone
+    for generation in [0, 3, 10, 12] {
+        assert_eq!(history.redo(board(0)), Some(board(generation)));
+    }
+}
+
+#[test]
+fn budget_drops_oldest_snapshots() {
+    // Empty boards all take up the same space
+    let empty = |generation| Snapshot::new(&CpuBoard::new(20, 20), generation);
+    let size = empty(0).size();
+    let mut history = History::new(size*3);
+    for generation in 0..5 {
+        history.record(empty(generation));
+    }
+    assert_eq!(history.size(), size*3);
+    assert_eq!(history.undo(empty(5)), Some(empty(4)));
+    assert_eq!(history.undo(empty(4)), Some(empty(3)));
+    assert_eq!(history.undo(empty(3)), Some(empty(2)));
+    assert_eq!(history.undo(empty(2)), None);
+
+    let mut off = History::new(0);
+    assert!(!off.is_enabled());
+    off.record(empty(0));
+    assert!(!off.can_undo());
+}
+
+#[test]
+fn snapshots_build_from_words() {
+    // Widths ending partway through a word, with the bits past the edge set
+    for width in [1, 31, 32, 33, 70] {
+        let board = common::random_board(width, 9, 0.4, width as u64, Rule::CONWAY, Topology::PLANE);
+        let mut builder = SnapshotBuilder::new(width, 9, 4);
+        for y in 0..9 {
+            for start in (0..width).step_by(32) {
+                let cells = (width-start).min(32);
+                let past_edge = if cells == 32 { 0 } else { !0u32 << cells };
+                let word = (0..cells).filter(|i| board.get(start+i, y)).fold(past_edge, |word, i| word | 1 << i);
+                builder.push_bits(word, cells);
+            }
+        }
+        assert_eq!(builder.finish(), Snapshot::new(&board, 4));
+    }
+}
+
+#[test]
+fn storage_snapshots_match_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = common::random_board(70, 33, 0.3, 8, Rule::CONWAY, Topology::TORUS);
+    let texture = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    let packed = Storage::Packed(common::packed_game_of_life(&device, &queue, &board));
+    for game_of_life in [texture, packed] {
+        assert_eq!(game_of_life.snapshot(&device, &queue), Some(Snapshot::new(&board, 0)));
+    }
+}
+
+#[test]
+fn storage_restores_snapshots() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = common::random_board(40, 30, 0.4, 21, Rule::CONWAY, Topology::TORUS);
+    let mut game_of_life = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    game_of_life.compute_n(&device, &queue, 5).unwrap();
+    let snapshot = game_of_life.snapshot(&device, &queue).unwrap();
+    let expected = game_of_life.read_board(&device, &queue);
+    game_of_life.compute_n(&device, &queue, 20).unwrap();
+    game_of_life.toggle(3, 3, &device, &queue).unwrap();
+
+    game_of_life.restore(&queue, &snapshot);
+    assert_eq!(game_of_life.generation(), 5);
+    assert_eq!(game_of_life.read_board(&device, &queue), expected);
+    // Computing on from the restored board gives the same boards as before
+    let mut board = board;
+    board.step_n(25);
+    game_of_life.compute_n(&device, &queue, 20).unwrap();
+    assert_eq!(game_of_life.read_board(&device, &queue), Some(board));
+}
+
+#[test]
+fn forward_redoes_up_to_a_generation() {
+    let mut history = History::new(1 << 20);
+    for generation in [0, 4, 4, 9] {
+        history.record(board(generation));
+    }
+    assert_eq!(history.generation_range(), Some((0, 9)));
+    assert_eq!(history.back_to(board(12), 0), Some(board(0)));
+    // The board at generation 0 is the current one now, not a snapshot
+    assert_eq!(history.generation_range(), Some((4, 12)));
+
+    // Nothing to redo before generation 4, the board just computes on from where it is
+    assert_eq!(history.computes_from(0, 3), Some(0));
+    assert_eq!(history.computes_from(0, 100), Some(12));
+    assert_eq!(history.forward_to(board(0), 3), None);
+    assert_eq!(history.forward_to(board(0), 7), Some(board(4)));
+    assert_eq!(history.forward_to(board(4), 100), Some(board(12)));
+    assert!(!history.can_redo());
+    assert_eq!(history.undo(board(12)), Some(board(9)));
+}
+
+#[test]
+fn snapshots_rebuild_from_runs() {
+    let snapshot = board(3);
+    assert_eq!(Snapshot::from_runs(20, 20, 3, snapshot.runs().to_vec()), Ok(snapshot.clone()));
+    assert!(Snapshot::from_runs(20, 21, 3, snapshot.runs().to_vec()).is_err());
+    assert!(Snapshot::from_runs(20, 20, 3, vec![0x80]).is_err());
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::{GameOfLife, Storage}, packed_board::PackedGameOfLife, rule::Rule, topology::{Edge, Topology}};
+
+fn assert_packed_matches_cpu(board: CpuBoard, generations: u32) {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = common::packed_game_of_life(&device, &queue, &board);
+    assert_eq!(game_of_life.read_board(&device, &queue), board, "board didn't survive a round trip to the GPU");
+
+    let mut board = board;
+    for generation in 1..=generations {
+        game_of_life.compute(&device, &queue);
+        board.step();
+        assert_eq!(game_of_life.read_board(&device, &queue), board, "packed GPU board and CPU disagree at generation {} on a {}x{} board with topology {}", generation, board.width(), board.height(), board.topology());
+    }
+}
+
+#[test]
+fn toggle_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = CpuBoard::new(40, 5);
+    let mut game_of_life = common::packed_game_of_life(&device, &queue, &board);
+    for (x, y) in [(0, 0), (39, 4), (31, 2), (32, 2), (0, 0)] {
+        game_of_life.toggle(x as i32, y as i32, &device, &queue);
+        board.toggle(x, y);
+        assert_eq!(game_of_life.read_board(&device, &queue), board);
+    }
+}
+
+#[test]
+fn glider_matches_cpu() {
+    // Crosses from one word to the next
+    assert_packed_matches_cpu(CpuBoard::with_cells(70, 20, &[(26, 0), (27, 1), (25, 2), (26, 2), (27, 2)]), 60);
+}
+
+#[test]
+fn rules_match_cpu() {
+    for rule in ["B3/S23", "B36/S23", "B3678/S34678", "B2/S", "B/S012345678", "B012345678/S"] {
+        assert_packed_matches_cpu(common::random_board(77, 29, 0.4, 7, Rule::parse(rule).unwrap(), Topology::PLANE), 10);
+    }
+}
+
+#[test]
+fn topologies_match_cpu() {
+    let edges = [Edge::Dead, Edge::Wrap, Edge::Flip, Edge::Mirror, Edge::Alive];
+    for x in edges {
+        for y in edges {
+            assert_packed_matches_cpu(common::random_board(45, 17, 0.35, 42, Rule::CONWAY, Topology { x, y }), 8);
+        }
+    }
+}
+
+#[test]
+fn word_edges_match_cpu() {
+    // Widths around multiples of 32, where the last word of a row is full, nearly empty or nearly full
+    for width in [31, 32, 33, 63, 64, 65, 100] {
+        for topology in [Topology::PLANE, Topology::TORUS, Topology::CROSS_SURFACE, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+            assert_packed_matches_cpu(common::random_board(width, 19, 0.4, width as u64, Rule::CONWAY, topology), 6);
+        }
+    }
+}
+
+#[test]
+fn tiny_boards_match_cpu() {
+    for (width, height) in [(1, 1), (1, 5), (5, 1), (2, 2)] {
+        for topology in [Topology::PLANE, Topology::TORUS, Topology::CROSS_SURFACE, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+            assert_packed_matches_cpu(common::random_board(width, height, 0.5, 3, Rule::CONWAY, topology), 4);
+        }
+    }
+}
+
+#[test]
+fn matches_texture_board() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = common::random_board(300, 200, 0.3, 5, Rule::CONWAY, Topology::KLEIN_BOTTLE);
+    let mut texture = common::game_of_life(&device, &queue, &board);
+    let mut packed = common::packed_game_of_life(&device, &queue, &board);
+    for n in [1, 10, 100] {
+        texture.compute_n(&device, &queue, n);
+        packed.compute_n(&device, &queue, n);
+        assert_eq!(packed.read_board(&device, &queue), texture.read_board(&device, &queue));
+    }
+    assert_eq!(packed.generation(), texture.generation());
+}
+
+#[test]
+fn storage_picks_packed_past_texture_limits() {
+    let limits = wgpu::Limits::default();
+    assert!(GameOfLife::check_size(8192, 8192, &limits).is_ok());
+    assert!(GameOfLife::check_size(8193, 100, &limits).is_err());
+    assert!(PackedGameOfLife::check_size(32768, 32768, &limits).is_ok());
+    assert!(PackedGameOfLife::check_size(65536, 65536, &limits).is_err());
+    assert!(Storage::check_size(32768, 32768, &limits).is_ok());
+    assert!(Storage::check_size(65536, 65536, &limits).is_err());
+}
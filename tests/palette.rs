@@ -0,0 +1,32 @@
+use wgpu_game_of_life::palette::AgePalette;
+
+#[test]
+fn colours_fade_over_the_span() {
+    let palette = AgePalette {
+        name: "Test",
+        young: [1.0, 0.0, 0.0, 1.0],
+        old: [0.0, 0.0, 1.0, 1.0],
+        alive_span: 10.0,
+        dead: [0.0, 1.0, 0.0, 1.0],
+        background: [0.0, 0.0, 0.0, 1.0],
+        dead_span: 4.0,
+    };
+    assert_eq!(palette.colour(true, 0), palette.young);
+    assert_eq!(palette.colour(true, 5), [0.5, 0.0, 0.5, 1.0]);
+    assert_eq!(palette.colour(true, 10), palette.old);
+    assert_eq!(palette.colour(true, 1000), palette.old);
+    assert_eq!(palette.colour(false, 0), palette.dead);
+    assert_eq!(palette.colour(false, 1), [0.0, 0.75, 0.0, 1.0]);
+    // Cells dead since the board was written are as old as can be
+    assert_eq!(palette.colour(false, 1 << 31), palette.background);
+}
+
+#[test]
+fn presets_have_distinct_names() {
+    for (i, a) in AgePalette::PRESETS.iter().enumerate() {
+        assert!(a.alive_span >= 1.0 && a.dead_span >= 1.0);
+        for b in AgePalette::PRESETS.iter().skip(i+1) {
+            assert_ne!(a.name, b.name);
+        }
+    }
+}
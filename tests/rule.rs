@@ -0,0 +1,58 @@
+use wgpu_game_of_life::{cpu_board::CpuBoard, rule::Rule};
+
+#[test]
+fn parse_bs_notation() {
+    assert_eq!(Rule::parse("B3/S23"), Ok(Rule::CONWAY));
+    assert_eq!(Rule::parse("b3/s23"), Ok(Rule::CONWAY));
+    assert_eq!(Rule::parse("B3S23"), Ok(Rule::CONWAY));
+    assert_eq!(Rule::parse(" S23 / B3 "), Ok(Rule::CONWAY));
+    assert_eq!(Rule::parse("B36/S23"), Ok(Rule::new(&[3, 6], &[2, 3])));
+    assert_eq!(Rule::parse("B3678/S34678"), Ok(Rule::new(&[3, 6, 7, 8], &[3, 4, 6, 7, 8])));
+    assert_eq!(Rule::parse("B2/S"), Ok(Rule::new(&[2], &[])));
+}
+
+#[test]
+fn parse_sb_notation() {
+    assert_eq!(Rule::parse("23/3"), Ok(Rule::CONWAY));
+    assert_eq!(Rule::parse("23/36"), Ok(Rule::new(&[3, 6], &[2, 3])));
+    assert_eq!(Rule::parse("/2"), Ok(Rule::new(&[2], &[])));
+}
+
+#[test]
+fn parse_errors() {
+    assert!(Rule::parse("").is_err());
+    assert!(Rule::parse("B3").is_err());
+    assert!(Rule::parse("S23").is_err());
+    assert!(Rule::parse("B3/S239").is_err());
+    assert!(Rule::parse("B3/B4/S23").is_err());
+    assert!(Rule::parse("23").is_err());
+    assert!(Rule::parse("Life").is_err());
+}
+
+#[test]
+fn display_round_trips() {
+    for rule in ["B3/S23", "B36/S23", "B3678/S34678", "B2/S", "B/S012345678"] {
+        assert_eq!(Rule::parse(rule).unwrap().to_string(), rule);
+    }
+}
+
+#[test]
+fn seeds_cells_never_survive() {
+    let mut board = CpuBoard::with_cells(6, 6, &[(2, 2), (3, 2)]);
+    board.set_rule(Rule::parse("B2/S").unwrap());
+    board.step();
+    assert_eq!(board, CpuBoard::with_cells(6, 6, &[(2, 1), (3, 1), (2, 3), (3, 3)]));
+}
+
+#[test]
+fn highlife_births_on_six() {
+    // The centre has six neighbours: it's only born under HighLife
+    let cells = [(0, 0), (1, 0), (2, 0), (0, 2), (1, 2), (2, 2)];
+    let mut conway = CpuBoard::with_cells(3, 3, &cells);
+    let mut highlife = conway.clone();
+    highlife.set_rule(Rule::parse("B36/S23").unwrap());
+    conway.step();
+    highlife.step();
+    assert!(!conway.get(1, 1));
+    assert!(highlife.get(1, 1));
+}
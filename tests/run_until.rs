@@ -0,0 +1,113 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, run_until::{RunUntil, StopCondition}, stats::GenerationStats, topology::Topology};
+
+/// Steps `board` until `run_until` stops it, returning the generation it stopped at and why
+fn run(board: &mut CpuBoard, run_until: &mut RunUntil, limit: u64) -> Option<(u64, String)> {
+    for generation in 0..=limit {
+        if generation > 0 {
+            board.step();
+        }
+        if let Some(reason) = run_until.check(&GenerationStats::of_board(board, generation)) {
+            return Some((generation, reason))
+        }
+    }
+    None
+}
+
+#[test]
+fn conditions_parse() {
+    for (text, condition) in [
+        ("gen 1103", StopCondition::Generation(1103)),
+        ("1103", StopCondition::Generation(1103)),
+        ("Generation 7", StopCondition::Generation(7)),
+        ("pop > 500", StopCondition::PopulationAbove(500)),
+        ("pop<10", StopCondition::PopulationBelow(10)),
+        ("population < 3", StopCondition::PopulationBelow(3)),
+        (" Static ", StopCondition::Static),
+        ("cycle", StopCondition::Cycle),
+    ] {
+        assert_eq!(text.parse::<StopCondition>(), Ok(condition), "{}", text);
+        assert_eq!(condition.to_string().parse::<StopCondition>(), Ok(condition));
+    }
+    for text in ["", "pop", "pop = 3", "gen -1", "forever", "pop > lots"] {
+        assert!(text.parse::<StopCondition>().is_err(), "{}", text);
+    }
+}
+
+#[test]
+fn stops_on_population() {
+    // Diehard dies out at generation 130
+    let diehard = [(6, 0), (0, 1), (1, 1), (1, 2), (5, 2), (6, 2), (7, 2)];
+    let mut board = CpuBoard::with_cells(40, 40, &diehard.map(|(x, y)| (x+15, y+18)));
+    let (generation, _) = run(&mut board, &mut RunUntil::new(StopCondition::PopulationBelow(1)), 200).unwrap();
+    assert_eq!(generation, 130);
+
+    let mut board = CpuBoard::with_cells(40, 40, &diehard.map(|(x, y)| (x+15, y+18)));
+    let (generation, _) = run(&mut board, &mut RunUntil::new(StopCondition::PopulationAbove(7)), 200).unwrap();
+    assert_eq!(generation, 1);
+    assert!(board.population() > 7);
+}
+
+#[test]
+fn stops_on_static_boards() {
+    // A pre-block settles into a block after a generation
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 4), (5, 4), (4, 5)]);
+    let (generation, reason) = run(&mut board, &mut RunUntil::new(StopCondition::Static), 10).unwrap();
+    assert_eq!(generation, 2);
+    assert!(reason.contains("since generation 1"), "{}", reason);
+
+    // A blinker never stops changing
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 5), (5, 5), (6, 5)]);
+    assert_eq!(run(&mut board, &mut RunUntil::new(StopCondition::Static), 10), None);
+}
+
+#[test]
+fn stops_on_cycles() {
+    let mut board = CpuBoard::with_cells(10, 10, &[(4, 5), (5, 5), (6, 5)]);
+    let (generation, reason) = run(&mut board, &mut RunUntil::new(StopCondition::Cycle), 10).unwrap();
+    assert_eq!(generation, 2);
+    assert!(reason.contains("every 2 generations since generation 0"), "{}", reason);
+
+    // A glider on a 12x12 torus comes back where it started after 48 generations
+    let mut board = CpuBoard::with_cells(12, 12, &[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    board.set_topology(Topology::TORUS);
+    let (generation, reason) = run(&mut board, &mut RunUntil::new(StopCondition::Cycle), 100).unwrap();
+    assert_eq!(generation, 48);
+    assert!(reason.contains("every 48 generations since generation 0"), "{}", reason);
+}
+
+#[test]
+fn stops_at_a_generation() {
+    let mut run_until = RunUntil::new(StopCondition::Generation(5));
+    assert!(!run_until.needs_stats());
+    let mut board = CpuBoard::new(4, 4);
+    assert_eq!(run(&mut board, &mut run_until, 10).map(|(generation, _)| generation), Some(5));
+}
+
+#[test]
+fn gpu_stats_stop_runs() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let diehard = [(6, 0), (0, 1), (1, 1), (1, 2), (5, 2), (6, 2), (7, 2)];
+    let board = CpuBoard::with_cells(40, 40, &diehard.map(|(x, y)| (x+15, y+18)));
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    let mut packed_game_of_life = common::packed_game_of_life(&device, &queue, &board);
+    game_of_life.compute_n(&device, &queue, 140);
+    packed_game_of_life.compute_n(&device, &queue, 140);
+    device.poll(wgpu::Maintain::Wait);
+    game_of_life.poll_stats(&device);
+    packed_game_of_life.poll_stats(&device);
+
+    for stats in [game_of_life.take_stats(), packed_game_of_life.take_stats()] {
+        let mut run_until = RunUntil::new(StopCondition::PopulationBelow(1));
+        let stopped = stats.iter().find_map(|stats| run_until.check(stats).map(|_| stats.generation));
+        assert_eq!(stopped, Some(130));
+        // Once it's died out, the board stays empty
+        let mut run_until = RunUntil::new(StopCondition::Static);
+        let stopped = stats.iter().find_map(|stats| run_until.check(stats).map(|_| stats.generation));
+        assert_eq!(stopped, Some(131));
+    }
+}
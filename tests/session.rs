@@ -0,0 +1,45 @@
+mod common;
+
+use wgpu_game_of_life::{history::Snapshot, rule::Rule, session::{Bookmark, Session}, topology::Topology};
+
+fn path(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("wgpu_game_of_life_{}_{}.session", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn bookmarks_round_trip() {
+    let path = path("round_trip");
+    let session = Session::new(path.clone(), Some((37, 29)), Rule::parse("B36/S23").unwrap(), Topology::KLEIN_BOTTLE);
+    assert_eq!(session.load(), Ok(Vec::new()));
+
+    let bookmarks = vec![
+        Bookmark {
+            name: "before collision".to_string(),
+            snapshot: Snapshot::new(&common::random_board(37, 29, 0.3, 1, Rule::CONWAY, Topology::PLANE), 1103),
+        },
+        Bookmark {
+            name: String::new(),
+            snapshot: Snapshot::new(&common::random_board(37, 29, 0.1, 2, Rule::CONWAY, Topology::PLANE), 0),
+        },
+    ];
+    session.save(&bookmarks).unwrap();
+    assert_eq!(session.load(), Ok(bookmarks));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn sessions_are_for_one_board() {
+    let path = path("one_board");
+    Session::new(path.clone(), Some((30, 30)), Rule::CONWAY, Topology::PLANE).save(&[]).unwrap();
+    assert_eq!(Session::new(path.clone(), Some((30, 30)), Rule::CONWAY, Topology::PLANE).load(), Ok(Vec::new()));
+    assert!(Session::new(path.clone(), Some((30, 31)), Rule::CONWAY, Topology::PLANE).load().is_err());
+    assert!(Session::new(path.clone(), Some((30, 30)), Rule::parse("B2/S").unwrap(), Topology::PLANE).load().is_err());
+    assert!(Session::new(path.clone(), Some((30, 30)), Rule::CONWAY, Topology::TORUS).load().is_err());
+    assert!(Session::new(path.clone(), None, Rule::CONWAY, Topology::PLANE).load().is_err());
+
+    std::fs::write(&path, "something else").unwrap();
+    assert!(Session::new(path.clone(), Some((30, 30)), Rule::CONWAY, Topology::PLANE).load().is_err());
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,23 @@
+// Parses and validates the shaders like wgpu does when a board is made, so they're checked without a GPU
+
+fn validate(name: &str, source: &str) -> usize {
+    let module = naga::front::wgsl::parse_str(source).unwrap_or_else(|err| panic!("{} doesn't parse:\n{}", name, err.emit_to_string(source)));
+    naga::valid::Validator::new(naga::valid::ValidationFlags::all(), naga::valid::Capabilities::empty())
+        .validate(&module)
+        .unwrap_or_else(|err| panic!("{} doesn't validate: {:?}", name, err));
+    module.entry_points.len()
+}
+
+#[test]
+fn game_of_life_shader_validates() {
+    // Boards are stored in either format, replacing r32uint like board_format_name
+    for format in ["r32uint", "r8uint"] {
+        let source = include_str!("../src/game_of_life.wgsl").replace("r32uint", format);
+        assert!(validate(&format!("game_of_life.wgsl with {}", format), &source) > 0);
+    }
+}
+
+#[test]
+fn overlay_shader_validates() {
+    assert!(validate("overlay.wgsl", include_str!("../src/overlay.wgsl")) > 0);
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use wgpu_game_of_life::{chunked_board::ChunkedGameOfLife, cpu_board::CpuBoard, game::Storage, rule::Rule, soup::{Region, Soup, SoupArea, Symmetry, transform}, stats::board_hash};
+
+#[test]
+fn parses_soups() {
+    assert_eq!(Soup::parse("50%", 7), Ok(Soup { density: 50.0, seed: 7, symmetry: Symmetry::C1, area: SoupArea::Board }));
+    assert_eq!(Soup::parse(" 12.5 % seed 0x10 ", 7), Ok(Soup { density: 12.5, seed: 16, symmetry: Symmetry::C1, area: SoupArea::Board }));
+    let soup = Soup::parse("30% seed 123 in 4,5 16x8", 7).unwrap();
+    assert_eq!(soup.area, SoupArea::Region(Region { x: 4, y: 5, width: 16, height: 8 }));
+    assert_eq!(Soup::parse(&soup.to_string(), 0), Ok(soup));
+    let soup = Soup::parse("30% D2y seed 5 in 16x16", 7).unwrap();
+    assert_eq!((soup.symmetry, soup.area), (Symmetry::D2Y, SoupArea::Centre { width: 16, height: 16 }));
+    assert_eq!(Soup::parse(&soup.to_string(), 0), Ok(soup));
+    assert!(Soup::parse("101%", 7).is_err());
+    assert!(Soup::parse("50% seed x", 7).is_err());
+    assert!(Soup::parse("50% in 4,5", 7).is_err());
+    let soup = Soup::parse("50% in -10,-3 8x8", 7).unwrap();
+    assert_eq!(soup.area, SoupArea::Region(Region { x: -10, y: -3, width: 8, height: 8 }));
+    assert_eq!(Soup::parse(&soup.to_string(), 0), Ok(soup));
+    assert!(Soup::parse("50% in 9223372036854775807,0 2x2", 7).is_err());
+}
+
+#[test]
+fn density_matches_soup() {
+    let mut empty = CpuBoard::new(64, 64);
+    Soup::parse("0%", 1).unwrap().fill(&mut empty);
+    assert_eq!(empty.population(), 0);
+    let mut full = CpuBoard::new(64, 64);
+    Soup::parse("100%", 1).unwrap().fill(&mut full);
+    assert_eq!(full.population(), 64*64);
+
+    let mut half = CpuBoard::new(128, 128);
+    Soup::parse("50%", 1).unwrap().fill(&mut half);
+    let fraction = half.population() as f64/(128*128) as f64;
+    assert!((fraction-0.5).abs() < 0.02, "{} of cells alive", fraction);
+
+    let mut other = CpuBoard::new(128, 128);
+    Soup::parse("50%", 2).unwrap().fill(&mut other);
+    assert_ne!(half, other);
+}
+
+#[test]
+fn regions_clip_to_the_board() {
+    let soup = Soup::parse("100% in -10,-3 14x8", 7).unwrap();
+    assert_eq!(soup.clip(40, 30), ([0, 0], [4, 5]));
+    let mut board = CpuBoard::new(40, 30);
+    soup.fill(&mut board);
+    assert_eq!(board.population(), 4*5);
+    assert!(Soup::parse("50% C2 in -10,-3 14x8", 7).unwrap().check(40, 30).is_err());
+    assert_eq!(Soup::parse("50% in 50,50 8x8", 7).unwrap().clip(40, 30), ([40, 30], [40, 30]));
+}
+
+#[test]
+fn unbounded_soups_match_bounded_ones() {
+    let soup = Soup::parse("50% seed 4 D4 in 6,9 16x12", 0).unwrap();
+    let region = match soup.area {
+        SoupArea::Region(region) => region,
+        _ => unreachable!(),
+    };
+    let mut board = CpuBoard::new(40, 30);
+    soup.fill(&mut board);
+    for y in 0..region.height {
+        for x in 0..region.width {
+            assert_eq!(soup.alive_in_region(&region, x, y), board.get(6+x, 9+y));
+        }
+    }
+    // Left of and above the origin, the symmetry still holds
+    let region = Region { x: -20, y: -7, ..region };
+    for y in 0..region.height {
+        for x in 0..region.width {
+            assert_eq!(soup.alive_in_region(&region, x, y), soup.alive_in_region(&region, region.width-1-x, region.height-1-y));
+        }
+    }
+}
+
+#[test]
+fn soups_have_their_symmetry() {
+    for symmetry in Symmetry::ALL {
+        for size in [15, 16] {
+            let soup = Soup::parse(&format!("50% seed 3 {} in {}x{}", symmetry, size, size), 0).unwrap();
+            let mut board = CpuBoard::new(40, 30);
+            soup.check(board.width(), board.height()).unwrap();
+            soup.fill(&mut board);
+            let (min, max) = soup.clip(board.width(), board.height());
+            assert_eq!((min, max), ([12, 7], [12+size, 7+size]));
+            assert!(board.alive_cells().into_iter().all(|(x, y)| x >= min[0] && x < max[0] && y >= min[1] && y < max[1]));
+            for i in (0..8).filter(|i| symmetry.transforms() >> i & 1 == 1) {
+                for y in 0..size {
+                    for x in 0..size {
+                        let [tx, ty] = transform(i, [x, y], [size, size]);
+                        assert_eq!(board.get(min[0]+x, min[1]+y), board.get(min[0]+tx, min[1]+ty), "{} soup isn't symmetric under transform {}", symmetry, i);
+                    }
+                }
+            }
+        }
+    }
+    assert!(Soup::parse("50% C4 in 16x8", 0).unwrap().check(40, 30).is_err());
+    assert!(Soup::parse("50% D4 in 16x8", 0).unwrap().check(40, 30).is_ok());
+    assert!(Soup::parse("50% C2 in 50x8", 0).unwrap().check(40, 30).is_err());
+    assert!(Soup::parse("50% in 50x8", 0).unwrap().check(40, 30).is_ok());
+}
+
+#[test]
+fn gpu_soup_matches_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut board = CpuBoard::new(70, 45);
+    board.set(0, 0, true);
+    board.set(69, 44, true);
+    let mut texture = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    let mut packed = Storage::Packed(common::packed_game_of_life(&device, &queue, &board));
+    for soup in ["35% seed 99 in 10,5 40x50", "60% seed 18446744073709551615", "40% seed 8 C4 in 21x21", "45% seed 8 D8 in 30,2 20x20", "50% seed 1 D2x in 31x17"] {
+        let soup = Soup::parse(soup, 0).unwrap();
+        soup.fill(&mut board);
+        for game_of_life in [&mut texture, &mut packed] {
+            game_of_life.fill_soup(&device, &queue, &soup).unwrap();
+            assert_eq!(game_of_life.read_board(&device, &queue).unwrap(), board, "board differs after {}", soup);
+            device.poll(wgpu::Maintain::Wait);
+            game_of_life.poll_stats(&device);
+            let stats = game_of_life.stats().unwrap();
+            assert_eq!((stats.population, stats.hash), (board.population() as u64, board_hash(&board)));
+        }
+    }
+}
+
+#[test]
+fn unbounded_soups_fill_past_the_origin() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let mut game_of_life = ChunkedGameOfLife::new(&device, wgpu::TextureFormat::Rgba8UnormSrgb, Rule::CONWAY, 1.0, 1.0);
+    game_of_life.construct(&queue);
+    let mut chunked = Storage::Chunked(game_of_life);
+    let soup = Soup::parse("40% seed 21 C4 in -30,-50 40x40", 0).unwrap();
+    chunked.fill_soup(&device, &queue, &soup).unwrap();
+    let region = match soup.area {
+        SoupArea::Region(region) => region,
+        _ => unreachable!(),
+    };
+    let mut expected = CpuBoard::new(region.width, region.height);
+    for y in 0..region.height {
+        for x in 0..region.width {
+            expected.set(x, y, soup.alive_in_region(&region, x, y));
+        }
+    }
+    let game_of_life = match &chunked {
+        Storage::Chunked(game_of_life) => game_of_life,
+        _ => unreachable!(),
+    };
+    assert_eq!(game_of_life.read_region(&device, &queue, region.x, region.y, region.width, region.height), expected);
+    assert!(chunked.fill_soup(&device, &queue, &Soup::parse("40% C4 in 0,0 8x4", 0).unwrap()).is_err());
+    assert!(chunked.fill_soup(&device, &queue, &Soup::parse("40% in 8x8", 0).unwrap()).is_err());
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, cycles::{Cycle, CycleDetector}, rule::Rule, stats::{GenerationStats, board_hash}, topology::Topology};
+
+/// Steps `board`, returning the stats of the generation it steps to
+fn step(board: &mut CpuBoard, generation: u64) -> GenerationStats {
+    let before = board.clone();
+    board.step();
+    let mut stats = GenerationStats {
+        generation,
+        population: board.population() as u64,
+        births: 0,
+        deaths: 0,
+        hash: board_hash(board),
+    };
+    for y in 0..board.height() {
+        for x in 0..board.width() {
+            match (before.get(x, y), board.get(x, y)) {
+                (false, true) => stats.births += 1,
+                (true, false) => stats.deaths += 1,
+                _ => {},
+            }
+        }
+    }
+    stats
+}
+
+#[test]
+fn stats_match_cpu() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    // Big enough to span several workgroups, wrapping so cells are born and die across the edges
+    let mut board = common::random_board(70, 45, 0.3, 11, Rule::CONWAY, Topology::TORUS);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    let mut untracked_game_of_life = common::game_of_life(&device, &queue, &board);
+    untracked_game_of_life.set_tile_tracking(&queue, false);
+    let mut packed_game_of_life = common::packed_game_of_life(&device, &queue, &board);
+
+    // Writing the board comes first
+    let mut expected = vec![GenerationStats {
+        generation: 0,
+        population: board.population() as u64,
+        births: 0,
+        deaths: 0,
+        hash: board_hash(&board),
+    }];
+    for generation in 1..=5 {
+        expected.push(step(&mut board, generation));
+    }
+    game_of_life.compute_n(&device, &queue, 5);
+    untracked_game_of_life.compute_n(&device, &queue, 5);
+    packed_game_of_life.compute_n(&device, &queue, 5);
+    for generation in 6..=8 {
+        expected.push(step(&mut board, generation));
+        game_of_life.compute(&device, &queue);
+        untracked_game_of_life.compute(&device, &queue);
+        packed_game_of_life.compute(&device, &queue);
+    }
+
+    device.poll(wgpu::Maintain::Wait);
+    game_of_life.poll_stats(&device);
+    untracked_game_of_life.poll_stats(&device);
+    packed_game_of_life.poll_stats(&device);
+    assert!(!game_of_life.stats_pending());
+    assert!(!packed_game_of_life.stats_pending());
+    assert_eq!(game_of_life.take_stats(), expected);
+    assert_eq!(untracked_game_of_life.take_stats(), expected);
+    assert_eq!(packed_game_of_life.take_stats(), expected);
+    assert_eq!(game_of_life.stats(), *expected.last().unwrap());
+    assert_eq!(packed_game_of_life.stats(), *expected.last().unwrap());
+    assert!(game_of_life.take_stats().is_empty());
+}
+
+#[test]
+fn toggles_and_writes_set_the_population() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = CpuBoard::with_cells(40, 6, &[(1, 1), (2, 1), (3, 1)]);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    let mut packed_game_of_life = common::packed_game_of_life(&device, &queue, &board);
+    assert_eq!(game_of_life.stats().population, 3);
+    assert_eq!(packed_game_of_life.stats().population, 3);
+
+    let mut toggled = board.clone();
+    for (x, y) in [(35, 4), (36, 4), (1, 1)] {
+        game_of_life.toggle(x, y, &device, &queue);
+        packed_game_of_life.toggle(x, y, &device, &queue);
+        toggled.toggle(x as u32, y as u32);
+    }
+    device.poll(wgpu::Maintain::Wait);
+    game_of_life.poll_stats(&device);
+    packed_game_of_life.poll_stats(&device);
+    assert_eq!(game_of_life.stats().population, 4);
+    assert_eq!(packed_game_of_life.stats().population, 4);
+    assert_eq!(game_of_life.stats().hash, board_hash(&toggled));
+    assert_eq!(packed_game_of_life.stats().hash, board_hash(&toggled));
+    // Every toggle is in the stats, after the board written first
+    assert_eq!(game_of_life.take_stats().len(), 4);
+
+    // Counting carries on from a board written over a running one
+    let board = common::random_board(40, 6, 0.5, 5, Rule::CONWAY, Topology::PLANE);
+    game_of_life.write_board(&queue, &board);
+    packed_game_of_life.write_board(&queue, &board);
+    let mut expected = board.clone();
+    step(&mut expected, 1);
+    game_of_life.compute(&device, &queue);
+    packed_game_of_life.compute(&device, &queue);
+    device.poll(wgpu::Maintain::Wait);
+    game_of_life.poll_stats(&device);
+    packed_game_of_life.poll_stats(&device);
+    assert_eq!(game_of_life.stats().population, expected.population() as u64);
+    assert_eq!(packed_game_of_life.stats().population, expected.population() as u64);
+    assert_eq!(game_of_life.stats().hash, board_hash(&expected));
+    assert_eq!(packed_game_of_life.stats().hash, board_hash(&expected));
+}
+
+#[test]
+fn gpu_hashes_find_cycles() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    // A blinker next to a block, across the boundary between two packed words
+    let board = CpuBoard::with_cells(50, 20, &[(30, 5), (31, 5), (32, 5), (40, 10), (41, 10), (40, 11), (41, 11)]);
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    let mut packed_game_of_life = common::packed_game_of_life(&device, &queue, &board);
+    game_of_life.compute_n(&device, &queue, 4);
+    packed_game_of_life.compute_n(&device, &queue, 4);
+    device.poll(wgpu::Maintain::Wait);
+    game_of_life.poll_stats(&device);
+    packed_game_of_life.poll_stats(&device);
+
+    for stats in [game_of_life.take_stats(), packed_game_of_life.take_stats()] {
+        let mut detector = CycleDetector::new(100);
+        let found: Vec<_> = stats.into_iter().filter_map(|stats| detector.push(stats)).collect();
+        assert_eq!(found, vec![Cycle { start: 0, period: 2, exact: true }]);
+    }
+}
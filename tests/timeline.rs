@@ -0,0 +1,54 @@
+use bui::rect::SizeAndCenter;
+use bui_basic::{containers::Fill, signal::{SignalReciever, CursorMovedSignal, MouseLeftDownSignal, MouseLeftUpSignal}};
+use wgpu_game_of_life::timeline::{Timeline, TimelinePick};
+
+fn move_to(timeline: &mut Timeline, x: f32, y: f32) -> bool {
+    timeline.take_signal(&mut CursorMovedSignal {
+        pixel_posx: 0.0,
+        pixel_posy: 0.0,
+        norm_posx: x,
+        norm_posy: y,
+    })
+}
+
+fn timeline() -> Timeline {
+    let mut timeline = Timeline::new();
+    // Spans x from -0.5 to 0.5 and y from -0.1 to 0.1
+    timeline.fill(SizeAndCenter {
+        sx: 0.5,
+        sy: 0.1,
+        cx: 0.0,
+        cy: 0.0,
+    });
+    timeline.set_generations(100, 200, 180, vec![125, 190]);
+    timeline
+}
+
+#[test]
+fn dragging_picks_a_generation() {
+    let mut timeline = timeline();
+    move_to(&mut timeline, 0.1, 0.0);
+    assert!(timeline.take_signal(&mut MouseLeftDownSignal()));
+    assert_eq!(timeline.hovered(), Some(TimelinePick::Generation(160)));
+    assert!(move_to(&mut timeline, -0.9, 0.5));
+    assert_eq!(timeline.hovered(), Some(TimelinePick::Generation(100)));
+    assert_eq!(timeline.take_signal(&mut MouseLeftUpSignal()), Some(TimelinePick::Generation(100)));
+    assert!(!timeline.is_dragging());
+}
+
+#[test]
+fn clicking_near_a_bookmark_picks_it() {
+    let mut timeline = timeline();
+    move_to(&mut timeline, 0.405, 0.05);
+    assert!(timeline.take_signal(&mut MouseLeftDownSignal()));
+    assert_eq!(timeline.take_signal(&mut MouseLeftUpSignal()), Some(TimelinePick::Bookmark(1)));
+}
+
+#[test]
+fn clicking_elsewhere_picks_nothing() {
+    let mut timeline = timeline();
+    move_to(&mut timeline, 0.0, 0.5);
+    assert!(!timeline.take_signal(&mut MouseLeftDownSignal()));
+    assert!(!move_to(&mut timeline, 0.0, 0.0));
+    assert_eq!(timeline.take_signal(&mut MouseLeftUpSignal()), None);
+}
@@ -0,0 +1,117 @@
+use wgpu_game_of_life::{cpu_board::CpuBoard, topology::{Edge, Topology, Neighbour}};
+
+const GLIDER: [(u32, u32); 5] = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+
+fn board(width: u32, height: u32, topology: Topology, alive: &[(u32, u32)]) -> CpuBoard {
+    let mut board = CpuBoard::with_cells(width, height, alive);
+    board.set_topology(topology);
+    board
+}
+
+#[test]
+fn parse() {
+    assert_eq!(Topology::parse("plane"), Ok(Topology::PLANE));
+    assert_eq!(Topology::parse(" Torus "), Ok(Topology::TORUS));
+    assert_eq!(Topology::parse("wrap/dead"), Ok(Topology::CYLINDER));
+    assert_eq!(Topology::parse("klein"), Ok(Topology::KLEIN_BOTTLE));
+    assert_eq!(Topology::parse("cross"), Ok(Topology::CROSS_SURFACE));
+    assert_eq!(Topology::parse("mirror / alive"), Ok(Topology { x: Edge::Mirror, y: Edge::Alive }));
+    assert!(Topology::parse("donut").is_err());
+    assert!(Topology::parse("wrap/bounce").is_err());
+}
+
+#[test]
+fn display_round_trips() {
+    for topology in [Topology::PLANE, Topology::TORUS, Topology::KLEIN_BOTTLE, Topology::CROSS_SURFACE, Topology::CYLINDER, Topology { x: Edge::Dead, y: Edge::Wrap }, Topology { x: Edge::Mirror, y: Edge::Alive }] {
+        assert_eq!(Topology::parse(&topology.to_string()), Ok(topology));
+    }
+}
+
+#[test]
+fn resolve() {
+    assert_eq!(Topology::PLANE.resolve(3, 3, 8, 6), Neighbour::Cell(3, 3));
+    assert_eq!(Topology::PLANE.resolve(-1, 3, 8, 6), Neighbour::Dead);
+    assert_eq!(Topology::TORUS.resolve(-1, -1, 8, 6), Neighbour::Cell(7, 5));
+    assert_eq!(Topology::TORUS.resolve(8, 6, 8, 6), Neighbour::Cell(0, 0));
+    assert_eq!(Topology::CYLINDER.resolve(8, 2, 8, 6), Neighbour::Cell(0, 2));
+    assert_eq!(Topology::CYLINDER.resolve(2, 6, 8, 6), Neighbour::Dead);
+    assert_eq!(Topology::KLEIN_BOTTLE.resolve(8, 1, 8, 6), Neighbour::Cell(0, 4));
+    assert_eq!(Topology::KLEIN_BOTTLE.resolve(1, -1, 8, 6), Neighbour::Cell(1, 5));
+    assert_eq!(Topology::CROSS_SURFACE.resolve(-1, 0, 8, 6), Neighbour::Cell(7, 5));
+    assert_eq!(Topology::CROSS_SURFACE.resolve(2, 6, 8, 6), Neighbour::Cell(5, 0));
+    // Corners resolve x first, then y
+    assert_eq!(Topology::CROSS_SURFACE.resolve(-1, -1, 8, 6), Neighbour::Cell(0, 0));
+    assert_eq!(Topology::KLEIN_BOTTLE.resolve(8, 6, 8, 6), Neighbour::Cell(0, 5));
+    let mirror = Topology { x: Edge::Mirror, y: Edge::Mirror };
+    assert_eq!(mirror.resolve(-1, 6, 8, 6), Neighbour::Cell(0, 5));
+    let alive = Topology { x: Edge::Alive, y: Edge::Dead };
+    assert_eq!(alive.resolve(8, 2, 8, 6), Neighbour::Alive);
+    assert_eq!(alive.resolve(2, 6, 8, 6), Neighbour::Dead);
+    assert_eq!(alive.resolve(8, 6, 8, 6), Neighbour::Alive);
+}
+
+#[test]
+fn glider_survives_a_lap_of_the_torus() {
+    let start = board(8, 8, Topology::TORUS, &GLIDER);
+    let mut glider = start.clone();
+    for _ in 0..8*4 {
+        glider.step();
+        assert_eq!(glider.population(), 5);
+    }
+    assert_eq!(glider, start);
+}
+
+#[test]
+fn glider_dies_on_the_plane() {
+    let mut glider = board(8, 8, Topology::PLANE, &GLIDER);
+    glider.step_n(8*4);
+    assert_ne!(glider.population(), 5);
+}
+
+#[test]
+fn blinker_across_wrapped_edge() {
+    let mut blinker = board(5, 5, Topology { x: Edge::Wrap, y: Edge::Dead }, &[(4, 2), (0, 2), (1, 2)]);
+    blinker.step();
+    assert_eq!(blinker, CpuBoard::with_cells(5, 5, &[(0, 1), (0, 2), (0, 3)]));
+    blinker.step();
+    assert_eq!(blinker, CpuBoard::with_cells(5, 5, &[(4, 2), (0, 2), (1, 2)]));
+}
+
+#[test]
+fn blinker_across_flipped_edge() {
+    // Past the right edge of a Klein bottle, y is mirrored: (5, 1) is (0, 3)
+    let horizontal = board(5, 5, Topology::KLEIN_BOTTLE, &[(3, 1), (4, 1), (0, 3)]);
+    let mut blinker = horizontal.clone();
+    blinker.step();
+    assert_eq!(blinker, CpuBoard::with_cells(5, 5, &[(4, 0), (4, 1), (4, 2)]));
+    blinker.step();
+    assert_eq!(blinker, horizontal);
+}
+
+#[test]
+fn domino_against_mirror_is_a_block() {
+    let domino = board(5, 5, Topology { x: Edge::Mirror, y: Edge::Dead }, &[(0, 1), (0, 2)]);
+    let mut board = domino.clone();
+    board.step_n(3);
+    assert_eq!(board, domino);
+}
+
+#[test]
+fn alive_edges_give_birth_along_the_border() {
+    let mut board = board(5, 5, Topology { x: Edge::Alive, y: Edge::Alive }, &[]);
+    board.step();
+    // Corners see five live cells past the edges, so only the middle of each edge is born
+    let border = [(1, 0), (2, 0), (3, 0), (1, 4), (2, 4), (3, 4), (0, 1), (0, 2), (0, 3), (4, 1), (4, 2), (4, 3)];
+    assert_eq!(board, CpuBoard::with_cells(5, 5, &border));
+}
+
+#[test]
+fn cylinder_wraps_only_sideways() {
+    let across_top = [(2, 4), (2, 0), (2, 1)];
+    let mut cylinder = board(5, 5, Topology::CYLINDER, &across_top);
+    let mut torus = board(5, 5, Topology::TORUS, &across_top);
+    cylinder.step();
+    torus.step();
+    assert_eq!(cylinder, CpuBoard::new(5, 5));
+    assert_eq!(torus, CpuBoard::with_cells(5, 5, &[(1, 0), (2, 0), (3, 0)]));
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use wgpu_game_of_life::{cpu_board::CpuBoard, game::Storage, rule::Rule, topology::Topology, trails::{check_size, opacity, MAX_TRAIL_LENGTH}};
+
+#[test]
+fn trails_fade_with_age() {
+    assert_eq!(opacity(0, 4), 1.0);
+    assert_eq!(opacity(1, 4), 0.8);
+    assert!(opacity(4, 4) > 0.0);
+    for age in 1..MAX_TRAIL_LENGTH {
+        assert!(opacity(age+1, MAX_TRAIL_LENGTH) < opacity(age, MAX_TRAIL_LENGTH));
+    }
+}
+
+#[test]
+fn trails_fit_in_budget() {
+    assert!(check_size(1024, 1024, wgpu::TextureFormat::R32Uint, MAX_TRAIL_LENGTH).is_ok());
+    assert!(check_size(8192, 8192, wgpu::TextureFormat::R8Uint, 8).is_ok());
+    assert!(check_size(8192, 8192, wgpu::TextureFormat::R8Uint, 16).is_err());
+    assert!(check_size(8192, 8192, wgpu::TextureFormat::R32Uint, 16).is_err());
+}
+
+#[test]
+fn trails_hold_past_generations() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = common::random_board(30, 20, 0.4, 5, Rule::CONWAY, Topology::TORUS);
+    let mut past = vec![board.clone()];
+    for _ in 0..13 {
+        let mut next = past.last().unwrap().clone();
+        next.step();
+        past.push(next);
+    }
+    let mut game_of_life = common::game_of_life(&device, &queue, &board);
+    game_of_life.set_trail_length(&device, &queue, 4).unwrap();
+    assert!(game_of_life.read_trail(&device, &queue).is_empty());
+
+    // Newest first, and only as many as have been computed
+    game_of_life.compute_n(&device, &queue, 3);
+    let trail = game_of_life.read_trail(&device, &queue);
+    assert_eq!(trail.len(), 3);
+    for (i, generation) in trail.iter().enumerate() {
+        assert_eq!(generation.alive_cells(), past[2-i].alive_cells(), "generation {}", 2-i);
+    }
+
+    // Batches of any size keep the ring in order as it wraps around
+    for generations in [7, 1, 2] {
+        game_of_life.compute_n(&device, &queue, generations);
+    }
+    let trail = game_of_life.read_trail(&device, &queue);
+    assert_eq!(trail.len(), 4);
+    for (i, generation) in trail.iter().enumerate() {
+        assert_eq!(generation.alive_cells(), past[12-i].alive_cells(), "generation {}", 12-i);
+    }
+}
+
+#[test]
+fn only_texture_boards_have_trails() {
+    let (device, queue) = match common::device() {
+        Some(device) => device,
+        None => return,
+    };
+    let board = CpuBoard::new(20, 10);
+    let mut texture = Storage::Texture(common::game_of_life(&device, &queue, &board));
+    texture.set_trail_length(&device, &queue, 8).unwrap();
+    assert_eq!(texture.trail_length(), 8);
+    assert!(texture.set_trail_length(&device, &queue, MAX_TRAIL_LENGTH+1).is_err());
+    assert_eq!(texture.trail_length(), 8);
+    texture.compute_n(&device, &queue, 20).unwrap();
+    texture.set_trail_length(&device, &queue, 0).unwrap();
+    assert_eq!(texture.trail_length(), 0);
+
+    let mut packed = Storage::Packed(common::packed_game_of_life(&device, &queue, &board));
+    assert!(packed.set_trail_length(&device, &queue, 0).is_ok());
+    assert!(packed.set_trail_length(&device, &queue, 4).is_err());
+}